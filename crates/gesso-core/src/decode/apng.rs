@@ -0,0 +1,334 @@
+// Author: Dustin Pilgrim
+// License: MIT
+
+use std::io::Cursor;
+use std::time::Duration;
+
+use png::{BlendOp, ColorType, DisposeOp, Transformations};
+
+use crate::Colour;
+use crate::decode::{exif, icc, DecodeOptions, DecodedImage};
+use crate::decode::gif::rgba_canvas_to_xrgb_inplace;
+use crate::mem;
+use crate::render::scale::{scale_rgba_canvas_view_into, BlurScratch, Framing, ScaleMode};
+use crate::render::Viewport;
+
+/// `(num_frames, num_plays)` from the `acTL` chunk, if `data` is an APNG.
+pub fn animation_control(data: &[u8]) -> Option<(u32, u32)> {
+    let actl = super::exif::png_chunk(data, b"acTL")?;
+    let num_frames = u32::from_be_bytes(actl.get(0..4)?.try_into().ok()?);
+    let num_plays  = u32::from_be_bytes(actl.get(4..8)?.try_into().ok()?);
+    Some((num_frames, num_plays))
+}
+
+/// Decode just the first fully-rendered APNG frame as a still image,
+/// colour-managed and turned upright the same way as a still PNG.
+pub fn decode_apng_first_frame(data: &[u8], opts: DecodeOptions) -> Result<DecodedImage, String> {
+    let mut stream = ApngFrameStream::new(data, opts)?;
+    let n = stream.width as usize * stream.height as usize * 4;
+    let mut buf = vec![0u8; n];
+    match stream.next_frame_into(&mut buf) {
+        Some(Ok(_))  => {}
+        Some(Err(e)) => return Err(e),
+        None         => return Err("apng has no frames".to_string()),
+    }
+    let img = DecodedImage {
+        width:  stream.width,
+        height: stream.height,
+        stride: stream.width as usize * 4,
+        pixels: buf,
+    };
+    Ok(exif::orient(img, exif::png_exif(data)))
+}
+
+/// A streaming APNG decoder that yields composited full-canvas frames + delays.
+/// Same shape as `GifFrameStream`:
+///
+/// - ONE full-canvas RGBA compositing buffer (`canvas`)
+/// - `prev_canvas` allocated lazily only for `DisposeOp::Previous`
+/// - ONE subframe scratch buffer the png reader decodes into
+pub struct ApngFrameStream<'a> {
+    pub(crate) width:  u32,
+    pub(crate) height: u32,
+    reader: png::Reader<Cursor<&'a [u8]>>,
+
+    /// Full-canvas RGBA compositing buffer. Persists across frames.
+    canvas: Vec<u8>,
+
+    /// Saved canvas for `DisposeOp::Previous`.
+    prev_canvas: Option<Vec<u8>>,
+
+    /// Raw subframe output (RGBA8 or GrayAlpha8), sized for a full frame.
+    frame_buf: Vec<u8>,
+
+    /// iCCP → sRGB transform applied to each RGBA subframe before it is
+    /// composited. None without a usable profile or with colour management off.
+    icc:     Option<icc::Transform>,
+    icc_row: Vec<u8>,
+
+    /// EXIF orientation (2–8) for scaled output, and the upright copy of the
+    /// canvas it is written through. The 1:1 `next_frame_into` stays as stored.
+    orientation: Option<u8>,
+    upright:     Vec<u8>,
+
+    last_dispose: DisposeOp,
+    last_rect:    (u32, u32, u32, u32),
+
+    /// Animation frames not yet read in this pass.
+    remaining: u32,
+    first:     bool,
+}
+
+impl<'a> ApngFrameStream<'a> {
+    pub fn new(data: &'a [u8], opts: DecodeOptions) -> Result<Self, String> {
+        let mut decoder = png::Decoder::new(Cursor::new(data));
+        decoder.set_transformations(
+            Transformations::EXPAND | Transformations::STRIP_16 | Transformations::ALPHA,
+        );
+        let mut reader = decoder.read_info().map_err(|e| e.to_string())?;
+
+        let info      = reader.info();
+        let width     = info.width;
+        let height    = info.height;
+        let remaining = info
+            .animation_control
+            .map(|a| a.num_frames)
+            .ok_or_else(|| "png is not animated".to_string())?;
+        let icc = info
+            .icc_profile
+            .as_deref()
+            .filter(|_| opts.colour_manage)
+            .and_then(icc::rgba_transform);
+
+        // No fcTL before IDAT: the default image is a fallback for viewers
+        // that don't know APNG, not frame 0. Step over it.
+        if info.frame_control.is_none() {
+            reader.next_frame_info().map_err(|e| e.to_string())?;
+        }
+
+        let buf_len = reader
+            .output_buffer_size()
+            .ok_or_else(|| "apng could not determine output buffer size".to_string())?;
+        let n = width as usize * height as usize * 4;
+
+        Ok(Self {
+            width,
+            height,
+            reader,
+            canvas:       vec![0u8; n],
+            prev_canvas:  None,
+            frame_buf:    vec![0u8; buf_len],
+            icc,
+            icc_row:      Vec::new(),
+            orientation:  exif::orientation_of(exif::png_exif(data)),
+            upright:      Vec::new(),
+            last_dispose: DisposeOp::None,
+            last_rect:    (0, 0, 0, 0),
+            remaining,
+            first:        true,
+        })
+    }
+
+    #[inline] pub fn width(&self)  -> u32 { self.width  }
+    #[inline] pub fn height(&self) -> u32 { self.height }
+
+    // ── Public decode APIs ──────────────────────────────────────────────────
+
+    /// Advance the stream past the next frame without producing pixel output.
+    /// All compositing is still performed so the canvas stays correct.
+    pub fn skip_frame(&mut self) -> Option<Result<Duration, String>> {
+        self.step_frame()
+    }
+
+    /// Decode and composite the next frame into `out` as XRGB8888 (1:1, no scale).
+    /// `out` must be exactly `width * height * 4` bytes.
+    pub fn next_frame_into(&mut self, out: &mut [u8]) -> Option<Result<Duration, String>> {
+        let delay = match self.step_frame()? {
+            Ok(d)  => d,
+            Err(e) => return Some(Err(e)),
+        };
+        rgba_canvas_to_xrgb_inplace(&self.canvas, out);
+        Some(Ok(delay))
+    }
+
+    /// Decode, composite, scale, and convert into `out` (XRGB8888, out_w×out_h×4).
+    pub fn next_frame_scaled_into(
+        &mut self,
+        out:     &mut [u8],
+        out_w:   u32,
+        out_h:   u32,
+        view:    Viewport,
+        mode:    ScaleMode,
+        framing: Framing,
+        bg:      Colour,
+        blur:    &mut BlurScratch,
+    ) -> Option<Result<Duration, String>> {
+        let delay = match self.step_frame()? {
+            Ok(d)  => d,
+            Err(e) => return Some(Err(e)),
+        };
+        let (canvas, w, h) = match self.orientation {
+            Some(o) => {
+                let (w, h) = exif::orient_into(
+                    &self.canvas, self.width, self.height, self.width as usize * 4, o, &mut self.upright,
+                );
+                (&self.upright, w, h)
+            }
+            None => (&self.canvas, self.width, self.height),
+        };
+        scale_rgba_canvas_view_into(
+            canvas, w, h,
+            out, out_w, out_h,
+            view, mode, framing, bg, blur,
+        );
+        Some(Ok(delay))
+    }
+
+    /// Return the canvas pages to the OS right away; see
+    /// `GifFrameStream::release_canvas`.
+    pub fn release_canvas(&mut self) {
+        mem::pages_dontneed(&self.canvas);
+        mem::pages_dontneed(&self.frame_buf);
+        mem::pages_dontneed(&self.upright);
+        if let Some(ref pc) = self.prev_canvas {
+            mem::pages_dontneed(pc);
+        }
+        self.canvas.fill(0);
+        self.prev_canvas = None;
+    }
+
+    // ── Internal core ───────────────────────────────────────────────────────
+
+    fn step_frame(&mut self) -> Option<Result<Duration, String>> {
+        if self.remaining == 0 { return None; }
+
+        // ── 1. Apply previous disposal ──
+        match self.last_dispose {
+            DisposeOp::Background => {
+                let (x, y, w, h) = self.last_rect;
+                self.clear_rect(x, y, w, h);
+            }
+            DisposeOp::Previous => {
+                if let Some(prev) = self.prev_canvas.as_ref() {
+                    self.canvas.copy_from_slice(prev);
+                }
+            }
+            DisposeOp::None => {}
+        }
+
+        // ── 2. Read next raw subframe ──
+        let out = match self.reader.next_frame(&mut self.frame_buf) {
+            Ok(o)  => o,
+            Err(e) => return Some(Err(e.to_string())),
+        };
+        self.remaining -= 1;
+
+        let fc = match self.reader.info().frame_control {
+            Some(fc) => fc,
+            None     => return Some(Err("apng frame without fcTL".to_string())),
+        };
+
+        // delay_den 0 means 1/100 s units.
+        let den   = if fc.delay_den == 0 { 100 } else { fc.delay_den as u64 };
+        let delay = Duration::from_millis(fc.delay_num as u64 * 1000 / den);
+
+        // The spec treats Previous on the first frame as Background.
+        let mut dispose = fc.dispose_op;
+        if self.first && dispose == DisposeOp::Previous {
+            dispose = DisposeOp::Background;
+        }
+        self.first = false;
+
+        // ── 3. Save canvas before compositing if this frame uses Previous ──
+        if dispose == DisposeOp::Previous {
+            let prev = self.prev_canvas
+                .get_or_insert_with(|| vec![0u8; self.canvas.len()]);
+            prev.copy_from_slice(&self.canvas);
+        }
+
+        self.last_dispose = dispose;
+        self.last_rect    = (fc.x_offset, fc.y_offset, out.width, out.height);
+
+        // ── 4. Composite subframe onto canvas ──
+        let bpp = match out.color_type {
+            ColorType::Rgba           => 4,
+            ColorType::GrayscaleAlpha => 2,
+            other => return Some(Err(format!("apng unexpected output colour type: {other:?}"))),
+        };
+
+        let cw   = self.width  as usize;
+        let ch   = self.height as usize;
+        let left = fc.x_offset as usize;
+        let top  = fc.y_offset as usize;
+        let fw   = out.width   as usize;
+        let over = fc.blend_op == BlendOp::Over;
+
+        // Interlaced subframes are expanded at full-canvas row stride.
+        let stride = if self.reader.info().interlaced { cw * bpp } else { out.line_size };
+
+        if let Some(t) = self.icc.as_ref().filter(|_| bpp == 4) {
+            for y in 0..out.height as usize {
+                let row = &mut self.frame_buf[y * stride..y * stride + fw * 4];
+                icc::rgba_row_to_srgb(t, row, &mut self.icc_row);
+            }
+        }
+
+        for y in 0..out.height as usize {
+            let cy = top + y;
+            if cy >= ch { break; }
+            let src_row = &self.frame_buf[y * stride..y * stride + fw * bpp];
+
+            for x in 0..fw {
+                let cx = left + x;
+                if cx >= cw { break; }
+                let si = x * bpp;
+                let (r, g, b, a) = if bpp == 4 {
+                    (src_row[si], src_row[si + 1], src_row[si + 2], src_row[si + 3])
+                } else {
+                    (src_row[si], src_row[si], src_row[si], src_row[si + 1])
+                };
+                let di = (cy * cw + cx) * 4;
+                let dst = &mut self.canvas[di..di + 4];
+                if over {
+                    blend_over(dst, r, g, b, a);
+                } else {
+                    dst.copy_from_slice(&[r, g, b, a]);
+                }
+            }
+        }
+
+        Some(Ok(delay))
+    }
+
+    fn clear_rect(&mut self, x: u32, y: u32, w: u32, h: u32) {
+        let cw = self.width  as usize;
+        let ch = self.height as usize;
+        let x0 = (x as usize).min(cw);
+        let x1 = (x as usize + w as usize).min(cw);
+        if x1 <= x0 { return; }
+        for cy in y as usize..(y as usize + h as usize).min(ch) {
+            self.canvas[(cy * cw + x0) * 4..(cy * cw + x1) * 4].fill(0);
+        }
+    }
+}
+
+// ── Pixel helpers ───────────────────────────────────────────────────────────
+
+/// Straight-alpha "over" of (r,g,b,a) onto `dst` (RGBA).
+#[inline]
+fn blend_over(dst: &mut [u8], r: u8, g: u8, b: u8, a: u8) {
+    match a {
+        0   => {}
+        255 => dst.copy_from_slice(&[r, g, b, 255]),
+        _   => {
+            let sa    = a as u32;
+            let da    = dst[3] as u32 * (255 - sa) / 255;
+            let out_a = sa + da;
+            let mix   = |s: u8, d: u8| ((s as u32 * sa + d as u32 * da) / out_a) as u8;
+            dst[0] = mix(r, dst[0]);
+            dst[1] = mix(g, dst[1]);
+            dst[2] = mix(b, dst[2]);
+            dst[3] = out_a as u8;
+        }
+    }
+}
@@ -0,0 +1,140 @@
+// Author: Dustin Pilgrim
+// License: MIT
+
+use super::DecodedImage;
+
+/// EXIF Orientation tag.
+const TAG_ORIENTATION: u16 = 0x0112;
+
+/// Turn `img` upright according to the Orientation tag in `tiff` (a raw
+/// TIFF-structured EXIF block). Missing or unreadable EXIF leaves it as is.
+pub(crate) fn orient(img: DecodedImage, tiff: Option<&[u8]>) -> DecodedImage {
+    match orientation_of(tiff) {
+        Some(o) => apply(img, o),
+        None    => img,
+    }
+}
+
+/// Orientation 2–8 from `tiff`, or None when the image is already upright.
+pub(crate) fn orientation_of(tiff: Option<&[u8]>) -> Option<u8> {
+    tiff.and_then(orientation).filter(|o| (2..=8).contains(o))
+}
+
+// ── Locating metadata chunks ─────────────────────────────────────────────────
+
+/// Payload of the PNG `eXIf` chunk. Scanned by hand because the chunk may sit
+/// after IDAT, where the `png` reader has already stopped looking.
+pub(crate) fn png_exif(data: &[u8]) -> Option<&[u8]> {
+    png_chunk(data, b"eXIf")
+}
+
+/// Payload of the first PNG chunk named `kind`, up to IEND.
+pub(crate) fn png_chunk<'a>(data: &'a [u8], kind: &[u8; 4]) -> Option<&'a [u8]> {
+    let mut pos = 8;
+    while pos + 8 <= data.len() {
+        let len  = u32::from_be_bytes(data[pos..pos + 4].try_into().ok()?) as usize;
+        let name = &data[pos + 4..pos + 8];
+        let body = data.get(pos + 8..(pos + 8).checked_add(len)?)?;
+        if name == kind { return Some(body); }
+        if name == b"IEND" { return None; }
+        pos += 12 + len; // length + type + data + crc
+    }
+    None
+}
+
+/// Payload of the WebP `EXIF` chunk, minus the `Exif\0\0` prefix some
+/// writers leave on it.
+pub(crate) fn webp_exif(data: &[u8]) -> Option<&[u8]> {
+    let body = webp_chunk(data, b"EXIF")?;
+    Some(body.strip_prefix(b"Exif\0\0").unwrap_or(body))
+}
+
+/// Payload of the first RIFF chunk named `fourcc` in a WebP file.
+pub(crate) fn webp_chunk<'a>(data: &'a [u8], fourcc: &[u8; 4]) -> Option<&'a [u8]> {
+    let mut pos = 12;
+    while pos + 8 <= data.len() {
+        let kind = &data[pos..pos + 4];
+        let len  = u32::from_le_bytes(data[pos + 4..pos + 8].try_into().ok()?) as usize;
+        let body = data.get(pos + 8..(pos + 8).checked_add(len)?)?;
+        if kind == fourcc {
+            return Some(body);
+        }
+        pos += 8 + len + (len & 1); // chunks are padded to even length
+    }
+    None
+}
+
+// ── TIFF ──────────────────────────────────────────────────────────────────────
+
+/// Orientation (1–8) from IFD0 of a TIFF block, either byte order.
+fn orientation(tiff: &[u8]) -> Option<u8> {
+    let le = match tiff.get(..4)? {
+        [b'I', b'I', 42, 0] => true,
+        [b'M', b'M', 0, 42] => false,
+        _                   => return None,
+    };
+    let u16_at = |at: usize| -> Option<u16> {
+        let b: [u8; 2] = tiff.get(at..at + 2)?.try_into().ok()?;
+        Some(if le { u16::from_le_bytes(b) } else { u16::from_be_bytes(b) })
+    };
+    let u32_at = |at: usize| -> Option<u32> {
+        let b: [u8; 4] = tiff.get(at..at + 4)?.try_into().ok()?;
+        Some(if le { u32::from_le_bytes(b) } else { u32::from_be_bytes(b) })
+    };
+
+    let ifd   = u32_at(4)? as usize;
+    let count = u16_at(ifd)? as usize;
+    for i in 0..count {
+        let entry = ifd + 2 + i * 12;
+        if u16_at(entry)? != TAG_ORIENTATION { continue; }
+        // SHORT, count 1: the value sits in the first two bytes of the field.
+        let v = u16_at(entry + 8)?;
+        return (1..=8).contains(&v).then_some(v as u8);
+    }
+    None
+}
+
+// ── Pixel transform ───────────────────────────────────────────────────────────
+
+/// Rotate / flip so the image displays the way the camera meant it.
+/// Orientations 5–8 swap width and height.
+fn apply(img: DecodedImage, o: u8) -> DecodedImage {
+    let mut out = Vec::new();
+    let (ow, oh) = orient_into(&img.pixels, img.width, img.height, img.stride, o, &mut out);
+
+    DecodedImage {
+        width:  ow,
+        height: oh,
+        stride: ow as usize * 4,
+        pixels: out,
+    }
+}
+
+/// [`apply`] for any 4-byte-per-pixel buffer, written packed into `out`
+/// (resized to fit, so a caller can reuse it per frame). Returns the upright
+/// width and height.
+pub(crate) fn orient_into(src: &[u8], w: u32, h: u32, stride: usize, o: u8, out: &mut Vec<u8>) -> (u32, u32) {
+    let (w, h)   = (w as usize, h as usize);
+    let swap     = o >= 5;
+    let (ow, oh) = if swap { (h, w) } else { (w, h) };
+
+    out.resize(ow * oh * 4, 0);
+    for oy in 0..oh {
+        for ox in 0..ow {
+            let (sx, sy) = match o {
+                2 => (w - 1 - ox, oy),
+                3 => (w - 1 - ox, h - 1 - oy),
+                4 => (ox,         h - 1 - oy),
+                5 => (oy,         ox),
+                6 => (oy,         h - 1 - ox),
+                7 => (w - 1 - oy, h - 1 - ox),
+                _ => (w - 1 - oy, ox),
+            };
+            let si = sy * stride + sx * 4;
+            let di = (oy * ow + ox) * 4;
+            out[di..di + 4].copy_from_slice(&src[si..si + 4]);
+        }
+    }
+
+    (ow as u32, oh as u32)
+}
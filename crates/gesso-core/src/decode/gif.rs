@@ -9,7 +9,8 @@ use gif::DisposalMethod;
 use crate::Colour;
 use crate::decode::DecodedImage;
 use crate::mem;
-use crate::render::scale::{scale_rgba_canvas_into, ScaleMode};
+use crate::render::scale::{scale_rgba_canvas_view_into, BlurScratch, Framing, ScaleMode};
+use crate::render::Viewport;
 
 /// Decode just the first fully-rendered GIF frame as a still image.
 pub fn decode_gif_first_frame(data: &[u8]) -> Result<DecodedImage, String> {
@@ -29,6 +30,18 @@ pub fn decode_gif_first_frame(data: &[u8]) -> Result<DecodedImage, String> {
     })
 }
 
+/// Plays requested by the NETSCAPE2.0 loop extension: None = loop forever.
+///
+/// The stored count is repeats after the first play, as browsers read it, and
+/// a GIF without the extension plays once.
+pub fn gif_loop_count(data: &[u8]) -> Option<u16> {
+    let decoder = gif::DecodeOptions::new().read_info(Cursor::new(data)).ok()?;
+    match decoder.repeat() {
+        gif::Repeat::Infinite  => None,
+        gif::Repeat::Finite(n) => Some(n.saturating_add(1)),
+    }
+}
+
 /// A streaming GIF decoder that yields composited full-canvas frames + delays.
 ///
 /// Memory layout:
@@ -100,22 +113,27 @@ impl<'a> GifFrameStream<'a> {
     /// Decode, composite, scale, and convert into `out` (XRGB8888, out_w×out_h×4).
     ///
     /// Single-pass: no intermediate allocation.  Primary hot-path for GIF playback.
+    /// `view` selects this output's slice of a spanned canvas
+    /// (`Viewport::full(out_w, out_h)` for the normal case).
     pub fn next_frame_scaled_into(
         &mut self,
-        out:   &mut [u8],
-        out_w: u32,
-        out_h: u32,
-        mode:  ScaleMode,
-        bg:    Colour,
+        out:     &mut [u8],
+        out_w:   u32,
+        out_h:   u32,
+        view:    Viewport,
+        mode:    ScaleMode,
+        framing: Framing,
+        bg:      Colour,
+        blur:    &mut BlurScratch,
     ) -> Option<Result<Duration, String>> {
         let delay = match self.step_frame()? {
             Ok(d)  => d,
             Err(e) => return Some(Err(e)),
         };
-        scale_rgba_canvas_into(
+        scale_rgba_canvas_view_into(
             &self.canvas, self.width, self.height,
             out, out_w, out_h,
-            mode, bg,
+            view, mode, framing, bg, blur,
         );
         Some(Ok(delay))
     }
@@ -246,7 +264,7 @@ impl<'a> GifFrameStream<'a> {
 // ── Pixel format helpers ────────────────────────────────────────────────────
 
 #[inline]
-fn rgba_canvas_to_xrgb_inplace(rgba: &[u8], out: &mut [u8]) {
+pub(super) fn rgba_canvas_to_xrgb_inplace(rgba: &[u8], out: &mut [u8]) {
     debug_assert_eq!(rgba.len(), out.len());
     for (src, dst) in rgba.chunks_exact(4).zip(out.chunks_exact_mut(4)) {
         let r = src[0] as u16;
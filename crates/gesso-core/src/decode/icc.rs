@@ -0,0 +1,98 @@
+// Author: Dustin Pilgrim
+// License: MIT
+
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use moxcms::{ColorProfile, DataColorSpace, Layout, Transform8BitExecutor, TransformOptions};
+use rayon::prelude::*;
+
+use super::DecodedImage;
+
+/// Transforms kept around. Wallpapers tend to come from a handful of cameras,
+/// so a small cache covers playlists, restores and hotplug re-decodes.
+const CACHE_MAX: usize = 16;
+
+pub(crate) type Transform = Arc<Transform8BitExecutor>;
+
+/// Profile hash plus whether the transform is for RGBA rather than RGB.
+type Key = (u64, bool);
+
+/// Key → transform to sRGB, or None for profiles we can't use (so a bad
+/// profile isn't re-parsed on every decode either).
+static CACHE: OnceLock<Mutex<HashMap<Key, Option<Transform>>>> = OnceLock::new();
+
+/// Convert `img` from its embedded ICC `profile` to sRGB in place. Profiles
+/// that don't parse, or aren't RGB, leave the pixels as stored.
+pub(crate) fn to_srgb(img: &mut DecodedImage, profile: &[u8]) {
+    let Some(t) = transform_for(profile, Layout::Rgb) else { return };
+
+    let w = img.width as usize;
+    if w == 0 || img.stride < w * 4 { return; }
+
+    // moxcms wants packed RGB; we hold B,G,R,0. Swizzle one row at a time
+    // through per-thread scratch so the full image is never copied.
+    img.pixels.par_chunks_mut(img.stride).for_each_init(
+        || (vec![0u8; w * 3], vec![0u8; w * 3]),
+        |(src, dst), row| {
+            for x in 0..w {
+                src[x * 3]     = row[x * 4 + 2];
+                src[x * 3 + 1] = row[x * 4 + 1];
+                src[x * 3 + 2] = row[x * 4];
+            }
+            if t.transform(src, dst).is_err() { return; }
+            for x in 0..w {
+                row[x * 4]     = dst[x * 3 + 2];
+                row[x * 4 + 1] = dst[x * 3 + 1];
+                row[x * 4 + 2] = dst[x * 3];
+            }
+        },
+    );
+}
+
+/// Transform for packed RGBA rows, for animations converted frame by frame
+/// with [`rgba_row_to_srgb`]. Alpha passes through untouched.
+pub(crate) fn rgba_transform(profile: &[u8]) -> Option<Transform> {
+    transform_for(profile, Layout::Rgba)
+}
+
+/// Convert one packed RGBA row in place; `scratch` is grown to fit.
+pub(crate) fn rgba_row_to_srgb(t: &Transform, row: &mut [u8], scratch: &mut Vec<u8>) {
+    scratch.resize(row.len(), 0);
+    if t.transform(row, scratch).is_ok() {
+        row.copy_from_slice(scratch);
+    }
+}
+
+/// Cached lookup; builds (and remembers) the transform on a miss.
+fn transform_for(profile: &[u8], layout: Layout) -> Option<Transform> {
+    let key = {
+        let mut h = DefaultHasher::new();
+        profile.hash(&mut h);
+        (h.finish(), layout == Layout::Rgba)
+    };
+
+    let cache = CACHE.get_or_init(Default::default);
+    if let Some(hit) = cache.lock().ok()?.get(&key) {
+        return hit.clone();
+    }
+
+    // Built outside the lock: a big LUT profile can take a few ms.
+    let built = build(profile, layout);
+    if let Ok(mut map) = cache.lock() {
+        if map.len() >= CACHE_MAX { map.clear(); }
+        map.insert(key, built.clone());
+    }
+    built
+}
+
+fn build(profile: &[u8], layout: Layout) -> Option<Transform> {
+    let src = ColorProfile::new_from_slice(profile).ok()?;
+    if src.color_space != DataColorSpace::Rgb { return None; }
+
+    let srgb = ColorProfile::new_srgb();
+    src.create_transform_8bit(layout, &srgb, layout, TransformOptions::default())
+        .ok()
+        .map(Arc::from)
+}
@@ -1,9 +1,9 @@
 // Author: Dustin Pilgrim
 // License: MIT
 
-use super::DecodedImage;
+use super::{exif, icc, DecodeOptions, DecodedImage};
 
-pub fn decode_jpeg(data: &[u8]) -> Result<DecodedImage, String> {
+pub fn decode_jpeg(data: &[u8], opts: DecodeOptions) -> Result<DecodedImage, String> {
     use jpeg_decoder::Decoder;
 
     let mut dec = Decoder::new(data);
@@ -91,10 +91,17 @@ pub fn decode_jpeg(data: &[u8]) -> Result<DecodedImage, String> {
         }
     }
 
-    Ok(DecodedImage {
+    let mut img = DecodedImage {
         width,
         height,
         stride: out_stride,
         pixels: out,
-    })
+    };
+
+    if let Some(profile) = dec.icc_profile().filter(|_| opts.colour_manage) {
+        icc::to_srgb(&mut img, &profile);
+    }
+
+    // Phone photos are stored sensor-up; EXIF says how to turn them.
+    Ok(exif::orient(img, dec.exif_data()))
 }
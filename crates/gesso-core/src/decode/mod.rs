@@ -5,9 +5,13 @@ use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 
+mod exif;
+mod icc;
 mod png;
 mod jpeg;
+pub mod apng;
 pub mod gif;
+pub mod stream;
 pub mod webp;
 
 #[derive(Debug, thiserror::Error)]
@@ -26,6 +30,26 @@ pub enum DecodeError {
     WebP(String),
 }
 
+/// Per-call decode switches.
+#[derive(Debug, Clone, Copy)]
+pub struct DecodeOptions {
+    /// Convert embedded ICC profiles (PNG iCCP, JPEG APP2, WebP ICCP) to sRGB.
+    /// Off = use the stored pixel values as they are.
+    pub colour_manage: bool,
+    /// Most bytes an animated WebP may take fully decoded (canvas × frames).
+    /// Larger animations keep only the first frame and stream the rest.
+    pub anim_memory_limit: usize,
+}
+
+/// Default for `DecodeOptions::anim_memory_limit`.
+pub const ANIM_MEMORY_LIMIT: usize = 64 << 20;
+
+impl Default for DecodeOptions {
+    fn default() -> Self {
+        Self { colour_manage: true, anim_memory_limit: ANIM_MEMORY_LIMIT }
+    }
+}
+
 #[derive(Clone)]
 pub struct DecodedImage {
     pub width:  u32,
@@ -34,7 +58,7 @@ pub struct DecodedImage {
     pub pixels: Vec<u8>, // XRGB8888: B,G,R,0
 }
 
-/// A single animation frame shared between GIF and animated WebP.
+/// A single pre-decoded animation frame (animated WebP under the memory limit).
 pub struct AnimFrame {
     pub img:   DecodedImage,
     pub delay: Duration,
@@ -47,20 +71,22 @@ pub enum Decoded {
 }
 
 pub struct AnimDecoded {
-    /// Raw source bytes for the streaming GIF decoder.
-    /// `None` for animated WebP (frames are pre-decoded eagerly).
+    /// Raw source bytes for the streaming decoder (`FrameStream`).
+    /// `None` when `frames` holds every frame (animated WebP under the limit).
     pub data:        Option<Arc<Vec<u8>>>,
     /// First frame, ready to display immediately.
     pub first_frame: DecodedImage,
-    /// All frames — populated for WebP; empty for GIF (streaming decoder used).
+    /// All frames — populated for small WebPs; empty when streaming.
     pub frames:      Vec<AnimFrame>,
     /// None = loop forever.  Some(n) = play n times.
     pub loop_count:  Option<u16>,
+    /// Options `first_frame` was decoded with; pass them on to `FrameStream`.
+    pub opts:        DecodeOptions,
 }
 
 // ── Magic-byte detectors ──────────────────────────────────────────────────────
 
-fn is_png(buf: &[u8]) -> bool {
+pub(crate) fn is_png(buf: &[u8]) -> bool {
     buf.len() >= 8 && buf[..8] == [137, 80, 78, 71, 13, 10, 26, 10]
 }
 
@@ -73,23 +99,39 @@ fn is_gif(buf: &[u8]) -> bool {
 }
 
 /// WebP: RIFF container with "WEBP" at offset 8.
-fn is_webp(buf: &[u8]) -> bool {
+pub(crate) fn is_webp(buf: &[u8]) -> bool {
     buf.len() >= 12 && &buf[..4] == b"RIFF" && &buf[8..12] == b"WEBP"
 }
 
 // ── Public API ────────────────────────────────────────────────────────────────
 
 pub fn decode(path: &Path) -> Result<Decoded, DecodeError> {
+    decode_with(path, DecodeOptions::default())
+}
+
+pub fn decode_with(path: &Path, opts: DecodeOptions) -> Result<Decoded, DecodeError> {
     let data = std::fs::read(path)?;
 
     if is_png(&data) {
-        return png::decode_png(&data)
+        // APNG with a single frame is just its default image.
+        if let Some((_, plays)) = apng::animation_control(&data).filter(|&(n, _)| n > 1) {
+            let data  = Arc::new(data);
+            let first = apng::decode_apng_first_frame(&data, opts).map_err(DecodeError::Png)?;
+            return Ok(Decoded::Animated(AnimDecoded {
+                data:        Some(data),
+                first_frame: first,
+                frames:      Vec::new(), // APNG streams like GIF
+                loop_count:  (plays > 0).then(|| plays.min(u16::MAX as u32) as u16),
+                opts,
+            }));
+        }
+        return png::decode_png(&data, opts)
             .map(Decoded::Still)
             .map_err(DecodeError::Png);
     }
 
     if is_jpeg(&data) {
-        return jpeg::decode_jpeg(&data)
+        return jpeg::decode_jpeg(&data, opts)
             .map(Decoded::Still)
             .map_err(DecodeError::Jpeg);
     }
@@ -97,32 +139,35 @@ pub fn decode(path: &Path) -> Result<Decoded, DecodeError> {
     if is_gif(&data) {
         let data  = Arc::new(data);
         let first = gif::decode_gif_first_frame(&data).map_err(DecodeError::Gif)?;
+        let loops = gif::gif_loop_count(&data);
         return Ok(Decoded::Animated(AnimDecoded {
             data:        Some(data),
             first_frame: first,
             frames:      Vec::new(), // GIF uses streaming decoder, no pre-decoded frames
-            loop_count:  None,
+            loop_count:  loops,
+            opts,
         }));
     }
 
     if is_webp(&data) {
-        return decode_webp_inner(data).map_err(DecodeError::WebP);
+        return decode_webp_inner(data, opts).map_err(DecodeError::WebP);
     }
 
     Err(DecodeError::Unsupported)
 }
 
-fn decode_webp_inner(data: Vec<u8>) -> Result<Decoded, String> {
-    match webp::decode_webp(&data)? {
+fn decode_webp_inner(data: Vec<u8>, opts: DecodeOptions) -> Result<Decoded, String> {
+    match webp::decode_webp(&data, opts)? {
         webp::WebpDecoded::Still(img) => Ok(Decoded::Still(img)),
         webp::WebpDecoded::Animated(anim) => {
-            let first_frame = anim.first_frame.clone();
-            let frames      = webp::into_anim_frames(anim);
+            // No frames held = over the memory limit; stream from the bytes.
+            let data = anim.frames.is_empty().then(|| Arc::new(data));
             Ok(Decoded::Animated(AnimDecoded {
-                data:        None,
-                first_frame,
-                frames,
-                loop_count:  None,
+                data,
+                first_frame: anim.first_frame,
+                frames:      anim.frames,
+                loop_count:  anim.loop_count,
+                opts,
             }))
         }
     }
@@ -131,7 +176,11 @@ fn decode_webp_inner(data: Vec<u8>) -> Result<Decoded, String> {
 /// Decode and return a single still image.
 /// For animated formats, returns only the first frame.
 pub fn decode_image(path: &Path) -> Result<DecodedImage, DecodeError> {
-    match decode(path)? {
+    decode_image_with(path, DecodeOptions::default())
+}
+
+pub fn decode_image_with(path: &Path, opts: DecodeOptions) -> Result<DecodedImage, DecodeError> {
+    match decode_with(path, opts)? {
         Decoded::Still(img)  => Ok(img),
         Decoded::Animated(a) => Ok(a.first_frame),
     }
@@ -1,9 +1,9 @@
 // Author: Dustin Pilgrim
 // License: MIT
 
-use super::DecodedImage;
+use super::{exif, icc, DecodeOptions, DecodedImage};
 
-pub fn decode_png(data: &[u8]) -> Result<DecodedImage, String> {
+pub fn decode_png(data: &[u8], opts: DecodeOptions) -> Result<DecodedImage, String> {
     use std::io::Cursor;
 
     let decoder = png::Decoder::new(Cursor::new(data));
@@ -128,10 +128,16 @@ pub fn decode_png(data: &[u8]) -> Result<DecodedImage, String> {
         }
     }
 
-    Ok(DecodedImage {
+    let mut img = DecodedImage {
         width,
         height,
         stride: out_stride,
         pixels: out,
-    })
+    };
+
+    if let Some(profile) = reader.info().icc_profile.as_deref().filter(|_| opts.colour_manage) {
+        icc::to_srgb(&mut img, profile);
+    }
+
+    Ok(exif::orient(img, exif::png_exif(data)))
 }
@@ -0,0 +1,68 @@
+// Author: Dustin Pilgrim
+// License: MIT
+
+use std::time::Duration;
+
+use crate::Colour;
+use crate::decode::DecodeOptions;
+use crate::decode::apng::ApngFrameStream;
+use crate::decode::gif::GifFrameStream;
+use crate::decode::webp::WebpFrameStream;
+use crate::render::scale::{BlurScratch, Framing, ScaleMode};
+use crate::render::Viewport;
+
+/// Streaming decoder over the raw bytes of an animated GIF, APNG or WebP.
+/// The format is picked from the magic bytes; each composes frames onto one
+/// RGBA canvas and hands out scaled XRGB frames on demand.
+pub enum FrameStream<'a> {
+    Gif(Box<GifFrameStream<'a>>),
+    Apng(Box<ApngFrameStream<'a>>),
+    Webp(WebpFrameStream<'a>),
+}
+
+impl<'a> FrameStream<'a> {
+    /// `opts` matters for APNG and WebP; GIF has no colour profile.
+    pub fn new(data: &'a [u8], opts: DecodeOptions) -> Result<Self, String> {
+        if super::is_png(data) {
+            ApngFrameStream::new(data, opts).map(|s| Self::Apng(Box::new(s)))
+        } else if super::is_webp(data) {
+            WebpFrameStream::new(data, opts).map(Self::Webp)
+        } else {
+            GifFrameStream::new(data).map(|s| Self::Gif(Box::new(s)))
+        }
+    }
+
+    pub fn skip_frame(&mut self) -> Option<Result<Duration, String>> {
+        match self {
+            Self::Gif(s)  => s.skip_frame(),
+            Self::Apng(s) => s.skip_frame(),
+            Self::Webp(s) => s.skip_frame(),
+        }
+    }
+
+    pub fn next_frame_scaled_into(
+        &mut self,
+        out:     &mut [u8],
+        out_w:   u32,
+        out_h:   u32,
+        view:    Viewport,
+        mode:    ScaleMode,
+        framing: Framing,
+        bg:      Colour,
+        blur:    &mut BlurScratch,
+    ) -> Option<Result<Duration, String>> {
+        match self {
+            Self::Gif(s)  => s.next_frame_scaled_into(out, out_w, out_h, view, mode, framing, bg, blur),
+            Self::Apng(s) => s.next_frame_scaled_into(out, out_w, out_h, view, mode, framing, bg, blur),
+            Self::Webp(s) => s.next_frame_scaled_into(out, out_w, out_h, view, mode, framing, bg, blur),
+        }
+    }
+
+    pub fn release_canvas(&mut self) {
+        match self {
+            Self::Gif(s)  => s.release_canvas(),
+            Self::Apng(s) => s.release_canvas(),
+            Self::Webp(s) => s.release_canvas(),
+        }
+    }
+}
@@ -1,9 +1,17 @@
 // Author: Dustin Pilgrim
 // License: MIT
 
+use std::marker::PhantomData;
+use std::ptr::NonNull;
 use std::time::Duration;
 
-use crate::decode::{AnimFrame, DecodedImage};
+use libwebp_sys as sys;
+
+use crate::Colour;
+use crate::decode::{exif, icc, AnimFrame, DecodeOptions, DecodedImage};
+use crate::mem;
+use crate::render::scale::{scale_rgba_canvas_view_into, BlurScratch, Framing, ScaleMode};
+use crate::render::Viewport;
 
 pub enum WebpDecoded {
     Still(DecodedImage),
@@ -12,22 +20,25 @@ pub enum WebpDecoded {
 
 pub struct WebpAnimation {
     pub first_frame: DecodedImage,
-    pub frames:      Vec<WebpFrame>,
-}
-
-pub struct WebpFrame {
-    pub img:   DecodedImage,
-    pub delay: Duration,
+    /// Every frame when they fit in `DecodeOptions::anim_memory_limit`;
+    /// empty when playback should stream from the file (`WebpFrameStream`).
+    pub frames:      Vec<AnimFrame>,
+    /// None = loop forever.  Some(n) = play n times.
+    pub loop_count:  Option<u16>,
 }
 
 /// Decode a WebP file using libwebp (via the `webp` crate).
 ///
 /// Cargo.toml: `webp = "0.3"`
-pub fn decode_webp(data: &[u8]) -> Result<WebpDecoded, String> {
-    match try_animated(data) {
-        Ok(Some(anim)) => return Ok(WebpDecoded::Animated(anim)),
-        Ok(None)       => {}
-        Err(_)         => {}
+pub fn decode_webp(data: &[u8], opts: DecodeOptions) -> Result<WebpDecoded, String> {
+    // Only files with an ANIM chunk go near the animation decoder: it sets up
+    // two full canvases up front, which a big still has no use for.
+    if exif::webp_chunk(data, b"ANIM").is_some() {
+        match try_animated(data, opts) {
+            Ok(Some(anim)) => return Ok(WebpDecoded::Animated(anim)),
+            Ok(None)       => {}
+            Err(_)         => {}
+        }
     }
 
     // Static path.
@@ -55,84 +66,223 @@ pub fn decode_webp(data: &[u8]) -> Result<WebpDecoded, String> {
         }
     };
 
-    Ok(WebpDecoded::Still(DecodedImage {
+    let mut img = DecodedImage {
         width,
         height,
         stride: width as usize * 4,
         pixels,
-    }))
-}
+    };
 
-/// Convert a `WebpAnimation` into the shared `Vec<AnimFrame>` type.
-pub fn into_anim_frames(anim: WebpAnimation) -> Vec<AnimFrame> {
-    anim.frames
-        .into_iter()
-        .map(|f| AnimFrame { img: f.img, delay: f.delay })
-        .collect()
-}
+    if let Some(profile) = exif::webp_chunk(data, b"ICCP").filter(|_| opts.colour_manage) {
+        icc::to_srgb(&mut img, profile);
+    }
 
-fn try_animated(data: &[u8]) -> Result<Option<WebpAnimation>, String> {
-    let anim = match webp::AnimDecoder::new(data).decode() {
-        Ok(a)  => a,
-        Err(_) => return Ok(None),
-    };
+    Ok(WebpDecoded::Still(exif::orient(img, exif::webp_exif(data))))
+}
 
-    if anim.len() <= 1 {
+fn try_animated(data: &[u8], opts: DecodeOptions) -> Result<Option<WebpAnimation>, String> {
+    let mut stream = WebpFrameStream::new(data, opts)?;
+    if stream.frame_count <= 1 {
         // Single frame — treat as still so the static path handles it.
         return Ok(None);
     }
 
-    let mut frames: Vec<WebpFrame> = Vec::with_capacity(anim.len());
-    let mut prev_ts: i32 = 0;
-
-    for (i, frame) in anim.into_iter().enumerate() {
-        let ts_ms    = frame.get_time_ms();
-        let delay_ms = if i == 0 {
-            ts_ms.max(20)
-        } else {
-            (ts_ms - prev_ts).max(20)
-        };
-        prev_ts = ts_ms;
-
-        let delay  = Duration::from_millis(delay_ms as u64);
-        let width  = frame.width();
-        let height = frame.height();
+    let (width, height) = (stream.width, stream.height);
+    let loop_count      = stream.loop_count();
 
-        // IMPORTANT:
-        // webp::AnimFrame does NOT deref to pixels.
-        // Use get_image() and get_layout() to handle RGB vs RGBA correctly.
-        let img = frame.get_image();
+    // Holding every frame costs a full canvas each. Past the limit, keep just
+    // the first one and let the player stream the rest from the file.
+    let canvas   = width as usize * height as usize * 4;
+    let hold_all = canvas.saturating_mul(stream.frame_count as usize) <= opts.anim_memory_limit;
 
-        let pixels = match frame.get_layout() {
-            webp::PixelLayout::Rgba => rgba8_to_xrgb(img),
-            webp::PixelLayout::Rgb  => rgb8_to_xrgb(img),
-        };
-
-        // Sanity: after conversion we must be XRGB8888.
-        let expected = width as usize * height as usize * 4;
-        if pixels.len() != expected {
-            return Err(format!(
-                "libwebp: frame buffer size mismatch after conversion: got={} expected={} ({}x{})",
-                pixels.len(),
-                expected,
-                width,
-                height
-            ));
-        }
-
-        frames.push(WebpFrame {
+    let mut frames = Vec::new();
+    while let Some(next) = stream.step_frame() {
+        let (delay, rgba) = next?;
+        frames.push(AnimFrame {
             img: DecodedImage {
                 width,
                 height,
                 stride: width as usize * 4,
-                pixels,
+                pixels: rgba8_to_xrgb(rgba),
             },
             delay,
         });
+        if !hold_all { break; }
     }
 
-    let first_frame = frames[0].img.clone();
-    Ok(Some(WebpAnimation { first_frame, frames }))
+    let first_frame = match frames.first() {
+        Some(f) => f.img.clone(),
+        None    => return Err("libwebp: animation has no frames".to_string()),
+    };
+    if !hold_all { frames.clear(); }
+
+    Ok(Some(WebpAnimation { first_frame, frames, loop_count }))
+}
+
+/// A streaming animated-WebP decoder over libwebp's `WebPAnimDecoder`, which
+/// does the compositing (blend + dispose) itself and hands back one
+/// full-canvas RGBA frame at a time.
+///
+/// Memory: libwebp's own canvas plus its disposal copy, ~16 MB at 1080p —
+/// the same as `GifFrameStream` with a Previous-disposal GIF.
+pub struct WebpFrameStream<'a> {
+    pub(crate) width:  u32,
+    pub(crate) height: u32,
+    dec: NonNull<sys::WebPAnimDecoder>,
+
+    frame_count: u32,
+    loop_count:  u32,
+
+    /// End timestamp of the previous frame (ms), for per-frame delays.
+    prev_ts: i32,
+
+    /// ICCP → sRGB transform, None without a usable profile or with colour
+    /// management off. libwebp's canvas is read-only to us, so frames are
+    /// converted in a copy (`managed`).
+    icc:     Option<icc::Transform>,
+    managed: Vec<u8>,
+    icc_row: Vec<u8>,
+
+    /// The decoder reads straight from the file bytes; it must not outlive them.
+    _data: PhantomData<&'a [u8]>,
+}
+
+impl<'a> WebpFrameStream<'a> {
+    pub fn new(data: &'a [u8], opts: DecodeOptions) -> Result<Self, String> {
+        let webp_data = sys::WebPData { bytes: data.as_ptr(), size: data.len() };
+
+        // SAFETY: options are initialised by libwebp before use; the decoder
+        // keeps a pointer to `data`, which `'a` keeps alive.
+        let dec = unsafe {
+            let mut o = std::mem::MaybeUninit::<sys::WebPAnimDecoderOptions>::zeroed();
+            if sys::WebPAnimDecoderOptionsInit(o.as_mut_ptr()) == 0 {
+                return Err("libwebp: animation decoder version mismatch".to_string());
+            }
+            let mut o = o.assume_init();
+            o.color_mode  = sys::WEBP_CSP_MODE::MODE_RGBA;
+            o.use_threads = 0;
+            sys::WebPAnimDecoderNew(&webp_data, &o)
+        };
+        let dec = NonNull::new(dec)
+            .ok_or_else(|| "libwebp: failed to open animation".to_string())?;
+
+        let mut stream = Self {
+            width:       0,
+            height:      0,
+            dec,
+            frame_count: 0,
+            loop_count:  0,
+            prev_ts:     0,
+            icc:         exif::webp_chunk(data, b"ICCP")
+                .filter(|_| opts.colour_manage)
+                .and_then(icc::rgba_transform),
+            managed:     Vec::new(),
+            icc_row:     Vec::new(),
+            _data:       PhantomData,
+        };
+
+        let mut info = sys::WebPAnimInfo::default();
+        // SAFETY: `dec` is a live decoder; `info` is a plain out-param.
+        if unsafe { sys::WebPAnimDecoderGetInfo(dec.as_ptr(), &mut info) } == 0 {
+            return Err("libwebp: failed to read animation info".to_string());
+        }
+        stream.width       = info.canvas_width;
+        stream.height      = info.canvas_height;
+        stream.frame_count = info.frame_count;
+        stream.loop_count  = info.loop_count;
+        Ok(stream)
+    }
+
+    #[inline] pub fn width(&self)  -> u32 { self.width  }
+    #[inline] pub fn height(&self) -> u32 { self.height }
+
+    /// ANIM loop count: None = loop forever.
+    pub fn loop_count(&self) -> Option<u16> {
+        (self.loop_count > 0).then(|| self.loop_count.min(u16::MAX as u32) as u16)
+    }
+
+    // ── Public decode APIs ──────────────────────────────────────────────────
+
+    /// Advance the stream past the next frame without producing pixel output.
+    pub fn skip_frame(&mut self) -> Option<Result<Duration, String>> {
+        Some(self.step_frame()?.map(|(delay, _)| delay))
+    }
+
+    /// Decode, composite, scale, and convert into `out` (XRGB8888, out_w×out_h×4).
+    pub fn next_frame_scaled_into(
+        &mut self,
+        out:     &mut [u8],
+        out_w:   u32,
+        out_h:   u32,
+        view:    Viewport,
+        mode:    ScaleMode,
+        framing: Framing,
+        bg:      Colour,
+        blur:    &mut BlurScratch,
+    ) -> Option<Result<Duration, String>> {
+        let (width, height) = (self.width, self.height);
+        let (delay, canvas) = match self.step_frame()? {
+            Ok(f)  => f,
+            Err(e) => return Some(Err(e)),
+        };
+        scale_rgba_canvas_view_into(
+            canvas, width, height,
+            out, out_w, out_h,
+            view, mode, framing, bg, blur,
+        );
+        Some(Ok(delay))
+    }
+
+    /// Return the colour-managed copy's pages to the OS right away; libwebp's
+    /// own canvas goes back when the stream drops.
+    pub fn release_canvas(&mut self) {
+        mem::pages_dontneed(&self.managed);
+    }
+
+    // ── Internal core ───────────────────────────────────────────────────────
+
+    /// Next composited frame: its delay and libwebp's RGBA canvas (or the
+    /// colour-managed copy of it).
+    fn step_frame(&mut self) -> Option<Result<(Duration, &[u8]), String>> {
+        let dec = self.dec.as_ptr();
+
+        // SAFETY: `dec` is live for as long as `self`.
+        if unsafe { sys::WebPAnimDecoderHasMoreFrames(dec) } == 0 {
+            return None;
+        }
+
+        let mut buf: *mut u8 = std::ptr::null_mut();
+        let mut ts = 0;
+        // SAFETY: as above; both out-params are plain locals.
+        if unsafe { sys::WebPAnimDecoderGetNext(dec, &mut buf, &mut ts) } == 0 || buf.is_null() {
+            return Some(Err("libwebp: failed to decode animation frame".to_string()));
+        }
+
+        let delay = Duration::from_millis((ts - self.prev_ts).max(20) as u64);
+        self.prev_ts = ts;
+
+        // SAFETY: libwebp returns its width×height RGBA canvas, valid until the
+        // next GetNext or Delete — both need `&mut self`, so the borrow ends first.
+        let n      = self.width as usize * self.height as usize * 4;
+        let canvas = unsafe { std::slice::from_raw_parts(buf, n) };
+        let Some(t) = self.icc.as_ref() else {
+            return Some(Ok((delay, canvas)));
+        };
+
+        self.managed.clear();
+        self.managed.extend_from_slice(canvas);
+        for row in self.managed.chunks_exact_mut(self.width as usize * 4) {
+            icc::rgba_row_to_srgb(t, row, &mut self.icc_row);
+        }
+        Some(Ok((delay, &self.managed)))
+    }
+}
+
+impl Drop for WebpFrameStream<'_> {
+    fn drop(&mut self) {
+        // SAFETY: created by WebPAnimDecoderNew, deleted exactly once.
+        unsafe { sys::WebPAnimDecoderDelete(self.dec.as_ptr()) }
+    }
 }
 
 /// RGB8 → XRGB8888 (B, G, R, 0 byte order)
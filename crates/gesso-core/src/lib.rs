@@ -8,7 +8,7 @@ pub mod render;
 pub mod hex;
 pub mod paths;
 
-pub use decode::{DecodedImage, decode_image, DecodeError};
+pub use decode::{DecodedImage, DecodeOptions, decode_image, decode_image_with, DecodeError};
 pub use model::{
     Colour,
     OutputDesc,
@@ -22,8 +22,14 @@ pub use render::{
     Surface,
     Transition,
     WaveDir,
+    SlideDir,
+    Coord,
+    DropOrigin,
+    DissolveNoise,
+    Easing,
     RenderCtx,
     render_transition,
 };
 pub use render::{RenderEngine, Target};
-pub use render::{scale_image, ScaleMode};
+pub use render::{render_gradient, Gradient, GradientShape, GradientStop};
+pub use render::{scale_image, scale_image_view, Framing, ScaleMode, Viewport, MAX_ZOOM, MIN_ZOOM};
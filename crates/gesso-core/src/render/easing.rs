@@ -0,0 +1,116 @@
+// Author: Dustin Pilgrim
+// License: MIT
+
+use serde::{Deserialize, Serialize};
+
+/// A progress curve a transition can be asked to follow instead of its own
+/// tuned one. Names and the bezier form match CSS / easings.net, so curves
+/// can be copied from a desktop's animation settings.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Easing {
+    Linear,
+    InQuad,
+    OutQuad,
+    InOutQuad,
+    InCubic,
+    OutCubic,
+    InOutCubic,
+    InExpo,
+    OutExpo,
+    InOutExpo,
+    /// CSS `cubic-bezier(x1, y1, x2, y2)`. x1 and x2 are clamped to 0..=1 so
+    /// the curve stays a function of time. y1 and y2 may lie outside 0..=1,
+    /// but the result is clamped: the renderers can't draw past either image.
+    CubicBezier { x1: f32, y1: f32, x2: f32, y2: f32 },
+}
+
+impl Easing {
+    /// Maps linear progress 0..=1 onto the curve.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear     => t,
+            Easing::InQuad     => t * t,
+            Easing::OutQuad    => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::InOutQuad  => {
+                if t < 0.5 { 2.0 * t * t } else { 1.0 - (-2.0 * t + 2.0).powi(2) * 0.5 }
+            }
+            Easing::InCubic    => t * t * t,
+            Easing::OutCubic   => 1.0 - (1.0 - t).powi(3),
+            Easing::InOutCubic => {
+                if t < 0.5 { 4.0 * t * t * t } else { 1.0 - (-2.0 * t + 2.0).powi(3) * 0.5 }
+            }
+            Easing::InExpo     => {
+                if t <= 0.0 { 0.0 } else { 2f32.powf(10.0 * t - 10.0) }
+            }
+            Easing::OutExpo    => {
+                if t >= 1.0 { 1.0 } else { 1.0 - 2f32.powf(-10.0 * t) }
+            }
+            Easing::InOutExpo  => {
+                if t <= 0.0 {
+                    0.0
+                } else if t >= 1.0 {
+                    1.0
+                } else if t < 0.5 {
+                    2f32.powf(20.0 * t - 10.0) * 0.5
+                } else {
+                    1.0 - 2f32.powf(-20.0 * t + 10.0) * 0.5
+                }
+            }
+            Easing::CubicBezier { x1, y1, x2, y2 } => {
+                cubic_bezier(x1.clamp(0.0, 1.0), y1, x2.clamp(0.0, 1.0), y2, t).clamp(0.0, 1.0)
+            }
+        }
+    }
+}
+
+//
+// Cubic bezier
+//
+
+/// One coordinate of a bezier from (0,0) to (1,1) with control values a, b.
+#[inline(always)]
+fn bezier(a: f32, b: f32, s: f32) -> f32 {
+    let u = 1.0 - s;
+    3.0 * u * u * s * a + 3.0 * u * s * s * b + s * s * s
+}
+
+#[inline(always)]
+fn bezier_slope(a: f32, b: f32, s: f32) -> f32 {
+    let u = 1.0 - s;
+    3.0 * u * u * a + 6.0 * u * s * (b - a) + 3.0 * s * s * (1.0 - b)
+}
+
+/// y at the curve parameter whose x is `t`. Newton first (converges in a few
+/// steps on most curves), bisection when the slope is too flat to trust.
+fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32, t: f32) -> f32 {
+    let mut s = t;
+    for _ in 0..8 {
+        let err = bezier(x1, x2, s) - t;
+        if err.abs() < 1e-5 {
+            return bezier(y1, y2, s);
+        }
+        let d = bezier_slope(x1, x2, s);
+        if d.abs() < 1e-6 {
+            break;
+        }
+        s = (s - err / d).clamp(0.0, 1.0);
+    }
+
+    // x(s) is monotonic for x1, x2 in 0..=1, so bisection always lands.
+    let (mut lo, mut hi) = (0.0f32, 1.0f32);
+    s = t;
+    for _ in 0..24 {
+        let x = bezier(x1, x2, s);
+        if (x - t).abs() < 1e-5 {
+            break;
+        }
+        if x < t {
+            lo = s;
+        } else {
+            hi = s;
+        }
+        s = (lo + hi) * 0.5;
+    }
+    bezier(y1, y2, s)
+}
@@ -8,7 +8,7 @@ use std::time::{Duration, Instant};
 
 use crate::{Colour, Surface};
 use crate::mem;
-use super::{render_transition, RenderCtx, Transition};
+use super::{render_transition, Easing, RenderCtx, Transition, Viewport};
 
 //
 // Error
@@ -129,19 +129,35 @@ fn smootherstep(t: f32) -> f32 {
     t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
 }
 
-/// Returns an eased `t` appropriate for the given transition type.
+/// Returns an eased `t` appropriate for the given transition type, or on the
+/// transition's own `easing` when the request picked one.
 ///
 /// Tuning notes:
 ///   Drop  exp 6.0  — punchy launch, very long gentle settle (cinematic)
 ///   Wave  exp 4.5  — clear deceleration into the final rest position
 ///   Fade  quintic  — symmetric, noticeably slower at both ends
+///   Slide in-out-cubic — both images move, so ease in as well as out; a hard
+///                    start reads as a jump when the old one lurches away
+///   Cover out-cubic — the old image is static, so only the arrival needs
+///                    softening; a milder settle than Wave keeps it brisk
+///   Pixelate quintic — symmetric like Fade; lingers at the coarsest blocks
+///                    around the swap, where the change is least jarring
+///   Dissolve quintic — a grainy crossfade, so it paces like Fade
 #[inline(always)]
 fn ease_for_transition(transition: &Transition, t: f32) -> f32 {
+    if let Some(easing) = transition.easing() {
+        return easing.apply(t);
+    }
+
     match transition {
-        Transition::Drop { .. } => ease_out(t, 6.0),
-        Transition::Wave { .. } => ease_out(t, 4.5),
-        Transition::Fade { .. } => smootherstep(t),
-        Transition::None        => t,
+        Transition::Drop { .. }     => ease_out(t, 6.0),
+        Transition::Wave { .. }     => ease_out(t, 4.5),
+        Transition::Fade { .. }     => smootherstep(t),
+        Transition::Slide { .. }    => Easing::InOutCubic.apply(t),
+        Transition::Cover { .. }    => Easing::OutCubic.apply(t),
+        Transition::Pixelate { .. } => smootherstep(t),
+        Transition::Dissolve { .. } => smootherstep(t),
+        Transition::None            => t,
     }
 }
 
@@ -154,7 +170,9 @@ struct ActiveTransition {
     duration:   Duration,
     from:       OldSnapshot, // caller-supplied snapshot (may be colour-only)
     to:         Target,       // holds pixels only for the duration of the transition
+    view:       Viewport,     // where this output sits in the transition's canvas
     start:      Instant,
+    id:         u64,          // see RenderEngine::transition_id
 }
 
 impl ActiveTransition {
@@ -233,6 +251,8 @@ pub struct Frame<'a> {
 pub struct RenderEngine {
     ctx:     RenderCtx,
     outputs: HashMap<String, OutputState>,
+    /// Transitions started so far; the last one's number is its id.
+    started: u64,
 }
 
 impl Default for RenderEngine {
@@ -240,6 +260,7 @@ impl Default for RenderEngine {
         Self {
             ctx:     RenderCtx::default(),
             outputs: HashMap::new(),
+            started: 0,
         }
     }
 }
@@ -258,6 +279,11 @@ impl RenderEngine {
         }
     }
 
+    /// Forget an output (unplugged). Drops any pending frame or transition buffers.
+    pub fn remove_output(&mut self, name: &str) {
+        self.outputs.remove(name);
+    }
+
     /// True if we have work to do for this output (a frame must be rendered).
     /// When false, the caller should not call render_output_into(), allowing
     /// the engine to stay pixel-free while idle.
@@ -277,6 +303,13 @@ impl RenderEngine {
             .unwrap_or(false)
     }
 
+    /// Id of the transition running on this output, if any. Each one started
+    /// gets a new id, so a transition that replaced another mid-way can be
+    /// told apart from it.
+    pub fn transition_id(&self, output: &str) -> Option<u64> {
+        self.outputs.get(output)?.active.as_ref().map(|a| a.id)
+    }
+
     /// Set immediately (no transition). Pixels are kept only until the next render.
     pub fn set_now(&mut self, output: &str, target: Target) -> Result<()> {
         let st = self.outputs.get_mut(output).ok_or(EngineError::UnknownOutput)?;
@@ -305,6 +338,25 @@ impl RenderEngine {
         from: OldSnapshot,
         target: Target,
         transition: Transition,
+    ) -> Result<()> {
+        let (w, h) = self
+            .outputs
+            .get(output)
+            .map(|st| (st.width, st.height))
+            .ok_or(EngineError::UnknownOutput)?;
+        self.set_with_transition_view(output, from, target, transition, Viewport::full(w, h))
+    }
+
+    /// Like `set_with_transition_from`, but the transition runs across a larger
+    /// virtual canvas (span mode) and this output only renders its `view` of it.
+    /// A wave started on every spanned output this way travels across monitors.
+    pub fn set_with_transition_view(
+        &mut self,
+        output: &str,
+        from: OldSnapshot,
+        target: Target,
+        transition: Transition,
+        view: Viewport,
     ) -> Result<()> {
         let st = self.outputs.get_mut(output).ok_or(EngineError::UnknownOutput)?;
         st.ensure_dims_match(&target)?;
@@ -337,17 +389,79 @@ impl RenderEngine {
             st.current = CurrentKind::from_target(&target);
         }
 
+        self.started += 1;
         st.active = Some(ActiveTransition {
             duration: Duration::from_millis(transition.duration_ms() as u64),
             transition,
             from,
             to: target,
+            view,
             start: Instant::now(),
+            id: self.started,
         });
 
         Ok(())
     }
 
+    /// Restart the active transitions on `outputs` from a common instant.
+    ///
+    /// Spanned outputs are set one after another (decode + scale per output),
+    /// so their start times drift apart; aligning them keeps a wave that
+    /// crosses monitors continuous at the seams.
+    pub fn sync_transitions(&mut self, outputs: &[String]) {
+        let start = outputs
+            .iter()
+            .filter_map(|name| self.outputs.get(name)?.active.as_ref().map(|a| a.start))
+            .max();
+        let Some(start) = start else { return };
+
+        for name in outputs {
+            if let Some(a) = self.outputs.get_mut(name).and_then(|st| st.active.as_mut()) {
+                a.start = start;
+            }
+        }
+    }
+
+    /// If the next frame for `output` is a plain colour (a pending `set_now`, or
+    /// a transition to a colour that has just finished), return it.
+    ///
+    /// Lets the backend present it without a full-size buffer; follow a
+    /// successful present with `mark_presented`.
+    pub fn solid_colour(&self, output: &str) -> Option<Colour> {
+        let st = self.outputs.get(output)?;
+        match (&st.active, &st.pending) {
+            (Some(a), _) if a.is_complete(Instant::now()) => match a.to {
+                Target::Colour(c) => Some(c),
+                _                 => None,
+            },
+            (None, Some(Target::Colour(c))) => Some(*c),
+            _ => None,
+        }
+    }
+
+    /// The frame reported by `solid_colour` was shown by other means; drop it
+    /// as `render_output_into` would have.
+    pub fn mark_presented(&mut self, output: &str) {
+        let Some(st) = self.outputs.get_mut(output) else { return };
+
+        if let Some(finished) = st.active.take() {
+            if let OldSnapshot::Image(ref img) = finished.from {
+                mem::pixels_free(img);
+            }
+            #[cfg(debug_assertions)]
+            {
+                st.current = CurrentKind::from_target(&finished.to);
+            }
+        } else if let Some(pending) = st.pending.take() {
+            #[cfg(debug_assertions)]
+            {
+                st.current = CurrentKind::from_target(&pending);
+            }
+            #[cfg(not(debug_assertions))]
+            let _ = pending;
+        }
+    }
+
     /// Render one frame into `dst`. Caller should only invoke when needs_present() is true.
     ///
     /// Returns true if a frame was written.
@@ -478,6 +592,7 @@ fn render_active_into(
         stride,
         dst,
         t,
+        active.view,
     );
 }
 
@@ -3,15 +3,25 @@
 
 pub mod surface;
 pub mod transition;
+pub mod easing;
 pub mod engine;
+pub mod gradient;
+mod noise;
 pub mod scale;
+pub mod viewport;
 
 pub use surface::Surface;
-pub use transition::{Transition, WaveDir};
+pub use easing::Easing;
+pub use transition::{Coord, DissolveNoise, DropOrigin, SlideDir, Transition, WaveDir};
 pub use engine::{RenderEngine, Target, OldSnapshot};
-pub use scale::{scale_image, ScaleMode};
+pub use gradient::{render_gradient, render_gradient_into, Gradient, GradientShape, GradientStop};
+pub use scale::{scale_image, scale_image_view, Framing, ScaleMode, MAX_ZOOM, MIN_ZOOM};
+pub use viewport::Viewport;
+
+use rayon::prelude::*;
 
 use crate::Colour;
+use noise::BlueNoise;
 
 //
 // Quality
@@ -117,6 +127,7 @@ pub fn render_transition(
     out_stride: usize,
     out: &mut [u8],
     t: f32,
+    view: Viewport,
 ) {
     let t = t.clamp(0.0, 1.0);
 
@@ -125,9 +136,12 @@ pub fn render_transition(
             blit_new(new, new_colour, out_width, out_height, out_stride, out);
         }
 
-        // Drop: hard-edged expanding circle (no blending).
-        Transition::Drop { .. } => {
-            drop_hard(old, old_colour, new, new_colour, out_width, out_height, out_stride, out, t);
+        // Drop: expanding circle, crisp unless softness_px is set.
+        Transition::Drop { softness_px, seed, origin, .. } => {
+            drop_circle(
+                old, old_colour, new, new_colour, out_width, out_height, out_stride, out, t, view,
+                origin, softness_px, seed,
+            );
         }
 
         // Fade: EXACTLY like old gesso.
@@ -152,6 +166,7 @@ pub fn render_transition(
                 out_stride,
                 out,
                 t,
+                view,
                 dir,
                 softness_px,
                 amplitude_px,
@@ -170,6 +185,7 @@ pub fn render_transition(
                     out_stride,
                     out,
                     t,
+                    view,
                     dir,
                     softness_px,
                     amplitude_px,
@@ -177,6 +193,25 @@ pub fn render_transition(
                 )
             }
         },
+
+        Transition::Slide { dir, .. } => {
+            slide_impl(old, old_colour, new, new_colour, out_width, out_height, out_stride, out, t, dir, true);
+        }
+
+        Transition::Cover { dir, .. } => {
+            slide_impl(old, old_colour, new, new_colour, out_width, out_height, out_stride, out, t, dir, false);
+        }
+
+        Transition::Pixelate { max_block_px, .. } => {
+            pixelate(old, old_colour, new, new_colour, out_width, out_height, out_stride, out, t, view, max_block_px);
+        }
+
+        Transition::Dissolve { seed, grain_px, noise, .. } => {
+            dissolve(
+                old, old_colour, new, new_colour, out_width, out_height, out_stride, out, t, view,
+                seed, grain_px, noise,
+            );
+        }
     }
 }
 
@@ -206,6 +241,16 @@ fn clamp_i32(x: i32, lo: i32, hi: i32) -> i32 {
     if x < lo { lo } else if x > hi { hi } else { x }
 }
 
+/// 32-bit integer mix (lowbias32); used where a seed has to become noise.
+#[inline(always)]
+fn hash(mut x: u32) -> u32 {
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x7feb_352d);
+    x ^= x >> 15;
+    x = x.wrapping_mul(0x846c_a68b);
+    x ^ (x >> 16)
+}
+
 #[inline(always)]
 fn tau() -> f32 {
     std::f32::consts::PI * 2.0
@@ -295,10 +340,17 @@ fn blend_u32_xrgb(a: u32, b: u32, tt: u32, inv: u32) -> u32 {
 }
 
 //
-// ─── DROP (hard edge, center circle expanding) ────────────────────────────────
+// ─── DROP (circle expanding from an origin) ───────────────────────────────────
 //
 
-fn drop_hard(
+/// Canvas point for `DropOrigin::Random`: two hashes of the seed.
+fn random_origin(seed: u32, cw: u32, ch: u32) -> (f32, f32) {
+    let fx = hash(seed) as f32 / u32::MAX as f32;
+    let fy = hash(seed ^ 0x9e37_79b9) as f32 / u32::MAX as f32;
+    (fx * cw as f32, fy * ch as f32)
+}
+
+fn drop_circle(
     old: Option<Surface<'_>>,
     old_colour: Option<Colour>,
     new: Option<Surface<'_>>,
@@ -308,6 +360,10 @@ fn drop_hard(
     stride: usize,
     out: &mut [u8],
     t: f32,
+    view: Viewport,
+    origin: DropOrigin,
+    softness_px: u16,
+    seed: u32,
 ) {
     if t >= 0.999 {
         blit_new(new, new_colour, w, h, stride, out);
@@ -319,13 +375,28 @@ fn drop_hard(
     }
 
     let w_usize = w as usize;
-
-    let cx = w as f32 * 0.5;
-    let cy = h as f32 * 0.5;
-
-    let max_r = (cx * cx + cy * cy).sqrt();
+    let (cw, ch) = (view.canvas_width, view.canvas_height);
+
+    // Origin in canvas space, then expressed in this output's pixels.
+    let (ox, oy) = match origin {
+        DropOrigin::Centre      => (cw as f32 * 0.5, ch as f32 * 0.5),
+        DropOrigin::At { x, y } => (x.resolve(cw), y.resolve(ch)),
+        DropOrigin::Random      => random_origin(seed, cw, ch),
+    };
+    let cx = ox - view.x as f32;
+    let cy = oy - view.y as f32;
+
+    // Grow until the farthest canvas corner is covered, feather included.
+    let far_x = ox.max(cw as f32 - ox);
+    let far_y = oy.max(ch as f32 - oy);
+    let soft = softness_px as f32;
+    let max_r = (far_x * far_x + far_y * far_y).sqrt() + soft;
+
+    // `r` is the outer edge; the feather runs inwards from it to `r_in`.
     let r = t * max_r;
     let r2 = r * r;
+    let r_in = (r - soft).max(0.0);
+    let r_in2 = r_in * r_in;
 
     let col_px = new_colour.map(colour_u32);
     let old_px = old_colour.map(colour_u32);
@@ -342,49 +413,46 @@ fn drop_hard(
 
         if dy2 >= r2 {
             // all old
-            if let Some(or) = o_row {
-                dst.copy_from_slice(or);
-            } else if let Some(opx) = old_px {
-                dst.fill(opx);
-            } else {
-                dst.fill(0);
-            }
+            fill_old(dst, 0, w_usize, old_px, o_row);
             continue;
         }
 
-        let half = (r2 - dy2).max(0.0).sqrt();
-        let l = clamp_i32((cx - half).ceil() as i32, 0, w as i32) as usize;
-        let rr = clamp_i32((cx + half).floor() as i32, 0, w as i32) as usize;
+        let span = |half: f32| -> (usize, usize) {
+            let l = clamp_i32((cx - half).ceil() as i32, 0, w as i32) as usize;
+            let r = clamp_i32((cx + half).floor() as i32, 0, w as i32) as usize;
+            (l, r.max(l))
+        };
 
-        // left old
-        if l > 0 {
-            if let Some(or) = o_row {
-                dst[..l].copy_from_slice(&or[..l]);
-            } else if let Some(opx) = old_px {
-                dst[..l].fill(opx);
-            } else {
-                dst[..l].fill(0);
-            }
-        }
-        // middle new
-        if rr > l {
-            if let Some(c) = col_px {
-                dst[l..rr].fill(c);
-            } else if let Some(nr) = n_row {
-                dst[l..rr].copy_from_slice(&nr[l..rr]);
-            } else {
-                dst[l..rr].fill(0);
-            }
+        let (l, rr) = span((r2 - dy2).max(0.0).sqrt());
+
+        if soft <= 0.0 {
+            fill_old(dst, 0, l, old_px, o_row);
+            fill_new(dst, l, rr, col_px, n_row);
+            fill_old(dst, rr, w_usize, old_px, o_row);
+            continue;
         }
-        // right old
-        if rr < w_usize {
-            if let Some(or) = o_row {
-                dst[rr..].copy_from_slice(&or[rr..]);
-            } else if let Some(opx) = old_px {
-                dst[rr..].fill(opx);
+
+        // Fully new inside the inner circle; the feather is either side.
+        let (il, ir) = if dy2 < r_in2 { span((r_in2 - dy2).sqrt()) } else { (rr, rr) };
+        let (il, ir) = (il.max(l), ir.min(rr).max(il.max(l)));
+
+        fill_old(dst, 0, l, old_px, o_row);
+        fill_new(dst, il, ir, col_px, n_row);
+        fill_old(dst, rr, w_usize, old_px, o_row);
+
+        for x in (l..il).chain(ir..rr) {
+            let dx = x as f32 - cx;
+            let d = (dx * dx + dy2).sqrt();
+            let tt = (((r - d) / soft).clamp(0.0, 1.0) * 256.0) as u32;
+
+            let o = if let Some(or) = o_row {
+                or[x]
             } else {
-                dst[rr..].fill(0);
-            }
+                old_px.unwrap_or(0)
+            };
+            let npx = col_px.unwrap_or_else(|| n_row.map(|r| r[x]).unwrap_or(0));
+
+            dst[x] = blend_u32_xrgb(o, npx, tt, 256 - tt);
         }
     }
 }
@@ -543,12 +611,13 @@ fn wave_fast(
     stride: usize,
     out: &mut [u8],
     t: f32,
+    view: Viewport,
     dir: WaveDir,
     softness_px: u16,
     amplitude_px: u16,
     wavelength_px: u16,
 ) {
-    wave_impl(None, old, old_colour, new, new_colour, w, h, stride, out, t, dir, softness_px, amplitude_px, wavelength_px)
+    wave_impl(None, old, old_colour, new, new_colour, w, h, stride, out, t, view, dir, softness_px, amplitude_px, wavelength_px)
 }
 
 fn wave_lut(
@@ -562,12 +631,13 @@ fn wave_lut(
     stride: usize,
     out: &mut [u8],
     t: f32,
+    view: Viewport,
     dir: WaveDir,
     softness_px: u16,
     amplitude_px: u16,
     wavelength_px: u16,
 ) {
-    wave_impl(Some(ctx), old, old_colour, new, new_colour, w, h, stride, out, t, dir, softness_px, amplitude_px, wavelength_px)
+    wave_impl(Some(ctx), old, old_colour, new, new_colour, w, h, stride, out, t, view, dir, softness_px, amplitude_px, wavelength_px)
 }
 
 #[inline(always)]
@@ -602,6 +672,7 @@ fn wave_impl(
     stride: usize,
     out: &mut [u8],
     t: f32,
+    view: Viewport,
     dir: WaveDir,
     softness_px: u16,
     amplitude_px: u16,
@@ -616,176 +687,668 @@ fn wave_impl(
         return;
     }
 
-    let w_usize = w as usize;
+    // Left and Right are the common case and run as whole-row spans; every
+    // other direction goes through the general per-pixel front.
+    let from_left = match dir {
+        WaveDir::Left  => true,
+        WaveDir::Right => false,
+        _ => {
+            wave_angled(
+                ctx, old, old_colour, new, new_colour, w, h, stride, out, t, view,
+                dir.angle_deg(), softness_px, amplitude_px, wavelength_px,
+            );
+            return;
+        }
+    };
+
     let wl = wavelength_px.max(1) as f32;
     let new_px = new_colour.map(colour_u32);
     let old_px = old_colour.map(colour_u32);
 
-    let base = t * (w as f32);
+    // The wavefront lives in canvas space; `vx` maps it into this output's
+    // columns. For a single output the canvas is the output and vx == 0.
+    let cw = view.canvas_width as i32;
+    let vx = view.x;
+
+    let base = t * (view.canvas_width as f32);
     let damp = trailing_damp(t, 0.75);
 
     let amp = amplitude_px as f32 * damp;
     let effective_soft = ((softness_px as f32) * damp).round() as i32;
 
+    // Canvas column range -> clipped local column range.
+    let local = |a: i32, b: i32| -> (usize, usize) {
+        let l = clamp_i32(a - vx, 0, w as i32) as usize;
+        let r = clamp_i32(b - vx, 0, w as i32) as usize;
+        (l, r.max(l))
+    };
+
     for y in 0..h {
         let dst = as_u32_slice_mut(&mut out[y as usize * stride..][..stride]);
 
         let o_row = old.map(|s| as_u32_slice(s.row(y)));
         let n_row = new.map(|s| as_u32_slice(s.row(y)));
 
-        let phase = (y as f32 / wl) * tau() + t * tau();
+        let canvas_y = y as f32 + view.y as f32;
+        let phase = (canvas_y / wl) * tau() + t * tau();
         let wobble = phase.sin() * amp;
 
         let cut_f = base + wobble;
-        let cut = clamp_i32(cut_f.round() as i32, 0, w as i32);
+        let cut = clamp_i32(cut_f.round() as i32, 0, cw);
 
         if effective_soft <= 0 {
-            let cols = cut as usize;
-            match dir {
-                WaveDir::Left => {
-                    // new left, old right
-                    if cols > 0 {
-                        if let Some(c) = new_px {
-                            dst[..cols].fill(c);
-                        } else if let Some(nr) = n_row {
-                            dst[..cols].copy_from_slice(&nr[..cols]);
-                        } else {
-                            dst[..cols].fill(0);
-                        }
-                    }
-                    if cols < w_usize {
-                        if let Some(or) = o_row {
-                            dst[cols..].copy_from_slice(&or[cols..]);
-                        } else if let Some(opx) = old_px {
-                            dst[cols..].fill(opx);
-                        } else {
-                            dst[cols..].fill(0);
-                        }
-                    }
-                }
-                WaveDir::Right => {
-                    let start = w_usize.saturating_sub(cols);
-                    if start > 0 {
-                        if let Some(or) = o_row {
-                            dst[..start].copy_from_slice(&or[..start]);
-                        } else if let Some(opx) = old_px {
-                            dst[..start].fill(opx);
-                        } else {
-                            dst[..start].fill(0);
-                        }
-                    }
-                    if start < w_usize {
-                        if let Some(c) = new_px {
-                            dst[start..].fill(c);
-                        } else if let Some(nr) = n_row {
-                            dst[start..].copy_from_slice(&nr[start..]);
-                        } else {
-                            dst[start..].fill(0);
-                        }
-                    }
-                }
+            if from_left {
+                // new left, old right
+                let (_, c) = local(0, cut);
+                fill_new(dst, 0, c, new_px, n_row);
+                fill_old(dst, c, w as usize, old_px, o_row);
+            } else {
+                let (_, s) = local(0, cw - cut);
+                fill_old(dst, 0, s, old_px, o_row);
+                fill_new(dst, s, w as usize, new_px, n_row);
             }
             continue;
         }
 
-        let left = clamp_i32(cut - effective_soft, 0, w as i32) as usize;
-        let right = clamp_i32(cut + effective_soft, 0, w as i32) as usize;
+        let left = clamp_i32(cut - effective_soft, 0, cw);
+        let right = clamp_i32(cut + effective_soft, 0, cw);
 
         #[inline(always)]
         fn alpha_0_to_256(i: usize, len: usize) -> u32 {
             if len <= 1 { 256 } else { ((i * 256) / (len - 1)).min(256) as u32 }
         }
 
-        match dir {
-            WaveDir::Left => {
-                // new before band
-                if left > 0 {
-                    if let Some(c) = new_px {
-                        dst[..left].fill(c);
-                    } else if let Some(nr) = n_row {
-                        dst[..left].copy_from_slice(&nr[..left]);
-                    } else {
-                        dst[..left].fill(0);
-                    }
-                }
-                // old after band
-                if right < w_usize {
-                    if let Some(or) = o_row {
-                        dst[right..].copy_from_slice(&or[right..]);
-                    } else if let Some(opx) = old_px {
-                        dst[right..].fill(opx);
-                    } else {
-                        dst[right..].fill(0);
-                    }
-                }
+        let band_len = (right - left) as usize;
 
-                let band_len = right.saturating_sub(left);
-                for i in 0..band_len {
-                    let x = left + i;
+        if from_left {
+            // new before band, old after band
+            let (l, r) = local(left, right);
+            fill_new(dst, 0, l, new_px, n_row);
+            fill_old(dst, r, w as usize, old_px, o_row);
 
-                    let a = 256 - alpha_0_to_256(i, band_len);
-                    let inv = 256 - a;
+            for x in l..r {
+                let i = (x as i32 + vx - left) as usize;
 
-                    let o = if let Some(or) = o_row {
-                        or[x]
-                    } else {
-                        old_px.unwrap_or(0)
-                    };
+                let a = 256 - alpha_0_to_256(i, band_len);
+                let inv = 256 - a;
 
-                    let npx = new_px.unwrap_or_else(|| n_row.map(|r| r[x]).unwrap_or(0));
+                let o = if let Some(or) = o_row {
+                    or[x]
+                } else {
+                    old_px.unwrap_or(0)
+                };
 
-                    dst[x] = if let Some(c) = ctx {
-                        blend_lut(c, o, npx, a, inv)
-                    } else {
-                        blend_u32_xrgb(o, npx, a, inv)
-                    };
-                }
+                let npx = new_px.unwrap_or_else(|| n_row.map(|r| r[x]).unwrap_or(0));
+
+                dst[x] = if let Some(c) = ctx {
+                    blend_lut(c, o, npx, a, inv)
+                } else {
+                    blend_u32_xrgb(o, npx, a, inv)
+                };
             }
+        } else {
+            let old_end = cw - right;
+            let new_start = cw - left;
+
+            let (l, r) = local(old_end, new_start);
+            fill_old(dst, 0, l, old_px, o_row);
+            fill_new(dst, r, w as usize, new_px, n_row);
+
+            for x in l..r {
+                let i = (x as i32 + vx - old_end) as usize;
+                let a = alpha_0_to_256(i, band_len);
+                let inv = 256 - a;
+
+                let o = if let Some(or) = o_row {
+                    or[x]
+                } else {
+                    old_px.unwrap_or(0)
+                };
+
+                let npx = new_px.unwrap_or_else(|| n_row.map(|r| r[x]).unwrap_or(0));
+
+                dst[x] = if let Some(c) = ctx {
+                    blend_lut(c, o, npx, a, inv)
+                } else {
+                    blend_u32_xrgb(o, npx, a, inv)
+                };
+            }
+        }
+    }
+}
 
-            WaveDir::Right => {
-                let old_end = w_usize.saturating_sub(right);
-                let new_start = w_usize.saturating_sub(left);
+/// Wavefront travelling at `angle_deg` (0 = up, 90 = right). Pixels are
+/// projected onto the direction of travel; the ripple runs along the front.
+/// Only a strip `amp + soft` either side of the front is done per pixel.
+fn wave_angled(
+    ctx: Option<&RenderCtx>,
+    old: Option<Surface<'_>>,
+    old_colour: Option<Colour>,
+    new: Option<Surface<'_>>,
+    new_colour: Option<Colour>,
+    w: u32,
+    h: u32,
+    stride: usize,
+    out: &mut [u8],
+    t: f32,
+    view: Viewport,
+    angle_deg: f32,
+    softness_px: u16,
+    amplitude_px: u16,
+    wavelength_px: u16,
+) {
+    let wl = wavelength_px.max(1) as f32;
+    let new_px = new_colour.map(colour_u32);
+    let old_px = old_colour.map(colour_u32);
 
-                if old_end > 0 {
-                    if let Some(or) = o_row {
-                        dst[..old_end].copy_from_slice(&or[..old_end]);
-                    } else if let Some(opx) = old_px {
-                        dst[..old_end].fill(opx);
-                    } else {
-                        dst[..old_end].fill(0);
-                    }
-                }
-                if new_start < w_usize {
-                    if let Some(c) = new_px {
-                        dst[new_start..].fill(c);
-                    } else if let Some(nr) = n_row {
-                        dst[new_start..].copy_from_slice(&nr[new_start..]);
+    // Unit direction of travel in screen space (y grows downwards).
+    let rad = angle_deg.to_radians();
+    let (dx, dy) = (rad.sin(), -rad.cos());
+
+    // Projection range over the canvas corners; the front sweeps across it.
+    let (cw, ch) = (view.canvas_width as f32, view.canvas_height as f32);
+    let corners = [0.0, dx * cw, dy * ch, dx * cw + dy * ch];
+    let p_min = corners.iter().copied().fold(f32::INFINITY, f32::min);
+    let p_max = corners.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let front = p_min + t * (p_max - p_min);
+
+    let damp = trailing_damp(t, 0.75);
+    let amp = amplitude_px as f32 * damp;
+    let soft = ((softness_px as f32) * damp).round();
+    let reach = amp + soft;
+
+    // Ripple phase advance per canvas column (position along the front).
+    let k = -dy / wl * tau();
+    let wf = w as f32;
+
+    for y in 0..h {
+        let dst = as_u32_slice_mut(&mut out[y as usize * stride..][..stride]);
+
+        let o_row = old.map(|s| as_u32_slice(s.row(y)));
+        let n_row = new.map(|s| as_u32_slice(s.row(y)));
+
+        let cy = y as f32 + view.y as f32;
+        let cx0 = view.x as f32;
+
+        // Projection and ripple phase at local column 0.
+        let p0 = cx0 * dx + cy * dy;
+        let phase0 = (-dy * cx0 + dx * cy) / wl * tau() + t * tau();
+
+        // [0, a) and [b, w) are clear of the front on either side;
+        // `new_first` says which of them holds the new image.
+        let (a, b, new_first) = if dx.abs() < 1e-6 {
+            if p0 < front - reach {
+                (w as usize, w as usize, true)
+            } else if p0 > front + reach {
+                (0, 0, false)
+            } else {
+                (0, w as usize, true)
+            }
+        } else if dx > 0.0 {
+            let a = ((front - reach - p0) / dx).floor().clamp(0.0, wf) as usize;
+            let b = ((front + reach - p0) / dx).ceil().clamp(0.0, wf) as usize;
+            (a, b.max(a), true)
+        } else {
+            let a = ((front + reach - p0) / dx).floor().clamp(0.0, wf) as usize;
+            let b = ((front - reach - p0) / dx).ceil().clamp(0.0, wf) as usize;
+            (a, b.max(a), false)
+        };
+
+        if new_first {
+            fill_new(dst, 0, a, new_px, n_row);
+            fill_old(dst, b, w as usize, old_px, o_row);
+        } else {
+            fill_old(dst, 0, a, old_px, o_row);
+            fill_new(dst, b, w as usize, new_px, n_row);
+        }
+
+        for x in a..b {
+            let xf = x as f32;
+            // How far the front has passed this pixel.
+            let d = front + amp * (phase0 + k * xf).sin() - (p0 + dx * xf);
+
+            let tt = if soft <= 0.0 {
+                if d > 0.0 { 256 } else { 0 }
+            } else {
+                (((d + soft) / (2.0 * soft)).clamp(0.0, 1.0) * 256.0) as u32
+            };
+
+            let o = if let Some(or) = o_row {
+                or[x]
+            } else {
+                old_px.unwrap_or(0)
+            };
+
+            let npx = new_px.unwrap_or_else(|| n_row.map(|r| r[x]).unwrap_or(0));
+
+            dst[x] = match tt {
+                0   => o,
+                256 => npx,
+                _   => {
+                    let inv = 256 - tt;
+                    if let Some(c) = ctx {
+                        blend_lut(c, o, npx, tt, inv)
                     } else {
-                        dst[new_start..].fill(0);
+                        blend_u32_xrgb(o, npx, tt, inv)
                     }
                 }
+            };
+        }
+    }
+}
 
-                let band_len = new_start.saturating_sub(old_end);
-                for i in 0..band_len {
-                    let x = old_end + i;
-                    let a = alpha_0_to_256(i, band_len);
-                    let inv = 256 - a;
+/// Fill `dst[a..b]` with the new content (colour, row pixels, or black).
+#[inline(always)]
+fn fill_new(dst: &mut [u32], a: usize, b: usize, new_px: Option<u32>, n_row: Option<&[u32]>) {
+    if b <= a {
+        return;
+    }
+    if let Some(c) = new_px {
+        dst[a..b].fill(c);
+    } else if let Some(nr) = n_row {
+        dst[a..b].copy_from_slice(&nr[a..b]);
+    } else {
+        dst[a..b].fill(0);
+    }
+}
 
-                    let o = if let Some(or) = o_row {
-                        or[x]
-                    } else {
-                        old_px.unwrap_or(0)
-                    };
+/// Fill `dst[a..b]` with the old content (row pixels, colour, or black).
+#[inline(always)]
+fn fill_old(dst: &mut [u32], a: usize, b: usize, old_px: Option<u32>, o_row: Option<&[u32]>) {
+    if b <= a {
+        return;
+    }
+    if let Some(or) = o_row {
+        dst[a..b].copy_from_slice(&or[a..b]);
+    } else if let Some(opx) = old_px {
+        dst[a..b].fill(opx);
+    } else {
+        dst[a..b].fill(0);
+    }
+}
 
-                    let npx = new_px.unwrap_or_else(|| n_row.map(|r| r[x]).unwrap_or(0));
+//
+// ─── SLIDE / COVER ────────────────────────────────────────────────────────────
+//
+// These move whole images, so unlike Drop and Wave they need pixels from
+// beyond the split line. Each output only holds its own slice, so the motion
+// is in this output's pixels; in span mode every slice slides in step.
+//
 
-                    dst[x] = if let Some(c) = ctx {
-                        blend_lut(c, o, npx, a, inv)
-                    } else {
-                        blend_u32_xrgb(o, npx, a, inv)
-                    };
+/// Where a run of output pixels comes from.
+#[derive(Clone, Copy)]
+enum Src<'a> {
+    Px(u32),
+    Row(&'a [u32]),
+}
+
+impl Src<'_> {
+    /// Copy `dst.len()` pixels starting at column `from` of the source.
+    #[inline(always)]
+    fn copy_to(self, dst: &mut [u32], from: usize) {
+        match self {
+            Src::Px(px)   => dst.fill(px),
+            Src::Row(row) => dst.copy_from_slice(&row[from..from + dst.len()]),
+        }
+    }
+}
+
+/// Same precedence as `fill_new`: a solid colour wins over a surface.
+#[inline(always)]
+fn new_src<'a>(new: Option<Surface<'a>>, new_px: Option<u32>, y: u32) -> Src<'a> {
+    match (new_px, new) {
+        (Some(c), _)    => Src::Px(c),
+        (None, Some(n)) => Src::Row(as_u32_slice(n.row(y))),
+        (None, None)    => Src::Px(0),
+    }
+}
+
+/// Same precedence as `fill_old`: a surface wins over a solid colour.
+#[inline(always)]
+fn old_src<'a>(old: Option<Surface<'a>>, old_px: Option<u32>, y: u32) -> Src<'a> {
+    match (old, old_px) {
+        (Some(o), _)    => Src::Row(as_u32_slice(o.row(y))),
+        (None, Some(c)) => Src::Px(c),
+        (None, None)    => Src::Px(0),
+    }
+}
+
+/// `push` = Slide (the old image moves away with the new one); otherwise
+/// Cover (the old image stays where it is).
+fn slide_impl(
+    old: Option<Surface<'_>>,
+    old_colour: Option<Colour>,
+    new: Option<Surface<'_>>,
+    new_colour: Option<Colour>,
+    w: u32,
+    h: u32,
+    stride: usize,
+    out: &mut [u8],
+    t: f32,
+    dir: SlideDir,
+    push: bool,
+) {
+    if t >= 0.999 {
+        blit_new(new, new_colour, w, h, stride, out);
+        return;
+    }
+    if t <= 0.0 {
+        blit_old(old, old_colour, w, h, stride, out);
+        return;
+    }
+
+    let new_px = new_colour.map(colour_u32);
+    let old_px = old_colour.map(colour_u32);
+    let (wu, hu) = (w as usize, h as usize);
+
+    match dir {
+        SlideDir::Left | SlideDir::Right => {
+            // `d` columns of the new image are on screen.
+            let d = ((t * w as f32).round() as usize).min(wu);
+            let keep = wu - d;
+
+            for y in 0..h {
+                let dst = as_u32_slice_mut(&mut out[y as usize * stride..][..stride]);
+                let (o, n) = (old_src(old, old_px, y), new_src(new, new_px, y));
+
+                if dir == SlideDir::Left {
+                    // New enters at the right edge.
+                    o.copy_to(&mut dst[..keep], if push { d } else { 0 });
+                    n.copy_to(&mut dst[keep..wu], 0);
+                } else {
+                    n.copy_to(&mut dst[..d], keep);
+                    o.copy_to(&mut dst[d..wu], if push { 0 } else { d });
                 }
             }
         }
+
+        SlideDir::Up | SlideDir::Down => {
+            // `d` rows of the new image are on screen.
+            let d = ((t * h as f32).round() as usize).min(hu);
+            let keep = hu - d;
+
+            for y in 0..hu {
+                let dst = as_u32_slice_mut(&mut out[y * stride..][..stride]);
+
+                // (row comes from the new image, source row)
+                let (is_new, sy) = if dir == SlideDir::Up {
+                    // New enters at the bottom edge.
+                    if y < keep { (false, if push { y + d } else { y }) } else { (true, y - keep) }
+                } else if y < d {
+                    (true, y + keep)
+                } else {
+                    (false, if push { y - d } else { y })
+                };
+
+                let src = if is_new {
+                    new_src(new, new_px, sy as u32)
+                } else {
+                    old_src(old, old_px, sy as u32)
+                };
+                src.copy_to(&mut dst[..wu], 0);
+            }
+        }
+    }
+}
+
+//
+// ─── PIXELATE ─────────────────────────────────────────────────────────────────
+//
+// First half: the old image, blocks growing to `max_block_px`. Second half:
+// the new image, blocks shrinking back to 1. Blocks sit on a grid anchored
+// at the canvas origin so spanned outputs line up, but each output only
+// averages its own pixels.
+//
+
+fn pixelate(
+    old: Option<Surface<'_>>,
+    old_colour: Option<Colour>,
+    new: Option<Surface<'_>>,
+    new_colour: Option<Colour>,
+    w: u32,
+    h: u32,
+    stride: usize,
+    out: &mut [u8],
+    t: f32,
+    view: Viewport,
+    max_block_px: u16,
+) {
+    if t >= 0.999 {
+        blit_new(new, new_colour, w, h, stride, out);
+        return;
+    }
+    if t <= 0.0 {
+        blit_old(old, old_colour, w, h, stride, out);
+        return;
+    }
+
+    let (src, colour, k) = if t < 0.5 {
+        (old, old_colour, t * 2.0)
+    } else {
+        (new, new_colour, (1.0 - t) * 2.0)
+    };
+
+    let block = 1 + ((max_block_px.max(1) - 1) as f32 * k).round() as u32;
+
+    // Solid colours look the same at any block size.
+    let src = match (colour, src) {
+        (Some(c), _) => {
+            as_u32_slice_mut(out)[..w as usize * h as usize].fill(colour_u32(c));
+            return;
+        }
+        (None, Some(s)) if block > 1 => s,
+        (None, Some(_)) => {
+            if t < 0.5 {
+                blit_old(old, old_colour, w, h, stride, out);
+            } else {
+                blit_new(new, new_colour, w, h, stride, out);
+            }
+            return;
+        }
+        (None, None) => {
+            as_u32_slice_mut(out)[..w as usize * h as usize].fill(0);
+            return;
+        }
+    };
+
+    // Rows (or columns) before the first full block on the canvas grid.
+    let lead = |origin: i32, len: u32| -> u32 {
+        let r = origin.rem_euclid(block as i32) as u32;
+        (if r == 0 { block } else { block - r }).min(len)
+    };
+    let first_rows = lead(view.y, h);
+    let first_cols = lead(view.x, w);
+
+    let (head, tail) = out[..h as usize * stride].split_at_mut(first_rows as usize * stride);
+    pixelate_band(src, head, 0, first_rows, w, stride, block, first_cols);
+    tail.par_chunks_mut(block as usize * stride)
+        .enumerate()
+        .for_each(|(i, band)| {
+            let y0 = first_rows + i as u32 * block;
+            let rows = (band.len() / stride) as u32;
+            pixelate_band(src, band, y0, rows, w, stride, block, first_cols);
+        });
+}
+
+/// Average each block of `rows` source rows from `y0` and fill it into `dst`.
+fn pixelate_band(
+    src: Surface<'_>,
+    dst: &mut [u8],
+    y0: u32,
+    rows: u32,
+    w: u32,
+    stride: usize,
+    block: u32,
+    first_cols: u32,
+) {
+    if rows == 0 {
+        return;
+    }
+
+    let mut x0 = 0u32;
+    let mut bw = first_cols;
+    while x0 < w {
+        let x1 = (x0 + bw).min(w);
+        let (xa, xb) = (x0 as usize, x1 as usize);
+
+        // u64: a u32 channel sum overflows past ~16.8M pixels in one block.
+        let (mut r, mut g, mut b) = (0u64, 0u64, 0u64);
+        for y in y0..y0 + rows {
+            for &px in &src.row_u32(y)[xa..xb] {
+                r += ((px >> 16) & 0xFF) as u64;
+                g += ((px >> 8) & 0xFF) as u64;
+                b += (px & 0xFF) as u64;
+            }
+        }
+        let n = rows as u64 * (x1 - x0) as u64;
+        let avg = (((r / n) << 16) | ((g / n) << 8) | (b / n)) as u32;
+
+        for y in 0..rows as usize {
+            as_u32_slice_mut(&mut dst[y * stride..][..stride])[xa..xb].fill(avg);
+        }
+
+        x0 = x1;
+        bw = block;
+    }
+}
+
+//
+// ─── DISSOLVE ─────────────────────────────────────────────────────────────────
+//
+// Every `grain_px` cell of the canvas gets a threshold in [0, 1) from the
+// seed; it shows the new image once `t` passes it. Cells are in canvas
+// space so spanned outputs share one pattern, and nothing depends on frame
+// timing, so a given (seed, t) always renders the same pixels.
+//
+
+/// Threshold for the cell at canvas (cx, cy), in [0, 1). `blue` is the
+/// seed's variant of the blue-noise tile, present for `DissolveNoise::Blue`.
+#[inline(always)]
+fn dissolve_threshold(blue: Option<&BlueNoise>, seed: u32, cx: i32, cy: i32) -> f32 {
+    match blue {
+        Some(b) => b.at(cx, cy),
+        None    => {
+            let h = hash((cx as u32).wrapping_mul(0x9e37_79b1) ^ hash(cy as u32 ^ seed));
+            (h >> 8) as f32 / (1u32 << 24) as f32
+        }
+    }
+}
+
+fn dissolve(
+    old: Option<Surface<'_>>,
+    old_colour: Option<Colour>,
+    new: Option<Surface<'_>>,
+    new_colour: Option<Colour>,
+    w: u32,
+    h: u32,
+    stride: usize,
+    out: &mut [u8],
+    t: f32,
+    view: Viewport,
+    seed: u32,
+    grain_px: u16,
+    noise: DissolveNoise,
+) {
+    if t >= 0.999 {
+        blit_new(new, new_colour, w, h, stride, out);
+        return;
+    }
+    if t <= 0.0 {
+        blit_old(old, old_colour, w, h, stride, out);
+        return;
+    }
+
+    let grain = grain_px.max(1) as i32;
+    let blue  = matches!(noise, DissolveNoise::Blue).then(|| BlueNoise::new(hash(seed)));
+    let new_px = new_colour.map(colour_u32);
+    let old_px = old_colour.map(colour_u32);
+
+    out[..h as usize * stride]
+        .par_chunks_mut(stride)
+        .enumerate()
+        .for_each(|(y, row)| {
+            let y = y as u32;
+            let dst = as_u32_slice_mut(row);
+            let o_row = old.map(|s| s.row_u32(y));
+            let n_row = new.map(|s| s.row_u32(y));
+            let cy = (y as i32 + view.y).div_euclid(grain);
+
+            for x in 0..w as usize {
+                let cx = (x as i32 + view.x).div_euclid(grain);
+                dst[x] = if dissolve_threshold(blue.as_ref(), seed, cx, cy) < t {
+                    new_px.unwrap_or_else(|| n_row.map(|r| r[x]).unwrap_or(0))
+                } else {
+                    o_row.map(|r| r[x]).unwrap_or_else(|| old_px.unwrap_or(0))
+                };
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const W: u32 = 96;
+    const H: u32 = 64;
+    const OLD: Colour = Colour { r: 200, g: 10, b: 10 };
+    const NEW: Colour = Colour { r: 10, g: 10, b: 200 };
+
+    fn dissolve_frame(seed: u32, noise: DissolveNoise, t: f32) -> Vec<u8> {
+        let tr = Transition::Dissolve {
+            duration_ms: 1000,
+            seed,
+            grain_px: 1,
+            noise,
+            steps: 0,
+            easing: None,
+        };
+        let stride = W as usize * 4;
+        let mut out = vec![0u8; stride * H as usize];
+        render_transition(
+            &mut RenderCtx::default(), tr,
+            None, Some(OLD), None, Some(NEW),
+            W, H, stride, &mut out, t, Viewport::full(W, H),
+        );
+        out
+    }
+
+    fn all_are(buf: &[u8], c: Colour) -> bool {
+        as_u32_slice(buf).iter().all(|&px| px == colour_u32(c))
+    }
+
+    #[test]
+    fn dissolve_is_deterministic() {
+        for noise in [DissolveNoise::Blue, DissolveNoise::White] {
+            assert_eq!(dissolve_frame(7, noise, 0.4), dissolve_frame(7, noise, 0.4));
+        }
+    }
+
+    #[test]
+    fn dissolve_ends_are_pure() {
+        for noise in [DissolveNoise::Blue, DissolveNoise::White] {
+            assert!(all_are(&dissolve_frame(3, noise, 0.0), OLD));
+            assert!(all_are(&dissolve_frame(3, noise, 0.999), NEW));
+            assert!(all_are(&dissolve_frame(3, noise, 1.0), NEW));
+        }
+    }
+
+    #[test]
+    fn dissolve_seeds_differ() {
+        for noise in [DissolveNoise::Blue, DissolveNoise::White] {
+            let a = dissolve_frame(1, noise, 0.5);
+            for seed in 2..10 {
+                assert_ne!(a, dissolve_frame(seed, noise, 0.5), "seed {seed} ({noise:?})");
+            }
+        }
+    }
+
+    #[test]
+    fn blue_noise_flips_about_t() {
+        // Each threshold rank appears once per tile, so at t the share of
+        // new pixels is t to within a cell.
+        let frame = dissolve_frame(5, DissolveNoise::Blue, 0.25);
+        let n = as_u32_slice(&frame).iter().filter(|&&px| px == colour_u32(NEW)).count();
+        let share = n as f32 / (W * H) as f32;
+        assert!((share - 0.25).abs() < 0.02, "share {share}");
     }
 }
@@ -0,0 +1,159 @@
+// Author: Dustin Pilgrim
+// License: MIT
+
+use std::sync::OnceLock;
+
+//
+// Blue noise
+//
+// A 64×64 threshold tile built with Ulichney's void-and-cluster method: each
+// rank goes to the emptiest spot left, so any threshold picks evenly spread
+// cells with no clumps. The tile wraps, and a seed picks one of its 64×64
+// offsets and 8 rotations / mirrors, which keep the spacing intact.
+//
+
+const SIZE: usize = 64;
+const N:    usize = SIZE * SIZE;
+
+/// Gaussian energy kernel; σ = 1.5 is the usual choice, and past 6 cells its
+/// weight is negligible.
+const SIGMA:  f32   = 1.5;
+const RADIUS: isize = 6;
+
+static TILE: OnceLock<Box<[u16]>> = OnceLock::new();
+
+/// Blue-noise thresholds in [0, 1) for one seed.
+#[derive(Clone, Copy)]
+pub(crate) struct BlueNoise {
+    tile: &'static [u16],
+    ox:   usize,
+    oy:   usize,
+    sym:  u32,
+}
+
+impl BlueNoise {
+    /// `key` should already be hashed; its low 15 bits pick the variant.
+    pub(crate) fn new(key: u32) -> Self {
+        Self {
+            tile: TILE.get_or_init(void_and_cluster),
+            ox:   (key & 63) as usize,
+            oy:   (key >> 6 & 63) as usize,
+            sym:  key >> 12 & 7,
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn at(&self, x: i32, y: i32) -> f32 {
+        let mut u = (x as usize).wrapping_add(self.ox) % SIZE;
+        let mut v = (y as usize).wrapping_add(self.oy) % SIZE;
+        if self.sym & 1 != 0 { std::mem::swap(&mut u, &mut v); }
+        if self.sym & 2 != 0 { u = SIZE - 1 - u; }
+        if self.sym & 4 != 0 { v = SIZE - 1 - v; }
+        (self.tile[v * SIZE + u] as f32 + 0.5) / N as f32
+    }
+}
+
+// ── Generation ──────────────────────────────────────────────────────────────
+
+/// Rank (0..N) of every cell. Deterministic: same tile on every run.
+fn void_and_cluster() -> Box<[u16]> {
+    let kernel = kernel();
+    let mut field = Field { on: vec![false; N], energy: vec![0.0; N], kernel: &kernel };
+
+    // Initial pattern: 10% of cells from a fixed LCG, then relaxed by moving
+    // the tightest cluster into the largest void until it stops changing.
+    let mut rng = 0x2545_f491_u32;
+    let mut placed = 0;
+    while placed < N / 10 {
+        rng = rng.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+        let i = (rng >> 8) as usize % N;
+        if !field.on[i] {
+            field.set(i, true);
+            placed += 1;
+        }
+    }
+    loop {
+        let cluster = field.tightest_cluster();
+        field.set(cluster, false);
+        let void = field.largest_void();
+        if void == cluster {
+            field.set(cluster, true);
+            break;
+        }
+        field.set(void, true);
+    }
+
+    let mut rank = vec![0u16; N];
+
+    // Phase 1: strip the initial pattern, tightest clusters last-ranked first.
+    let mut strip = Field { on: field.on.clone(), energy: field.energy.clone(), kernel: &kernel };
+    for r in (0..placed).rev() {
+        let i = strip.tightest_cluster();
+        strip.set(i, false);
+        rank[i] = r as u16;
+    }
+
+    // Phase 2: fill the largest voids. On a torus the energy of the empty
+    // cells is a constant minus this one, so the same search also covers
+    // the second half (tightest cluster of the minority zeros).
+    for r in placed..N {
+        let i = field.largest_void();
+        field.set(i, true);
+        rank[i] = r as u16;
+    }
+
+    rank.into_boxed_slice()
+}
+
+fn kernel() -> Vec<f32> {
+    let d = (2 * RADIUS + 1) as usize;
+    let mut k = vec![0.0; d * d];
+    for dy in -RADIUS..=RADIUS {
+        for dx in -RADIUS..=RADIUS {
+            let r2 = (dx * dx + dy * dy) as f32;
+            k[(dy + RADIUS) as usize * d + (dx + RADIUS) as usize] = (-r2 / (2.0 * SIGMA * SIGMA)).exp();
+        }
+    }
+    k
+}
+
+struct Field<'k> {
+    on:     Vec<bool>,
+    energy: Vec<f32>,
+    kernel: &'k [f32],
+}
+
+impl Field<'_> {
+    fn set(&mut self, i: usize, on: bool) {
+        self.on[i] = on;
+        let sign = if on { 1.0 } else { -1.0 };
+        let (x, y) = ((i % SIZE) as isize, (i / SIZE) as isize);
+        let d = (2 * RADIUS + 1) as usize;
+        for dy in -RADIUS..=RADIUS {
+            let row = (y + dy).rem_euclid(SIZE as isize) as usize * SIZE;
+            for dx in -RADIUS..=RADIUS {
+                let col = (x + dx).rem_euclid(SIZE as isize) as usize;
+                let k = self.kernel[(dy + RADIUS) as usize * d + (dx + RADIUS) as usize];
+                self.energy[row + col] += sign * k;
+            }
+        }
+    }
+
+    /// Set cell with the most set neighbours.
+    fn tightest_cluster(&self) -> usize {
+        let mut best = (f32::MIN, 0);
+        for i in (0..N).filter(|&i| self.on[i]) {
+            if self.energy[i] > best.0 { best = (self.energy[i], i); }
+        }
+        best.1
+    }
+
+    /// Empty cell with the fewest set neighbours.
+    fn largest_void(&self) -> usize {
+        let mut best = (f32::MAX, 0);
+        for i in (0..N).filter(|&i| !self.on[i]) {
+            if self.energy[i] < best.0 { best = (self.energy[i], i); }
+        }
+        best.1
+    }
+}
@@ -2,17 +2,47 @@
 // License: MIT
 
 use crate::{Colour, DecodedImage};
+use super::viewport::Viewport;
 use rayon::prelude::*;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ScaleMode {
     Fill,
     Fit,
+    /// Fit, with the bars filled by a blurred, darkened, cover-scaled copy
+    /// of the image instead of `bg`.
+    FitBlur,
     Stretch,
     Center,
     Tile,
 }
 
+/// Where Fill and Center put the image when it doesn't match the output.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Framing {
+    /// Point of the image, as fractions of its size, lined up with the same
+    /// point of the output: (0.5, 0.5) crops evenly, (0.5, 0.0) keeps the top.
+    pub focus_x: f32,
+    pub focus_y: f32,
+    /// Fill only: magnification on top of the cover scale. 1.0 is plain Fill;
+    /// below 1.0 the image no longer covers and `bg` shows around it.
+    pub zoom: f32,
+    /// Fill only: shift of the crop window in output pixels (+x right, +y down),
+    /// clamped so an edge of the image is never pulled inside the output.
+    pub offset_x: i32,
+    pub offset_y: i32,
+}
+
+impl Default for Framing {
+    fn default() -> Self {
+        Self { focus_x: 0.5, focus_y: 0.5, zoom: 1.0, offset_x: 0, offset_y: 0 }
+    }
+}
+
+/// Bounds for [`Framing::zoom`]; keeps the scaled size sane for any input.
+pub const MIN_ZOOM: f32 = 0.1;
+pub const MAX_ZOOM: f32 = 8.0;
+
 // ── Public API ──────────────────────────────────────────────────────────────
 
 /// Scale `src` to `(dst_w, dst_h)` using `mode`, letterboxing with `bg` where needed.
@@ -25,48 +55,31 @@ pub fn scale_image(
     dst_w: u32,
     dst_h: u32,
     mode: ScaleMode,
+    framing: Framing,
     bg: Colour,
 ) -> Vec<u8> {
     let mut out = vec![0u8; dst_w as usize * dst_h as usize * 4];
-    scale_image_into(src, &mut out, dst_w, dst_h, mode, bg);
+    scale_image_into(src, &mut out, dst_w, dst_h, mode, framing, bg, &mut BlurScratch::default());
     out
 }
 
 /// Scale `src` into a caller-owned XRGB8888 buffer `dst` (must be dst_w * dst_h * 4 bytes).
 ///
 /// This is the zero-allocation variant used by the GIF and WebP players so they
-/// can reuse a single Arc-managed buffer across frames.
+/// can reuse a single Arc-managed buffer across frames; `blur` is theirs too.
 pub fn scale_image_into(
     src: &DecodedImage,
     dst: &mut [u8],
     dst_w: u32,
     dst_h: u32,
     mode: ScaleMode,
+    framing: Framing,
     bg: Colour,
+    blur: &mut BlurScratch,
 ) {
     fill_bg(dst, dst_w, dst_h, bg);
 
-    if dst_w == 0 || dst_h == 0 || src.width == 0 || src.height == 0 {
-        return;
-    }
-
-    // Validate source buffer to prevent panics inside blit helpers.
-    let min_stride = src.width as usize * 4;
-    if src.stride < min_stride {
-        debug_assert!(
-            false,
-            "DecodedImage stride too small: stride={} min={} ({}x{})",
-            src.stride, min_stride, src.width, src.height
-        );
-        return;
-    }
-    let needed = src.stride.saturating_mul(src.height as usize);
-    if src.pixels.len() < needed {
-        debug_assert!(
-            false,
-            "DecodedImage pixels too small: len={} needed={} ({}x{} stride={})",
-            src.pixels.len(), needed, src.width, src.height, src.stride
-        );
+    if dst_w == 0 || dst_h == 0 || !src_is_valid(src) {
         return;
     }
 
@@ -77,17 +90,23 @@ pub fn scale_image_into(
             blit_scaled(dst, dst_w, dst_h, stride, src, 0, 0, dst_w, dst_h);
         }
         ScaleMode::Fill => {
-            let scale = f32::max(
-                dst_w as f32 / src.width  as f32,
-                dst_h as f32 / src.height as f32,
-            );
-            let scaled_w = (src.width  as f32 * scale).round() as u32;
-            let scaled_h = (src.height as f32 * scale).round() as u32;
-            let off_x = ((scaled_w as i32 - dst_w as i32) / 2).max(0) as u32;
-            let off_y = ((scaled_h as i32 - dst_h as i32) / 2).max(0) as u32;
-            blit_scaled_crop(dst, dst_w, dst_h, stride, src, scaled_w, scaled_h, off_x, off_y);
+            let (scaled_w, scaled_h, x, y) =
+                canvas_placement(src.width, src.height, dst_w, dst_h, mode, framing);
+            if x <= 0 && y <= 0 {
+                blit_scaled_crop(dst, dst_w, dst_h, stride, src, scaled_w, scaled_h, (-x) as u32, (-y) as u32);
+            } else {
+                // Zoomed out: the image no longer covers at least one axis.
+                blit_scaled_window_bilinear_xrgb(
+                    dst, dst_w, dst_h, stride,
+                    &src.pixels, src.width, src.height, src.stride,
+                    scaled_w, scaled_h, x, y,
+                );
+            }
         }
-        ScaleMode::Fit => {
+        ScaleMode::Fit | ScaleMode::FitBlur => {
+            if mode == ScaleMode::FitBlur {
+                backdrop_xrgb(dst, dst_w, dst_h, stride, src, Viewport::full(dst_w, dst_h), blur);
+            }
             let scale = f32::min(
                 dst_w as f32 / src.width  as f32,
                 dst_h as f32 / src.height as f32,
@@ -100,12 +119,12 @@ pub fn scale_image_into(
         }
         ScaleMode::Center => {
             if src.width <= dst_w && src.height <= dst_h {
-                let x = ((dst_w as i32 - src.width  as i32) / 2).max(0) as u32;
-                let y = ((dst_h as i32 - src.height as i32) / 2).max(0) as u32;
+                let x = anchor(dst_w as i32 - src.width  as i32, framing.focus_x).max(0) as u32;
+                let y = anchor(dst_h as i32 - src.height as i32, framing.focus_y).max(0) as u32;
                 blit_exact(dst, dst_w, dst_h, stride, src, x, y);
             } else {
-                let off_x = ((src.width  as i32 - dst_w as i32) / 2).max(0) as u32;
-                let off_y = ((src.height as i32 - dst_h as i32) / 2).max(0) as u32;
+                let off_x = anchor(src.width  as i32 - dst_w as i32, framing.focus_x).max(0) as u32;
+                let off_y = anchor(src.height as i32 - dst_h as i32, framing.focus_y).max(0) as u32;
                 blit_scaled_crop(dst, dst_w, dst_h, stride, src, src.width, src.height, off_x, off_y);
             }
         }
@@ -115,6 +134,72 @@ pub fn scale_image_into(
     }
 }
 
+/// Span mode: scale `src` onto the virtual canvas described by `view` using
+/// `mode`, and return only this output's `dst_w`×`dst_h` slice of it.
+///
+/// With a full viewport this is exactly [`scale_image`].
+pub fn scale_image_view(
+    src: &DecodedImage,
+    dst_w: u32,
+    dst_h: u32,
+    view: Viewport,
+    mode: ScaleMode,
+    framing: Framing,
+    bg: Colour,
+) -> Vec<u8> {
+    let mut out = vec![0u8; dst_w as usize * dst_h as usize * 4];
+    scale_image_view_into(src, &mut out, dst_w, dst_h, view, mode, framing, bg, &mut BlurScratch::default());
+    out
+}
+
+/// Zero-allocation variant of [`scale_image_view`].
+///
+/// The full canvas is never materialised: placement is computed in canvas
+/// space and only the rows/columns that land on this output are sampled.
+pub fn scale_image_view_into(
+    src: &DecodedImage,
+    dst: &mut [u8],
+    dst_w: u32,
+    dst_h: u32,
+    view: Viewport,
+    mode: ScaleMode,
+    framing: Framing,
+    bg: Colour,
+    blur: &mut BlurScratch,
+) {
+    if view.is_full(dst_w, dst_h) {
+        scale_image_into(src, dst, dst_w, dst_h, mode, framing, bg, blur);
+        return;
+    }
+
+    fill_bg(dst, dst_w, dst_h, bg);
+
+    if dst_w == 0 || dst_h == 0 || !src_is_valid(src) {
+        return;
+    }
+
+    let stride = dst_w as usize * 4;
+
+    match mode {
+        ScaleMode::Tile => {
+            tile_window(dst, dst_w, dst_h, stride, src, view.x, view.y);
+        }
+        _ => {
+            if mode == ScaleMode::FitBlur {
+                backdrop_xrgb(dst, dst_w, dst_h, stride, src, view, blur);
+            }
+            let (sw, sh, px, py) = canvas_placement(
+                src.width, src.height, view.canvas_width, view.canvas_height, mode, framing,
+            );
+            blit_scaled_window_bilinear_xrgb(
+                dst, dst_w, dst_h, stride,
+                &src.pixels, src.width, src.height, src.stride,
+                sw, sh, px - view.x, py - view.y,
+            );
+        }
+    }
+}
+
 /// Scale an RGBA compositing canvas (R,G,B,A packed, src_w×src_h×4) into an XRGB8888
 /// destination buffer (B,G,R,0 packed, dst_w×dst_h×4).
 ///
@@ -124,14 +209,16 @@ pub fn scale_image_into(
 ///
 /// Called from `GifFrameStream::next_frame_scaled_into`.
 pub fn scale_rgba_canvas_into(
-    rgba:  &[u8],
-    src_w: u32,
-    src_h: u32,
-    dst:   &mut [u8],
-    dst_w: u32,
-    dst_h: u32,
-    mode:  ScaleMode,
-    bg:    Colour,
+    rgba:    &[u8],
+    src_w:   u32,
+    src_h:   u32,
+    dst:     &mut [u8],
+    dst_w:   u32,
+    dst_h:   u32,
+    mode:    ScaleMode,
+    framing: Framing,
+    bg:      Colour,
+    blur:    &mut BlurScratch,
 ) {
     // Fast path: 1:1 with no letterboxing — avoid fill_bg + all mode logic.
     if src_w == dst_w && src_h == dst_h {
@@ -163,14 +250,21 @@ pub fn scale_rgba_canvas_into(
             blit_scaled_rgba(rgba, src_w, src_h, dst, dst_w, dst_h, dst_stride, 0, 0, dst_w, dst_h);
         }
         ScaleMode::Fill => {
-            let scale   = f32::max(dst_w as f32 / src_w as f32, dst_h as f32 / src_h as f32);
-            let sc_w    = (src_w as f32 * scale).round() as u32;
-            let sc_h    = (src_h as f32 * scale).round() as u32;
-            let off_x   = ((sc_w as i32 - dst_w as i32) / 2).max(0) as u32;
-            let off_y   = ((sc_h as i32 - dst_h as i32) / 2).max(0) as u32;
-            blit_scaled_crop_rgba(rgba, src_w, src_h, dst, dst_w, dst_h, dst_stride, sc_w, sc_h, off_x, off_y);
+            let (sc_w, sc_h, x, y) = canvas_placement(src_w, src_h, dst_w, dst_h, mode, framing);
+            if x <= 0 && y <= 0 {
+                blit_scaled_crop_rgba(rgba, src_w, src_h, dst, dst_w, dst_h, dst_stride, sc_w, sc_h, (-x) as u32, (-y) as u32);
+            } else {
+                blit_scaled_window_bilinear_rgba(
+                    dst, dst_w, dst_h, dst_stride,
+                    rgba, src_w, src_h,
+                    sc_w, sc_h, x, y,
+                );
+            }
         }
-        ScaleMode::Fit => {
+        ScaleMode::Fit | ScaleMode::FitBlur => {
+            if mode == ScaleMode::FitBlur {
+                backdrop_rgba(dst, dst_w, dst_h, dst_stride, rgba, src_w, src_h, Viewport::full(dst_w, dst_h), blur);
+            }
             let scale = f32::min(dst_w as f32 / src_w as f32, dst_h as f32 / src_h as f32);
             let sc_w  = (src_w as f32 * scale).round() as u32;
             let sc_h  = (src_h as f32 * scale).round() as u32;
@@ -180,12 +274,12 @@ pub fn scale_rgba_canvas_into(
         }
         ScaleMode::Center => {
             if src_w <= dst_w && src_h <= dst_h {
-                let x = ((dst_w as i32 - src_w as i32) / 2).max(0) as u32;
-                let y = ((dst_h as i32 - src_h as i32) / 2).max(0) as u32;
+                let x = anchor(dst_w as i32 - src_w as i32, framing.focus_x).max(0) as u32;
+                let y = anchor(dst_h as i32 - src_h as i32, framing.focus_y).max(0) as u32;
                 blit_exact_rgba(rgba, src_w, src_h, dst, dst_w, dst_h, dst_stride, x, y);
             } else {
-                let off_x = ((src_w as i32 - dst_w as i32) / 2).max(0) as u32;
-                let off_y = ((src_h as i32 - dst_h as i32) / 2).max(0) as u32;
+                let off_x = anchor(src_w as i32 - dst_w as i32, framing.focus_x).max(0) as u32;
+                let off_y = anchor(src_h as i32 - dst_h as i32, framing.focus_y).max(0) as u32;
                 blit_scaled_crop_rgba(rgba, src_w, src_h, dst, dst_w, dst_h, dst_stride, src_w, src_h, off_x, off_y);
             }
         }
@@ -195,6 +289,55 @@ pub fn scale_rgba_canvas_into(
     }
 }
 
+/// Span-mode counterpart of [`scale_rgba_canvas_into`]: only this output's
+/// `view` of the virtual canvas is written.
+pub fn scale_rgba_canvas_view_into(
+    rgba:    &[u8],
+    src_w:   u32,
+    src_h:   u32,
+    dst:     &mut [u8],
+    dst_w:   u32,
+    dst_h:   u32,
+    view:    Viewport,
+    mode:    ScaleMode,
+    framing: Framing,
+    bg:      Colour,
+    blur:    &mut BlurScratch,
+) {
+    if view.is_full(dst_w, dst_h) {
+        scale_rgba_canvas_into(rgba, src_w, src_h, dst, dst_w, dst_h, mode, framing, bg, blur);
+        return;
+    }
+
+    fill_bg(dst, dst_w, dst_h, bg);
+
+    if dst_w == 0 || dst_h == 0 || src_w == 0 || src_h == 0 { return; }
+
+    let expected_src = (src_w as usize).saturating_mul(src_h as usize).saturating_mul(4);
+    if rgba.len() < expected_src { return; }
+
+    let dst_stride = dst_w as usize * 4;
+
+    match mode {
+        ScaleMode::Tile => {
+            tile_rgba_window(rgba, src_w, src_h, dst, dst_w, dst_h, dst_stride, view.x, view.y);
+        }
+        _ => {
+            if mode == ScaleMode::FitBlur {
+                backdrop_rgba(dst, dst_w, dst_h, dst_stride, rgba, src_w, src_h, view, blur);
+            }
+            let (sw, sh, px, py) = canvas_placement(
+                src_w, src_h, view.canvas_width, view.canvas_height, mode, framing,
+            );
+            blit_scaled_window_bilinear_rgba(
+                dst, dst_w, dst_h, dst_stride,
+                rgba, src_w, src_h,
+                sw, sh, px - view.x, py - view.y,
+            );
+        }
+    }
+}
+
 // ── Shared helpers ──────────────────────────────────────────────────────────
 
 #[inline]
@@ -206,6 +349,98 @@ fn fill_bg(out: &mut [u8], w: u32, h: u32, bg: Colour) {
     dst.fill(px);
 }
 
+/// Validate a source buffer up front to prevent panics inside blit helpers.
+fn src_is_valid(src: &DecodedImage) -> bool {
+    if src.width == 0 || src.height == 0 {
+        return false;
+    }
+    let min_stride = src.width as usize * 4;
+    if src.stride < min_stride {
+        debug_assert!(
+            false,
+            "DecodedImage stride too small: stride={} min={} ({}x{})",
+            src.stride, min_stride, src.width, src.height
+        );
+        return false;
+    }
+    let needed = src.stride.saturating_mul(src.height as usize);
+    if src.pixels.len() < needed {
+        debug_assert!(
+            false,
+            "DecodedImage pixels too small: len={} needed={} ({}x{} stride={})",
+            src.pixels.len(), needed, src.width, src.height, src.stride
+        );
+        return false;
+    }
+    true
+}
+
+/// Where `mode` puts a `src_w`×`src_h` image on a `canvas_w`×`canvas_h` canvas:
+/// `(scaled_w, scaled_h, x, y)`. Offsets go negative when the image overhangs
+/// the canvas (fill / oversized center). Same rounding as the per-output paths.
+fn canvas_placement(
+    src_w:    u32,
+    src_h:    u32,
+    canvas_w: u32,
+    canvas_h: u32,
+    mode:     ScaleMode,
+    framing:  Framing,
+) -> (u32, u32, i32, i32) {
+    let (sw, sh) = match mode {
+        ScaleMode::Stretch => return (canvas_w, canvas_h, 0, 0),
+        ScaleMode::Fill | ScaleMode::Fit | ScaleMode::FitBlur => {
+            let sx = canvas_w as f32 / src_w as f32;
+            let sy = canvas_h as f32 / src_h as f32;
+            let scale = if matches!(mode, ScaleMode::Fill) {
+                sx.max(sy) * framing.zoom.clamp(MIN_ZOOM, MAX_ZOOM)
+            } else {
+                sx.min(sy)
+            };
+            (
+                (src_w as f32 * scale).round() as u32,
+                (src_h as f32 * scale).round() as u32,
+            )
+        }
+        ScaleMode::Center | ScaleMode::Tile => (src_w, src_h),
+    };
+    let framing = match mode {
+        ScaleMode::Fill   => framing,
+        ScaleMode::Center => Framing { offset_x: 0, offset_y: 0, ..framing },
+        _                 => Framing::default(),
+    };
+    let x = place(canvas_w as i32 - sw as i32, framing.focus_x, framing.offset_x);
+    let y = place(canvas_h as i32 - sh as i32, framing.focus_y, framing.offset_y);
+    (sw, sh, x, y)
+}
+
+/// [`anchor`] shifted by `offset` (which moves the crop window, so the image
+/// moves the other way), clamped so the image never uncovers an edge it
+/// covered, nor leaves the target when it already fits inside it.
+#[inline]
+fn place(slack: i32, f: f32, offset: i32) -> i32 {
+    let p = anchor(slack, f).saturating_sub(offset);
+    if slack < 0 { p.clamp(slack, 0) } else { p.clamp(0, slack) }
+}
+
+/// Offset that lines up fraction `f` of the image with fraction `f` of the
+/// target, given `slack` = target size - image size (negative = overhang).
+/// Truncates toward zero, so 0.5 gives exactly the old `slack / 2`.
+#[inline]
+fn anchor(slack: i32, f: f32) -> i32 {
+    (slack as f32 * f.clamp(0.0, 1.0)) as i32
+}
+
+/// Fill-mode geometry for a `dst_w`×`dst_h` target: `(scaled_w, scaled_h, off_x, off_y)`
+/// for the crop blitters.
+fn cover_crop(src_w: u32, src_h: u32, dst_w: u32, dst_h: u32) -> (u32, u32, u32, u32) {
+    let scale = f32::max(dst_w as f32 / src_w as f32, dst_h as f32 / src_h as f32);
+    let sc_w  = (src_w as f32 * scale).round() as u32;
+    let sc_h  = (src_h as f32 * scale).round() as u32;
+    let off_x = ((sc_w as i32 - dst_w as i32) / 2).max(0) as u32;
+    let off_y = ((sc_h as i32 - dst_h as i32) / 2).max(0) as u32;
+    (sc_w, sc_h, off_x, off_y)
+}
+
 /// Safely fetch a full source row slice.
 #[inline]
 fn src_row(src: &DecodedImage, sy: u32) -> Option<&[u8]> {
@@ -214,6 +449,153 @@ fn src_row(src: &DecodedImage, sy: u32) -> Option<&[u8]> {
     src.pixels.get(start..end)
 }
 
+// ── Blurred backdrop (FitBlur) ──────────────────────────────────────────────
+//
+// The backdrop is built at 1/BLUR_DOWNSCALE of the canvas (1/64 of the pixels),
+// box-blurred there and stretched back up with the bilinear window blit, so
+// the only extra memory is that small copy and one line of scratch. Both live
+// in a caller-owned `BlurScratch`, so players re-scaling every frame reuse
+// them. Three box passes approximate a gaussian; the stretch smooths what's
+// left of the boxes.
+
+const BLUR_DOWNSCALE: u32   = 8;
+const BLUR_PASSES:    usize = 3;
+/// Brightness kept after blurring, out of 256 — dark enough that the sharp
+/// image reads as the subject.
+const BLUR_DIM:       u32   = 140;
+
+/// Reusable buffers for the FitBlur backdrop. Empty until first used; one-shot
+/// callers can pass a fresh `BlurScratch::default()`.
+#[derive(Default)]
+pub struct BlurScratch {
+    small: Vec<u8>,
+    line:  Vec<u8>,
+}
+
+impl BlurScratch {
+    /// The small copy, sized (and zeroed) for a `bw`×`bh` backdrop.
+    fn small(&mut self, bw: u32, bh: u32) -> &mut [u8] {
+        self.small.clear();
+        self.small.resize(bw as usize * bh as usize * 4, 0);
+        &mut self.small
+    }
+}
+
+/// Size of the small backdrop copy for a canvas.
+#[inline]
+fn backdrop_dims(canvas_w: u32, canvas_h: u32) -> (u32, u32) {
+    ((canvas_w / BLUR_DOWNSCALE).max(1), (canvas_h / BLUR_DOWNSCALE).max(1))
+}
+
+fn backdrop_xrgb(
+    dst:        &mut [u8],
+    dst_w:      u32,
+    dst_h:      u32,
+    dst_stride: usize,
+    src:        &DecodedImage,
+    view:       Viewport,
+    blur:       &mut BlurScratch,
+) {
+    let (bw, bh) = backdrop_dims(view.canvas_width, view.canvas_height);
+    let (sc_w, sc_h, off_x, off_y) = cover_crop(src.width, src.height, bw, bh);
+    blit_scaled_crop(blur.small(bw, bh), bw, bh, bw as usize * 4, src, sc_w, sc_h, off_x, off_y);
+    blur_backdrop_into(dst, dst_w, dst_h, dst_stride, blur, bw, bh, view);
+}
+
+fn backdrop_rgba(
+    dst:        &mut [u8],
+    dst_w:      u32,
+    dst_h:      u32,
+    dst_stride: usize,
+    rgba:       &[u8],
+    src_w:      u32,
+    src_h:      u32,
+    view:       Viewport,
+    blur:       &mut BlurScratch,
+) {
+    let (bw, bh) = backdrop_dims(view.canvas_width, view.canvas_height);
+    let (sc_w, sc_h, off_x, off_y) = cover_crop(src_w, src_h, bw, bh);
+    blit_scaled_crop_rgba(rgba, src_w, src_h, blur.small(bw, bh), bw, bh, bw as usize * 4, sc_w, sc_h, off_x, off_y);
+    blur_backdrop_into(dst, dst_w, dst_h, dst_stride, blur, bw, bh, view);
+}
+
+/// Blur and darken the small XRGB copy in place, then stretch it over the
+/// whole canvas and write this output's `view` of it into `dst`.
+fn blur_backdrop_into(
+    dst:        &mut [u8],
+    dst_w:      u32,
+    dst_h:      u32,
+    dst_stride: usize,
+    blur:       &mut BlurScratch,
+    bw:         u32,
+    bh:         u32,
+    view:       Viewport,
+) {
+    let BlurScratch { small, line } = blur;
+    let radius = (bw.max(bh) / 20).max(1) as usize;
+    box_blur_xrgb(small, bw as usize, bh as usize, radius, line);
+
+    for px in small.chunks_exact_mut(4) {
+        px[0] = ((px[0] as u32 * BLUR_DIM) >> 8) as u8;
+        px[1] = ((px[1] as u32 * BLUR_DIM) >> 8) as u8;
+        px[2] = ((px[2] as u32 * BLUR_DIM) >> 8) as u8;
+    }
+
+    blit_scaled_window_bilinear_xrgb(
+        dst, dst_w, dst_h, dst_stride,
+        small, bw, bh, bw as usize * 4,
+        view.canvas_width, view.canvas_height, -view.x, -view.y,
+    );
+}
+
+/// Separable box blur, `BLUR_PASSES` times over rows then columns. `line` is
+/// grown to fit the longer side if it's too short.
+fn box_blur_xrgb(buf: &mut [u8], w: usize, h: usize, radius: usize, line: &mut Vec<u8>) {
+    if line.len() < w.max(h) * 4 {
+        line.resize(w.max(h) * 4, 0);
+    }
+    for _ in 0..BLUR_PASSES {
+        for y in 0..h {
+            box_line(buf, y * w * 4, 4, w, radius, line);
+        }
+        for x in 0..w {
+            box_line(buf, x * 4, w * 4, h, radius, line);
+        }
+    }
+}
+
+/// Running-sum box filter over `n` pixels of `buf`, the first at byte `start`
+/// and each `step` bytes after the last. Edges clamp. `line` is scratch.
+fn box_line(buf: &mut [u8], start: usize, step: usize, n: usize, radius: usize, line: &mut [u8]) {
+    for i in 0..n {
+        let si = start + i * step;
+        line[i * 4..i * 4 + 3].copy_from_slice(&buf[si..si + 3]);
+    }
+
+    let last = n as isize - 1;
+    let at   = |i: isize| i.clamp(0, last) as usize * 4;
+    let r    = radius as isize;
+    let win  = (2 * radius + 1) as u32;
+
+    let mut sum = [0u32; 3];
+    for k in -r..=r {
+        let j = at(k);
+        for (c, s) in sum.iter_mut().enumerate() {
+            *s += line[j + c] as u32;
+        }
+    }
+
+    for i in 0..n {
+        let di = start + i * step;
+        let add = at(i as isize + r + 1);
+        let sub = at(i as isize - r);
+        for (c, s) in sum.iter_mut().enumerate() {
+            buf[di + c] = ((*s + win / 2) / win) as u8;
+            *s = *s + line[add + c] as u32 - line[sub + c] as u32;
+        }
+    }
+}
+
 // ── XRGB blit helpers ───────────────────────────────────────────────────────
 //
 // blit_scaled and blit_scaled_crop now use bilinear filtering with an optional
@@ -423,6 +805,79 @@ fn blit_scaled_crop_bilinear_xrgb(
         });
 }
 
+/// Fixed-point bilinear blit for XRGB8888 where the scaled image sits at
+/// (`px`, `py`) in destination space. Either offset may be negative (image
+/// starts left of / above this output), which is what span mode needs.
+/// Rows processed in parallel via rayon.
+fn blit_scaled_window_bilinear_xrgb(
+    out:        &mut [u8],
+    dst_w:      u32,
+    dst_h:      u32,
+    dst_stride: usize,
+    src_pix:    &[u8],
+    src_w:      u32,
+    src_h:      u32,
+    src_stride: usize,
+    scaled_w:   u32,
+    scaled_h:   u32,
+    px:         i32,
+    py:         i32,
+) {
+    if scaled_w == 0 || scaled_h == 0 || dst_w == 0 || dst_h == 0 { return; }
+
+    let dx    = px.max(0) as u32;
+    let dy    = py.max(0) as u32;
+    let off_x = px.min(0).unsigned_abs();
+    let off_y = py.min(0).unsigned_abs();
+
+    let vis_w = scaled_w.saturating_sub(off_x).min(dst_w.saturating_sub(dx)) as usize;
+    let vis_h = scaled_h.saturating_sub(off_y).min(dst_h.saturating_sub(dy)) as usize;
+    if vis_w == 0 || vis_h == 0 { return; }
+
+    let sy_scale = src_h as f32 / scaled_h as f32;
+    let sh1      = (src_h - 1) as usize;
+    let dx_off   = dx as usize * 4;
+
+    let xs = build_x_table(vis_w, src_w, scaled_w, off_x);
+
+    let dst_start = dy as usize * dst_stride;
+    let region    = &mut out[dst_start..dst_start + vis_h * dst_stride];
+
+    region
+        .par_chunks_mut(dst_stride)
+        .enumerate()
+        .for_each(|(oy, row_buf)| {
+            let sy_sc = oy as u32 + off_y;
+            let fy   = (sy_sc as f32 + 0.5) * sy_scale - 0.5;
+            let y0   = (fy.floor() as isize).clamp(0, sh1 as isize) as usize;
+            let y1   = (y0 + 1).min(sh1);
+            let iwy  = ((fy - y0 as f32).clamp(0.0, 1.0) * 256.0) as u32;
+            let iwy0 = 256 - iwy;
+
+            let r0 = &src_pix[y0 * src_stride .. y0 * src_stride + src_stride];
+            let r1 = &src_pix[y1 * src_stride .. y1 * src_stride + src_stride];
+
+            let dst_row = &mut row_buf[dx_off .. dx_off + vis_w * 4];
+
+            for (ox, s) in xs.iter().enumerate() {
+                let di = ox * 4;
+                // B
+                let top = r0[s.si0]   as u32 * s.w0 + r0[s.si1]   as u32 * s.w1;
+                let bot = r1[s.si0]   as u32 * s.w0 + r1[s.si1]   as u32 * s.w1;
+                dst_row[di]   = ((top * iwy0 + bot * iwy + (1 << 15)) >> 16) as u8;
+                // G
+                let top = r0[s.si0+1] as u32 * s.w0 + r0[s.si1+1] as u32 * s.w1;
+                let bot = r1[s.si0+1] as u32 * s.w0 + r1[s.si1+1] as u32 * s.w1;
+                dst_row[di+1] = ((top * iwy0 + bot * iwy + (1 << 15)) >> 16) as u8;
+                // R
+                let top = r0[s.si0+2] as u32 * s.w0 + r0[s.si1+2] as u32 * s.w1;
+                let bot = r1[s.si0+2] as u32 * s.w0 + r1[s.si1+2] as u32 * s.w1;
+                dst_row[di+2] = ((top * iwy0 + bot * iwy + (1 << 15)) >> 16) as u8;
+                dst_row[di+3] = 0;
+            }
+        });
+}
+
 fn blit_exact(
     out: &mut [u8],
     dst_w: u32,
@@ -477,6 +932,43 @@ fn tile(
     }
 }
 
+/// Tile with the tile grid anchored at the canvas origin; (`ox`, `oy`) is this
+/// output's offset into the canvas so tiles line up across spanned outputs.
+fn tile_window(
+    out: &mut [u8],
+    dst_w: u32,
+    dst_h: u32,
+    dst_stride: usize,
+    src: &DecodedImage,
+    ox: i32,
+    oy: i32,
+) {
+    if dst_w == 0 || dst_h == 0 || src.width == 0 || src.height == 0 { return; }
+
+    let row_len = dst_w as usize * 4;
+    let sw = src.width as i64;
+    let sh = src.height as i64;
+
+    for ty in 0..dst_h {
+        let sy = (ty as i64 + oy as i64).rem_euclid(sh) as u32;
+        let Some(sr) = src_row(src, sy) else { return; };
+
+        let dst_off = ty as usize * dst_stride;
+        let Some(dr) = out.get_mut(dst_off..dst_off + row_len) else { return; };
+
+        for tx in 0..dst_w as usize {
+            let sx = (tx as i64 + ox as i64).rem_euclid(sw) as usize;
+            let s  = sx * 4;
+            let d  = tx * 4;
+            if s + 2 >= sr.len() { break; }
+            dr[d]     = sr[s];
+            dr[d + 1] = sr[s + 1];
+            dr[d + 2] = sr[s + 2];
+            dr[d + 3] = 0;
+        }
+    }
+}
+
 // ── RGBA canvas blit helpers ────────────────────────────────────────────────
 // Same geometry logic as the XRGB helpers above, but source is R,G,B,A and
 // we premultiply alpha when writing the XRGB destination.
@@ -654,6 +1146,74 @@ fn blit_scaled_crop_bilinear_rgba(
             }
         });
 }
+/// Bilinear RGBA→XRGB blit with a signed placement (span mode).
+/// Rows processed in parallel via rayon.
+fn blit_scaled_window_bilinear_rgba(
+    out:        &mut [u8],
+    dst_w:      u32,
+    dst_h:      u32,
+    dst_stride: usize,
+    src_pix:    &[u8],
+    src_w:      u32,
+    src_h:      u32,
+    scaled_w:   u32,
+    scaled_h:   u32,
+    px:         i32,
+    py:         i32,
+) {
+    if scaled_w == 0 || scaled_h == 0 || dst_w == 0 || dst_h == 0 { return; }
+
+    let dx    = px.max(0) as u32;
+    let dy    = py.max(0) as u32;
+    let off_x = px.min(0).unsigned_abs();
+    let off_y = py.min(0).unsigned_abs();
+
+    let vis_w = scaled_w.saturating_sub(off_x).min(dst_w.saturating_sub(dx)) as usize;
+    let vis_h = scaled_h.saturating_sub(off_y).min(dst_h.saturating_sub(dy)) as usize;
+    if vis_w == 0 || vis_h == 0 { return; }
+
+    let src_stride = src_w as usize * 4;
+    let sy_scale   = src_h as f32 / scaled_h as f32;
+    let sh1        = (src_h - 1) as usize;
+    let dx_off     = dx as usize * 4;
+
+    let xs = build_x_table(vis_w, src_w, scaled_w, off_x);
+
+    let dst_start = dy as usize * dst_stride;
+    let region    = &mut out[dst_start..dst_start + vis_h * dst_stride];
+
+    region
+        .par_chunks_mut(dst_stride)
+        .enumerate()
+        .for_each(|(oy, row_buf)| {
+            let sy_sc = oy as u32 + off_y;
+            let fy   = (sy_sc as f32 + 0.5) * sy_scale - 0.5;
+            let y0   = (fy.floor() as isize).clamp(0, sh1 as isize) as usize;
+            let y1   = (y0 + 1).min(sh1);
+            let iwy  = ((fy - y0 as f32).clamp(0.0, 1.0) * 256.0) as u32;
+            let iwy0 = 256 - iwy;
+
+            let r0 = &src_pix[y0 * src_stride .. y0 * src_stride + src_stride];
+            let r1 = &src_pix[y1 * src_stride .. y1 * src_stride + src_stride];
+
+            let dst_row = &mut row_buf[dx_off .. dx_off + vis_w * 4];
+
+            for (ox, s) in xs.iter().enumerate() {
+                let di = ox * 4;
+                let blend = |ch: usize| -> u32 {
+                    let top = r0[s.si0+ch] as u32 * s.w0 + r0[s.si1+ch] as u32 * s.w1;
+                    let bot = r1[s.si0+ch] as u32 * s.w0 + r1[s.si1+ch] as u32 * s.w1;
+                    (top * iwy0 + bot * iwy + (1 << 15)) >> 16
+                };
+                let rv = blend(0); let gv = blend(1); let bv = blend(2); let av = blend(3);
+                dst_row[di]   = ((bv * av + 127) / 255) as u8;
+                dst_row[di+1] = ((gv * av + 127) / 255) as u8;
+                dst_row[di+2] = ((rv * av + 127) / 255) as u8;
+                dst_row[di+3] = 0;
+            }
+        });
+}
+
 fn blit_exact_rgba(
     rgba:       &[u8],
     src_w:      u32,
@@ -720,3 +1280,38 @@ fn tile_rgba(
         }
     }
 }
+
+fn tile_rgba_window(
+    rgba:       &[u8],
+    src_w:      u32,
+    src_h:      u32,
+    out:        &mut [u8],
+    dst_w:      u32,
+    dst_h:      u32,
+    dst_stride: usize,
+    ox:         i32,
+    oy:         i32,
+) {
+    if dst_w == 0 || dst_h == 0 || src_w == 0 || src_h == 0 { return; }
+
+    let src_stride = src_w as usize * 4;
+    let row_len    = dst_w as usize * 4;
+
+    for ty in 0..dst_h {
+        let sy  = (ty as i64 + oy as i64).rem_euclid(src_h as i64) as usize;
+        let sr0 = sy * src_stride;
+        if sr0 + src_stride > rgba.len() { break; }
+        let sr = &rgba[sr0..sr0 + src_stride];
+
+        let dst_off = ty as usize * dst_stride;
+        let Some(dr) = out.get_mut(dst_off..dst_off + row_len) else { break; };
+
+        for tx in 0..dst_w as usize {
+            let sx = (tx as i64 + ox as i64).rem_euclid(src_w as i64) as usize;
+            let si = sx * 4;
+            let di = tx * 4;
+            if si + 3 >= sr.len() { break; }
+            put_rgba_pixel(sr, si, dr, di);
+        }
+    }
+}
@@ -3,28 +3,101 @@
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+use super::easing::Easing;
+
+/// Where the new wallpaper comes in from.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum WaveDir {
     Left,
     Right,
+    Top,
+    Bottom,
+    /// Direction of travel in degrees, CSS-style like gradients
+    /// (0 = bottom→top, 90 = left→right).
+    Angle(f32),
+}
+
+impl WaveDir {
+    /// The preset as an angle of travel.
+    pub fn angle_deg(self) -> f32 {
+        match self {
+            WaveDir::Left     => 90.0,
+            WaveDir::Right    => 270.0,
+            WaveDir::Top      => 180.0,
+            WaveDir::Bottom   => 0.0,
+            WaveDir::Angle(a) => a,
+        }
+    }
+}
+
+/// Which way the new wallpaper moves in a Slide or Cover.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SlideDir {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// One axis of a point on the canvas.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Coord {
+    Px(u32),
+    /// 0.0..=1.0 of the canvas width or height.
+    Fraction(f32),
+}
+
+impl Coord {
+    #[inline]
+    pub fn resolve(self, extent: u32) -> f32 {
+        match self {
+            Coord::Px(px)      => px as f32,
+            Coord::Fraction(f) => f * extent as f32,
+        }
+    }
+}
+
+/// Where the Drop circle starts. Canvas space, so under span every output
+/// sees the same point.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum DropOrigin {
+    #[default]
+    Centre,
+    At { x: Coord, y: Coord },
+    /// Picked from the transition's `seed`.
+    Random,
+}
+
+/// Threshold pattern for Dissolve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DissolveNoise {
+    /// Independent per cell; clumps and gaps like TV static.
+    White,
+    /// Void-and-cluster blue noise: evenly spread, no clumps.
+    #[default]
+    Blue,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "kind", rename_all = "PascalCase")]
 pub enum Transition {
     None,
 
-    /// Drop: hard circle expands from screen centre, revealing new image.
-    /// (No blending / no feather in the renderer; this is a crisp cut.)
+    /// Drop: circle expands from `origin`, revealing new image.
     Drop {
         duration_ms: u32,
-        /// Kept for forward-compat, currently ignored by the hard renderer.
+        /// Width of the feathered edge. 0 = crisp cut.
         softness_px: u16,
-        /// Reserved.
+        /// Picks the point for `DropOrigin::Random`.
         seed: u32,
         /// Quantize progress into N steps. 0 = smooth.
         #[serde(default)]
         steps: u16,
+        #[serde(default)]
+        origin: DropOrigin,
+        /// Overrides the transition's own curve.
+        #[serde(default)]
+        easing: Option<Easing>,
     },
 
     /// Fade: crossfade old->new.
@@ -33,6 +106,9 @@ pub enum Transition {
         /// Quantize progress into N steps. 0 = smooth.
         #[serde(default)]
         steps: u16,
+        /// Overrides the transition's own curve.
+        #[serde(default)]
+        easing: Option<Easing>,
     },
 
     /// Directional wavefront wipe.
@@ -45,6 +121,63 @@ pub enum Transition {
         /// Quantize progress into N steps. 0 = smooth.
         #[serde(default)]
         steps: u16,
+        /// Overrides the transition's own curve.
+        #[serde(default)]
+        easing: Option<Easing>,
+    },
+
+    /// Slide: the new wallpaper pushes the old one off-screen.
+    Slide {
+        duration_ms: u32,
+        dir: SlideDir,
+        /// Quantize progress into N steps. 0 = smooth.
+        #[serde(default)]
+        steps: u16,
+        /// Overrides the transition's own curve.
+        #[serde(default)]
+        easing: Option<Easing>,
+    },
+
+    /// Cover: the new wallpaper slides in over the old one, which stays put.
+    Cover {
+        duration_ms: u32,
+        dir: SlideDir,
+        /// Quantize progress into N steps. 0 = smooth.
+        #[serde(default)]
+        steps: u16,
+        /// Overrides the transition's own curve.
+        #[serde(default)]
+        easing: Option<Easing>,
+    },
+
+    /// Pixelate: the old image breaks into ever larger blocks, swaps at the
+    /// peak, and the new one resolves back to full detail.
+    Pixelate {
+        duration_ms: u32,
+        /// Block size at the swap, in px.
+        max_block_px: u16,
+        /// Quantize progress into N steps. 0 = smooth.
+        #[serde(default)]
+        steps: u16,
+        /// Overrides the transition's own curve.
+        #[serde(default)]
+        easing: Option<Easing>,
+    },
+
+    /// Dissolve: each cell flips to the new image once its noise value
+    /// falls below the progress. The same seed gives the same pattern.
+    Dissolve {
+        duration_ms: u32,
+        seed: u32,
+        /// Cell size in px; 1 = per pixel.
+        grain_px: u16,
+        noise: DissolveNoise,
+        /// Quantize progress into N steps. 0 = smooth.
+        #[serde(default)]
+        steps: u16,
+        /// Overrides the transition's own curve.
+        #[serde(default)]
+        easing: Option<Easing>,
     },
 }
 
@@ -56,6 +189,10 @@ impl Transition {
             Transition::Drop { duration_ms, .. } => duration_ms,
             Transition::Fade { duration_ms, .. } => duration_ms,
             Transition::Wave { duration_ms, .. } => duration_ms,
+            Transition::Slide { duration_ms, .. } => duration_ms,
+            Transition::Cover { duration_ms, .. } => duration_ms,
+            Transition::Pixelate { duration_ms, .. } => duration_ms,
+            Transition::Dissolve { duration_ms, .. } => duration_ms,
         }
     }
 
@@ -66,6 +203,24 @@ impl Transition {
             Transition::Drop { steps, .. } => steps,
             Transition::Fade { steps, .. } => steps,
             Transition::Wave { steps, .. } => steps,
+            Transition::Slide { steps, .. } => steps,
+            Transition::Cover { steps, .. } => steps,
+            Transition::Pixelate { steps, .. } => steps,
+            Transition::Dissolve { steps, .. } => steps,
+        }
+    }
+
+    #[inline]
+    pub fn easing(&self) -> Option<Easing> {
+        match *self {
+            Transition::None => None,
+            Transition::Drop { easing, .. } => easing,
+            Transition::Fade { easing, .. } => easing,
+            Transition::Wave { easing, .. } => easing,
+            Transition::Slide { easing, .. } => easing,
+            Transition::Cover { easing, .. } => easing,
+            Transition::Pixelate { easing, .. } => easing,
+            Transition::Dissolve { easing, .. } => easing,
         }
     }
 }
@@ -0,0 +1,28 @@
+// Author: Dustin Pilgrim
+// License: MIT
+
+/// Where one output sits inside a larger virtual canvas (span mode).
+///
+/// Everything is in the output's own physical pixels: `x`/`y` is the offset of
+/// the output's top-left corner from the canvas origin, and the canvas size is
+/// the whole layout expressed at this output's pixel density.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Viewport {
+    pub canvas_width:  u32,
+    pub canvas_height: u32,
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Viewport {
+    /// The canvas is exactly the output (the non-span case).
+    #[inline]
+    pub fn full(width: u32, height: u32) -> Self {
+        Self { canvas_width: width, canvas_height: height, x: 0, y: 0 }
+    }
+
+    #[inline]
+    pub fn is_full(&self, width: u32, height: u32) -> bool {
+        *self == Self::full(width, height)
+    }
+}
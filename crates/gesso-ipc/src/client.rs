@@ -5,7 +5,7 @@ use std::os::unix::net::UnixStream;
 use std::path::{Path, PathBuf};
 
 use crate::frame;
-use crate::protocol::{Request, Response};
+use crate::protocol::{Event, Request, Response};
 
 const DIR_NAME: &str = "gesso";
 const SOCK_NAME: &str = "gesso.sock";
@@ -31,3 +31,30 @@ pub fn request(sock: impl AsRef<Path>, req: &Request) -> frame::Result<Response>
     let _ = stream.shutdown(std::net::Shutdown::Write);
     frame::recv(&mut stream)
 }
+
+/// Open an event subscription. Fails if the daemon doesn't acknowledge it.
+pub fn subscribe(sock: impl AsRef<Path>) -> frame::Result<EventStream> {
+    let mut stream = UnixStream::connect(sock)?;
+    frame::send(&mut stream, &Request::Subscribe)?;
+    match frame::recv(&mut stream)? {
+        Response::Ok => Ok(EventStream { stream }),
+        Response::Error { message } => Err(frame::FrameError::Decode(message)),
+        _ => Err(frame::FrameError::Decode("unexpected reply to subscribe".into())),
+    }
+}
+
+/// Blocking reader over a subscription.
+pub struct EventStream {
+    stream: UnixStream,
+}
+
+impl EventStream {
+    /// Next event, or `None` once the daemon closes the connection.
+    pub fn next_event(&mut self) -> frame::Result<Option<Event>> {
+        match frame::recv(&mut self.stream) {
+            Ok(ev) => Ok(Some(ev)),
+            Err(frame::FrameError::Io(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
@@ -6,7 +6,7 @@ mod frame;
 mod client;
 mod server;
 
-pub use client::{request, default_socket_path};
+pub use client::{request, default_socket_path, subscribe, EventStream};
 pub use server::bind;
-pub use server::run_server;
+pub use server::{run_server, EventHub};
 pub use protocol::*;
@@ -1,6 +1,8 @@
 // Author: Dustin Pilgrim
 // License: MIT
 
+use std::str::FromStr;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,21 +11,192 @@ pub enum OutputSel {
     Named(Vec<String>),
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Mode {
     Fill,
     Fit,
     Stretch,
     Center,
     Tile,
+    /// Fit over a blurred, darkened copy of the image.
+    FitBlur,
+}
+
+/// A point on the image as fractions of its size (0.5, 0.5 = centre). Fill
+/// and Center crop so it lines up with the same point of the output.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Focus {
+    pub x: f32,
+    pub y: f32,
+}
+
+/// Slow pan and zoom across a still image ("Ken Burns"). One pass drifts from
+/// the still's own framing to a zoomed-in point off to one side, the next
+/// drifts back.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Motion {
+    /// Length of one pass in seconds.
+    pub period_secs: u32,
+    /// Extra zoom at the far end of a pass (0.15 = 15% closer).
+    pub zoom: f32,
+    /// Frames rendered per second; low rates keep CPU use down.
+    pub fps: u8,
+}
+
+/// Shift of Fill's crop window in output pixels (+dx right, +dy down).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Offset {
+    pub dx: i32,
+    pub dy: i32,
+}
+
+/// How often an animation plays before it stops on its last frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Loop {
+    Forever,
+    /// Total plays; 1 = once.
+    Times(u16),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum WaveDir {
     Left,
     Right,
+    Top,
+    Bottom,
+    /// Direction of travel in degrees (0 = bottom→top, 90 = left→right).
+    Angle(f32),
+}
+
+/// Which way the new wallpaper moves in a Slide or Cover.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SlideDir {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// One axis of a point on the canvas.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Coord {
+    Px(u32),
+    /// 0.0..=1.0 of the canvas width or height.
+    Fraction(f32),
+}
+
+/// Where a Drop circle starts. Under span this is a point on the whole
+/// canvas, not on each output.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum DropOrigin {
+    #[default]
+    Centre,
+    At { x: Coord, y: Coord },
+    /// A fresh point for every change.
+    Random,
+}
+
+/// "random", "centre", or X,Y with each axis in px ("100") or percent
+/// ("20%"). Shared by the CLI's `--at` and the daemon's config `at`.
+impl FromStr for DropOrigin {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let coord = |v: &str| -> Result<Coord, String> {
+            let v = v.trim();
+            match v.strip_suffix('%') {
+                Some(pct) => match pct.trim().parse::<f32>() {
+                    Ok(p) if (0.0..=100.0).contains(&p) => Ok(Coord::Fraction(p / 100.0)),
+                    _ => Err(format!("invalid coordinate '{v}' (percent must be 0%..100%)")),
+                },
+                None => v
+                    .parse()
+                    .map(Coord::Px)
+                    .map_err(|_| format!("invalid coordinate '{v}' (use px or %)")),
+            }
+        };
+        match s.trim() {
+            "random"            => Ok(DropOrigin::Random),
+            "centre" | "center" => Ok(DropOrigin::Centre),
+            other               => {
+                let Some((x, y)) = other.split_once(',') else {
+                    return Err(format!("invalid drop origin '{other}' (X,Y, random or centre, e.g. 20%,80%)"));
+                };
+                Ok(DropOrigin::At { x: coord(x)?, y: coord(y)? })
+            }
+        }
+    }
+}
+
+/// Progress curve for a transition. Names follow easings.net; the bezier is
+/// CSS `cubic-bezier(x1, y1, x2, y2)`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Easing {
+    Linear,
+    InQuad,
+    OutQuad,
+    InOutQuad,
+    InCubic,
+    OutCubic,
+    InOutCubic,
+    InExpo,
+    OutExpo,
+    InOutExpo,
+    CubicBezier { x1: f32, y1: f32, x2: f32, y2: f32 },
+}
+
+/// Shared by the CLI's `--easing` and the daemon's config `easing`.
+impl FromStr for Easing {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let s = s.trim();
+        if let Some(args) = s.strip_prefix("cubic-bezier(").and_then(|r| r.strip_suffix(')')) {
+            let bad = || format!("invalid cubic-bezier '{s}' (expected four numbers)");
+            let v = args
+                .split(',')
+                .map(|p| p.trim().parse::<f32>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| bad())?;
+            let [x1, y1, x2, y2] = v[..] else {
+                return Err(bad());
+            };
+            if !(0.0..=1.0).contains(&x1) || !(0.0..=1.0).contains(&x2) {
+                return Err("cubic-bezier x values must be within 0..1".into());
+            }
+            if !(y1.is_finite() && y2.is_finite()) {
+                return Err("cubic-bezier y values must be numbers".into());
+            }
+            return Ok(Easing::CubicBezier { x1, y1, x2, y2 });
+        }
+        Ok(match s {
+            "linear"            => Easing::Linear,
+            "ease-in-quad"      => Easing::InQuad,
+            "ease-out-quad"     => Easing::OutQuad,
+            "ease-in-out-quad"  => Easing::InOutQuad,
+            "ease-in-cubic"     => Easing::InCubic,
+            "ease-out-cubic"    => Easing::OutCubic,
+            "ease-in-out-cubic" => Easing::InOutCubic,
+            "ease-in-expo"      => Easing::InExpo,
+            "ease-out-expo"     => Easing::OutExpo,
+            "ease-in-out-expo"  => Easing::InOutExpo,
+            other               => return Err(format!(
+                "unknown easing '{other}' (linear, ease-{{in,out,in-out}}-{{quad,cubic,expo}}, cubic-bezier(x1,y1,x2,y2))"
+            )),
+        })
+    }
+}
+
+/// Threshold pattern for Dissolve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DissolveNoise {
+    White,
+    #[default]
+    Blue,
 }
 
+/// A `duration_ms` of 0 asks the daemon for the kind's default, tuned for
+/// the target (image or colour) and the image's resolved mode.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Transition {
     None,
@@ -31,41 +204,183 @@ pub enum Transition {
         duration_ms: u32,
         #[serde(default)]
         steps: Option<u16>,
+        #[serde(default)]
+        origin: DropOrigin,
+        /// Feathered edge in px; None = crisp.
+        #[serde(default)]
+        softness_px: Option<u16>,
+        /// None = the transition's own curve.
+        #[serde(default)]
+        easing: Option<Easing>,
     },
     Fade {
         duration_ms: u32,
         #[serde(default)]
         steps: Option<u16>,
+        /// None = the transition's own curve.
+        #[serde(default)]
+        easing: Option<Easing>,
     },
     Wave {
         duration_ms: u32,
         dir: WaveDir,
         #[serde(default)]
         steps: Option<u16>,
+        /// None = the transition's own curve.
+        #[serde(default)]
+        easing: Option<Easing>,
+    },
+    /// New wallpaper pushes the old one off-screen.
+    Slide {
+        duration_ms: u32,
+        dir: SlideDir,
+        #[serde(default)]
+        steps: Option<u16>,
+        /// None = the transition's own curve.
+        #[serde(default)]
+        easing: Option<Easing>,
+    },
+    /// New wallpaper slides in over the old one.
+    Cover {
+        duration_ms: u32,
+        dir: SlideDir,
+        #[serde(default)]
+        steps: Option<u16>,
+        /// None = the transition's own curve.
+        #[serde(default)]
+        easing: Option<Easing>,
+    },
+    /// Old wallpaper breaks into blocks, new one resolves out of them.
+    Pixelate {
+        duration_ms: u32,
+        /// Block size at the peak, in px; None = the daemon's default.
+        #[serde(default)]
+        max_block_px: Option<u16>,
+        #[serde(default)]
+        steps: Option<u16>,
+        /// None = the transition's own curve.
+        #[serde(default)]
+        easing: Option<Easing>,
+    },
+    /// Pixels flip from old to new in a seeded noise pattern.
+    Dissolve {
+        duration_ms: u32,
+        /// Cell size in px; None = the daemon's default.
+        #[serde(default)]
+        grain_px: Option<u16>,
+        #[serde(default)]
+        noise: DissolveNoise,
+        /// Fixes the pattern; None = a new one for every change.
+        #[serde(default)]
+        seed: Option<u32>,
+        #[serde(default)]
+        steps: Option<u16>,
+        /// None = the transition's own curve.
+        #[serde(default)]
+        easing: Option<Easing>,
     },
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Rgb {
     pub r: u8,
     pub g: u8,
     pub b: u8,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum GradientShape {
+    /// CSS-style angle in degrees (0 = bottom→top, 90 = left→right).
+    Linear { angle: f32 },
+    /// Centre as fractions of the output (0.5, 0.5 = middle); radius as a
+    /// fraction of the distance to the farthest corner.
+    Radial { cx: f32, cy: f32, radius: f32 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GradientStop {
+    /// 0.0..=1.0 along the gradient.
+    pub pos: f32,
+    pub colour: Rgb,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Gradient {
+    pub shape: GradientShape,
+    pub stops: Vec<GradientStop>,
+    /// Ordered dither to hide banding on dark or slow ramps.
+    #[serde(default)]
+    pub dither: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SetTarget {
     ImagePath(String),
     Colour(Rgb),
     Unset,
+    Gradient(Gradient),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SetRequest {
     pub outputs: OutputSel,
     pub target: SetTarget,
-    pub mode: Mode,
+    /// None = the daemon's config.toml setting for each output, else Fill.
+    pub mode: Option<Mode>,
+    /// None = config.toml, else black.
     pub bg_colour: Option<Rgb>,
-    pub transition: Transition,
+    /// None = config.toml, else no transition.
+    pub transition: Option<Transition>,
+    /// Treat the selected outputs as one canvas (xdg-output logical layout).
+    #[serde(default)]
+    pub span: bool,
+    /// Also remember this as the wallpaper for outputs with nothing saved
+    /// (e.g. a monitor plugged in later).
+    #[serde(default)]
+    pub default: bool,
+    /// Where Fill and Center crop around; None = the centre.
+    #[serde(default)]
+    pub focus: Option<Focus>,
+    /// Fill magnification; None = 1.0. Below 1.0 the background shows.
+    #[serde(default)]
+    pub zoom: Option<f32>,
+    /// Fill crop shift, clamped to the overhang; None = no shift.
+    #[serde(default)]
+    pub offset: Option<Offset>,
+    /// Animate a still with a slow pan/zoom; ignored for animated images.
+    #[serde(default)]
+    pub motion: Option<Motion>,
+    /// Show the stored pixel values instead of converting an embedded ICC
+    /// profile to sRGB.
+    #[serde(default)]
+    pub ignore_icc: bool,
+    /// Override the animation's own loop count; None = what the file says.
+    #[serde(default)]
+    pub loops: Option<Loop>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PlaylistOrder {
+    Sequential,
+    /// Random pick every time; repeats are possible.
+    Shuffle,
+    /// Random permutation; every image once before any repeats.
+    ShuffleNoRepeat,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaylistRequest {
+    pub outputs: OutputSel,
+    /// Image files and/or directories (absolute, or names under GESSO_DIRS).
+    pub sources: Vec<String>,
+    pub interval_secs: u32,
+    pub order: PlaylistOrder,
+    /// None falls back to config.toml, as for `SetRequest`.
+    pub mode: Option<Mode>,
+    pub bg_colour: Option<Rgb>,
+    pub transition: Option<Transition>,
+    #[serde(default)]
+    pub span: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -76,9 +391,26 @@ pub enum Request {
     Info,
     Set(SetRequest),
     Unset { outputs: OutputSel },
+    /// Forget the wallpaper used for outputs with nothing saved.
+    ClearDefault,
+    /// Start (or replace) the slideshow.
+    Playlist(PlaylistRequest),
+    /// Skip to the next playlist image.
+    Next,
+    /// Go back to the previous playlist image.
+    Prev,
+    /// Pause the playlist timer, or resume it if already paused.
+    Pause,
     Stop,
     Doctor,
     Restore,
+    /// Keep the connection open and stream `Event` frames after the `Ok` reply.
+    Subscribe,
+    /// Re-read config.toml (same as sending the daemon SIGHUP).
+    Reload,
+    /// The session went idle (or came back): animated wallpapers hold their
+    /// current frame while idle. Meant for swayidle-style hooks.
+    Idle { idle: bool },
 }
 
 // ---- shared types ----
@@ -89,13 +421,28 @@ pub struct OutputInfo {
     pub width: u32,
     pub height: u32,
     pub scale: u32,
+    /// Exact scale (e.g. 1.25) when the compositor supports fractional scaling.
+    #[serde(default = "default_fractional_scale")]
+    pub fractional_scale: f32,
+    /// wl_output transform: "normal", "90", "180", "270", "flipped",
+    /// "flipped-90", "flipped-180" or "flipped-270". Width and height are
+    /// already swapped for quarter turns.
+    #[serde(default = "default_transform")]
+    pub transform: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// A daemon that predates `fractional_scale` only knew integer scales.
+fn default_fractional_scale() -> f32 { 1.0 }
+
+/// A daemon that predates `transform` didn't track rotation.
+fn default_transform() -> String { "normal".to_owned() }
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum CurrentTarget {
     Unset,
     Colour(Rgb),
     ImagePath(String),
+    Gradient(Gradient),
 }
 
 // ---- Info response ----
@@ -114,6 +461,22 @@ pub struct OutputFullInfo {
     pub bg_colour: Option<Rgb>,
     /// Last transition applied (None = instant / never set).
     pub transition: Transition,
+    /// True when this output shows a slice of a spanned wallpaper.
+    pub span: bool,
+    /// Crop focus — only meaningful when current is an image with fill/center.
+    #[serde(default)]
+    pub focus: Option<Focus>,
+    /// Zoom and crop shift — only meaningful when current is an image with fill.
+    #[serde(default)]
+    pub zoom: Option<f32>,
+    #[serde(default)]
+    pub offset: Option<Offset>,
+    /// Pan/zoom animation — only meaningful when current is a still image.
+    #[serde(default)]
+    pub motion: Option<Motion>,
+    /// Loop override — only meaningful when current is an animated image.
+    #[serde(default)]
+    pub loops: Option<Loop>,
 }
 
 // ---- Doctor response ----
@@ -126,11 +489,48 @@ pub struct DoctorReport {
     pub has_shm: bool,
     pub has_layer_shell: bool,
     pub has_xdg_output_manager: bool,
+    pub has_single_pixel_buffer: bool,
+    pub has_viewporter: bool,
+    /// Solid colours are shown as a scaled 1×1 buffer rather than full-size SHM.
+    pub single_pixel_colours: bool,
     /// Raw wl_shm pixel-format codes advertised by the compositor.
     pub shm_formats: Vec<u32>,
     pub warnings: Vec<String>,
 }
 
+// ---- Events (Subscribe) ----
+
+/// Pushed to subscribers as things happen. One framed message per event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Event {
+    /// Sent for every applied Set, even one that repeats the current wallpaper.
+    WallpaperChanged {
+        output: String,
+        target: CurrentTarget,
+        /// Scaling mode — only meaningful when target is an image.
+        mode: Option<Mode>,
+    },
+    /// A transition replaced mid-way gets its `TransitionFinished` first.
+    TransitionStarted {
+        output: String,
+        transition: Transition,
+    },
+    TransitionFinished {
+        output: String,
+    },
+    OutputAdded(OutputInfo),
+    OutputRemoved {
+        name: String,
+    },
+    OutputResized(OutputInfo),
+    /// A finite-loop GIF/WebP played its last frame.
+    AnimationFinished {
+        output: String,
+    },
+    /// The daemon is shutting down; the stream ends after this.
+    Stopping,
+}
+
 // ---- Response ----
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
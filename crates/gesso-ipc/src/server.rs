@@ -4,9 +4,46 @@
 use std::fs;
 use std::os::unix::net::{UnixListener, UnixStream};
 use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use crate::frame;
-use crate::protocol::{Request, Response};
+use crate::protocol::{Event, Request, Response};
+
+/// A subscriber that can't take a frame within this long is dropped, so a
+/// stuck client never stalls the render loop.
+const SUBSCRIBER_WRITE_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// Connections that sent `Request::Subscribe`. Cheap to clone; the IPC thread
+/// adds subscribers, the daemon broadcasts to them.
+#[derive(Clone, Default)]
+pub struct EventHub {
+    subs: Arc<Mutex<Vec<UnixStream>>>,
+}
+
+impl EventHub {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn add(&self, stream: UnixStream) {
+        let _ = stream.set_write_timeout(Some(SUBSCRIBER_WRITE_TIMEOUT));
+        if let Ok(mut subs) = self.subs.lock() {
+            subs.push(stream);
+        }
+    }
+
+    /// True when nobody is listening (callers can skip building events).
+    pub fn is_empty(&self) -> bool {
+        self.subs.lock().map(|s| s.is_empty()).unwrap_or(true)
+    }
+
+    /// Send `ev` to every subscriber, dropping any that have gone away.
+    pub fn broadcast(&self, ev: &Event) {
+        let Ok(mut subs) = self.subs.lock() else { return };
+        subs.retain_mut(|s| frame::send(s, ev).is_ok());
+    }
+}
 
 pub fn bind(sock: impl AsRef<Path>) -> std::io::Result<UnixListener> {
     let sock = sock.as_ref();
@@ -51,21 +88,31 @@ fn create_runtime_dir(dir: &Path) -> std::io::Result<()> {
 /// Blocking loop — run in dedicated thread.
 pub fn run_server(
     listener: UnixListener,
+    hub: EventHub,
     handler: impl Fn(Request) -> Response,
 ) -> frame::Result<()> {
     loop {
-        let (mut stream, _) = listener.accept()?;
-        handle_one(&mut stream, &handler)?;
+        let (stream, _) = listener.accept()?;
+        handle_one(stream, &hub, &handler)?;
     }
 }
 
 fn handle_one(
-    stream: &mut UnixStream,
+    mut stream: UnixStream,
+    hub: &EventHub,
     handler: &impl Fn(Request) -> Response,
 ) -> frame::Result<()> {
-    let req: Request = frame::recv(stream)?;
+    let req: Request = frame::recv(&mut stream)?;
+
+    // Subscriptions never reach the daemon loop: ack and park the stream.
+    if matches!(req, Request::Subscribe) {
+        frame::send(&mut stream, &Response::Ok)?;
+        hub.add(stream);
+        return Ok(());
+    }
+
     let resp = handler(req);
-    frame::send(stream, &resp)?;
+    frame::send(&mut stream, &resp)?;
     let _ = stream.shutdown(std::net::Shutdown::Both);
     Ok(())
 }
@@ -213,6 +213,22 @@ impl WlBackend {
         }
     }
 
+    /// Present a solid colour without an SHM buffer (see `HealthReport::single_pixel_colours`).
+    /// Same return convention as `present_rendered`.
+    pub fn present_colour(&mut self, output: &str, r: u8, g: u8, b: u8) -> WlResult<bool> {
+        let qh = self.queue.handle();
+        let out = self
+            .outputs
+            .by_name(output)
+            .ok_or_else(|| WlError::UnknownOutput(output.to_string()))?;
+        self.presenter.present_colour(&mut self.state, &qh, out, (r, g, b))
+    }
+
+    /// True if `present_colour` is available on this compositor.
+    pub fn supports_single_pixel_colours(&self) -> bool {
+        self.state.single_pixel.is_some() && self.state.viewporter.is_some()
+    }
+
     pub fn unset(&mut self, output: &str) -> WlResult<()> {
         let out = self
             .outputs
@@ -10,6 +10,8 @@ pub struct HealthReport {
     pub has_shm: bool,
     pub has_layer_shell: bool,
     pub has_xdg_output_manager: bool,
+    pub has_single_pixel_buffer: bool,
+    pub has_viewporter: bool,
     pub shm_formats: Vec<u32>,
 }
 
@@ -19,6 +21,8 @@ impl HealthReport {
         let has_shm = state.shm.is_some();
         let has_layer_shell = state.layer_shell.is_some();
         let has_xdg_output_manager = state.xdg_output_manager.is_some();
+        let has_single_pixel_buffer = state.single_pixel.is_some();
+        let has_viewporter = state.viewporter.is_some();
         let ok = has_compositor && has_shm && has_layer_shell;
         Self {
             ok,
@@ -26,7 +30,15 @@ impl HealthReport {
             has_shm,
             has_layer_shell,
             has_xdg_output_manager,
+            has_single_pixel_buffer,
+            has_viewporter,
             shm_formats: state.shm_formats.clone(),
         }
     }
+
+    /// Solid colours are presented as scaled 1×1 buffers instead of SHM.
+    #[inline]
+    pub fn single_pixel_colours(&self) -> bool {
+        self.has_single_pixel_buffer && self.has_viewporter
+    }
 }
@@ -7,6 +7,7 @@ use crate::state::WlState;
 use wayland_client::QueueHandle;
 use wayland_client::protocol::{wl_output, wl_region, wl_surface};
 
+use wayland_protocols::wp::fractional_scale::v1::client::wp_fractional_scale_v1;
 use wayland_protocols_wlr::layer_shell::v1::client::{
     zwlr_layer_shell_v1,
     zwlr_layer_surface_v1::{self, Anchor, KeyboardInteractivity},
@@ -16,6 +17,9 @@ pub struct LayerSurface {
     pub surface: wl_surface::WlSurface,
     #[allow(dead_code)]
     pub layer: zwlr_layer_surface_v1::ZwlrLayerSurfaceV1,
+    // Kept alive so preferred-scale events keep arriving.
+    #[allow(dead_code)]
+    pub fractional: Option<wp_fractional_scale_v1::WpFractionalScaleV1>,
 
     // Not currently used (we track configure in WlState), but kept for future.
     #[allow(dead_code)]
@@ -49,6 +53,17 @@ pub fn create_wallpaper_layer_surface(
 
     surface.set_buffer_scale(scale as i32);
 
+    // Buffer transform stays `Normal`: outputs report rotated sizes (see
+    // OutputInfo::width) and we render upright, leaving rotation of the
+    // final image to the compositor.
+
+    // With fractional-scale the compositor tells us the exact scale for this
+    // surface; the presenter then renders at physical size through a viewport.
+    let fractional = state
+        .fractional_scale_manager
+        .as_ref()
+        .map(|m| m.get_fractional_scale(&surface, qh, out_global));
+
     // Click-through (pointer): empty input region.
     let region: wl_region::WlRegion = compositor.create_region(qh, ());
     surface.set_input_region(Some(&region));
@@ -81,6 +96,7 @@ pub fn create_wallpaper_layer_surface(
     Ok(LayerSurface {
         surface,
         layer,
+        fractional,
         configured: false,
         last_configure_serial: 0,
     })
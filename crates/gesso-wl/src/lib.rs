@@ -14,4 +14,4 @@ mod health;
 pub use error::{WlError, WlResult};
 pub use backend::{WlBackend, PresentSpec};
 pub use health::HealthReport;
-pub use outputs::OutputInfo;
+pub use outputs::{OutputInfo, OutputTransform};
@@ -1,15 +1,79 @@
 // Author: Dustin Pilgrim
 // License: MIT
 
-use crate::state::WlState;
+use crate::state::{OutputRaw, WlState};
+
+use wayland_client::protocol::wl_output;
+
+/// How the compositor rotates/flips an output (wl_output.transform).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputTransform {
+    #[default]
+    Normal,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    Flipped,
+    Flipped90,
+    Flipped180,
+    Flipped270,
+}
+
+impl OutputTransform {
+    pub(crate) fn from_wl(t: wl_output::Transform) -> Self {
+        match t {
+            wl_output::Transform::_90         => Self::Rotate90,
+            wl_output::Transform::_180        => Self::Rotate180,
+            wl_output::Transform::_270        => Self::Rotate270,
+            wl_output::Transform::Flipped     => Self::Flipped,
+            wl_output::Transform::Flipped90   => Self::Flipped90,
+            wl_output::Transform::Flipped180  => Self::Flipped180,
+            wl_output::Transform::Flipped270  => Self::Flipped270,
+            _                                 => Self::Normal,
+        }
+    }
+
+    /// True for quarter turns, where width and height trade places.
+    #[inline]
+    pub fn swaps_axes(self) -> bool {
+        matches!(self, Self::Rotate90 | Self::Rotate270 | Self::Flipped90 | Self::Flipped270)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Normal     => "normal",
+            Self::Rotate90   => "90",
+            Self::Rotate180  => "180",
+            Self::Rotate270  => "270",
+            Self::Flipped    => "flipped",
+            Self::Flipped90  => "flipped-90",
+            Self::Flipped180 => "flipped-180",
+            Self::Flipped270 => "flipped-270",
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct OutputInfo {
     pub name: String,
+    /// Buffer size in physical pixels, already rotated for `transform`
+    /// (a portrait-mounted 2560×1440 panel reports 1440×2560).
     pub width: u32,
     pub height: u32,
     pub scale: u32,
+    /// Exact scale, e.g. 1.25. From wp_fractional_scale_v1 once our surface
+    /// exists; estimated from the physical/logical size before that.
+    pub fractional_scale: f32,
     pub wl_global: u32,
+
+    /// Logical position in the compositor's global space.
+    pub x: i32,
+    pub y: i32,
+    /// Logical size (physical size / scale when xdg-output isn't available).
+    pub logical_width: u32,
+    pub logical_height: u32,
+
+    pub transform: OutputTransform,
 }
 
 pub struct Outputs {
@@ -18,26 +82,60 @@ pub struct Outputs {
 
 impl Outputs {
     pub fn from_state(state: &WlState) -> Self {
-        let mut v: Vec<(u32, (u32, u32, u32, Option<String>))> = state
+        let mut v: Vec<(u32, OutputRaw)> = state
             .outputs
             .iter()
-            .map(|(global, (_obj, raw))| {
-                (*global, (raw.width, raw.height, raw.scale, raw.name.clone()))
-            })
+            .map(|(global, (_obj, raw))| (*global, raw.clone()))
             .collect();
 
         v.sort_by_key(|(global, _)| *global);
 
         let list = v
             .into_iter()
-            .filter_map(|(global, (w, h, s, name_opt))| {
-                let name = name_opt?; // skip unnamed outputs (no OUT-* fallback)
+            .filter_map(|(global, raw)| {
+                let name = raw.name?; // skip unnamed outputs (no OUT-* fallback)
+
+                // Mode size is the panel's native orientation; the surface is not.
+                let (mode_w, mode_h) = if raw.transform.swaps_axes() {
+                    (raw.height, raw.width)
+                } else {
+                    (raw.width, raw.height)
+                };
+
+                let (logical_width, logical_height) = if raw.logical_width > 0 && raw.logical_height > 0 {
+                    (raw.logical_width, raw.logical_height)
+                } else {
+                    ((mode_w / raw.scale).max(1), (mode_h / raw.scale).max(1))
+                };
+
+                // Physical size: the surface size the compositor configured at
+                // its preferred scale, falling back to the current mode.
+                let preferred = state.preferred_scale.get(&global).copied();
+                let (width, height) = match (preferred, state.layer_size.get(&global)) {
+                    (Some(s), Some(&(lw, lh))) if lw > 0 && lh > 0 => (
+                        ((lw as u64 * s as u64 + 60) / 120).max(1) as u32,
+                        ((lh as u64 * s as u64 + 60) / 120).max(1) as u32,
+                    ),
+                    _ => (mode_w, mode_h),
+                };
+                let fractional_scale = match preferred {
+                    Some(s) => s as f32 / 120.0,
+                    None if raw.logical_width > 0 => mode_w as f32 / raw.logical_width as f32,
+                    None => raw.scale as f32,
+                };
+
                 Some(OutputInfo {
                     name,
-                    width: w,
-                    height: h,
-                    scale: s,
+                    width,
+                    height,
+                    scale: raw.scale,
+                    fractional_scale,
                     wl_global: global,
+                    x: raw.x,
+                    y: raw.y,
+                    logical_width,
+                    logical_height,
+                    transform: raw.transform,
                 })
             })
             .collect();
@@ -10,7 +10,18 @@ use crate::state::WlState;
 use eventline::{debug, scope};
 use std::collections::HashMap;
 use wayland_client::QueueHandle;
-use wayland_client::protocol::wl_output;
+use wayland_client::protocol::{wl_buffer, wl_output};
+use wayland_protocols::wp::viewporter::client::wp_viewport;
+
+/// How buffer pixels map onto the layer surface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SurfaceGeom {
+    /// Classic integer `wl_surface.set_buffer_scale`.
+    BufferScale(i32),
+    /// Buffer scale 1, stretched to this surface-local size by a viewport.
+    /// Used for single-pixel colours and for fractional scales.
+    Viewport(i32, i32),
+}
 
 struct PerOutput {
     #[allow(dead_code)]
@@ -25,6 +36,59 @@ struct PerOutput {
     // its client-side mmap at idle to reclaim RSS while keeping wl_buffer alive.
     a: Option<ShmBuffer>, // which=0
     b: Option<ShmBuffer>, // which=1
+
+    // Solid-colour path: a 1×1 single-pixel buffer stretched over the surface
+    // by a viewport. `solid` is Some exactly while it is the attached buffer.
+    viewport: Option<wp_viewport::WpViewport>,
+    solid:    Option<wl_buffer::WlBuffer>,
+
+    // Last geometry sent, so we only send requests when it changes.
+    geom: SurfaceGeom,
+}
+
+impl PerOutput {
+    /// Bring the surface's scale/viewport in line with `want`. Double-buffered
+    /// state: takes effect with the next commit.
+    fn apply_geom(
+        &mut self,
+        state: &WlState,
+        qh:    &QueueHandle<WlState>,
+        want:  SurfaceGeom,
+    ) -> WlResult<()> {
+        if self.geom == want {
+            return Ok(());
+        }
+        match want {
+            SurfaceGeom::BufferScale(s) => {
+                self.layer.surface.set_buffer_scale(s);
+                if let Some(vp) = self.viewport.as_ref() {
+                    vp.set_destination(-1, -1);
+                }
+            }
+            SurfaceGeom::Viewport(w, h) => {
+                let viewporter = state
+                    .viewporter
+                    .as_ref()
+                    .ok_or(WlError::MissingGlobal("wp_viewporter"))?;
+                let surface = &self.layer.surface;
+                let vp = self
+                    .viewport
+                    .get_or_insert_with(|| viewporter.get_viewport(surface, qh, ()));
+                surface.set_buffer_scale(1);
+                vp.set_destination(w, h);
+            }
+        }
+        self.geom = want;
+        Ok(())
+    }
+}
+
+/// Surface-local size from the last configure, else the output's logical size.
+fn surface_size(state: &WlState, out: &OutputInfo) -> (i32, i32) {
+    match state.layer_size.get(&out.wl_global) {
+        Some(&(w, h)) if w > 0 && h > 0 => (w as i32, h as i32),
+        _ => (out.logical_width.max(1) as i32, out.logical_height.max(1) as i32),
+    }
 }
 
 pub struct Presenter {
@@ -61,6 +125,9 @@ impl Presenter {
                 layer,
                 a: None,
                 b: None,
+                viewport: None,
+                solid: None,
+                geom: SurfaceGeom::BufferScale(out.scale.max(1) as i32),
             });
         }
 
@@ -188,6 +255,16 @@ impl Presenter {
                 return Ok(false);
             }
 
+            // With fractional-scale we render at exact physical size and let
+            // a viewport map it 1:1; otherwise the integer buffer scale.
+            let want = if state.fractional_scale_manager.is_some() && state.viewporter.is_some() {
+                let (w, h) = surface_size(state, out);
+                SurfaceGeom::Viewport(w, h)
+            } else {
+                SurfaceGeom::BufferScale(out.scale.max(1) as i32)
+            };
+            po.apply_geom(state, qh, want)?;
+
             // Ensure we have at least buffer A.
             Self::ensure_a(po, state, qh, out, width, height)?;
 
@@ -230,10 +307,16 @@ impl Presenter {
             state.frame_callbacks.insert(out.wl_global, cb);
             state.mark_frame_pending(out.wl_global);
 
+            let solid = po.solid.take();
+
             po.layer.surface.attach(Some(&buf.wl_buffer), 0, 0);
             po.layer.surface.damage_buffer(0, 0, width as i32, height as i32);
             po.layer.surface.commit();
 
+            if let Some(old) = solid {
+                old.destroy();
+            }
+
             match which {
                 0 => { if let Some(b) = po.a.as_mut() { b.busy = true; } }
                 1 => { if let Some(b) = po.b.as_mut() { b.busy = true; } }
@@ -244,6 +327,64 @@ impl Presenter {
         })
     }
 
+    /// Present a solid colour as a single-pixel buffer scaled to the surface.
+    ///
+    /// No SHM is touched, so once the compositor releases the old buffers
+    /// `release_buffers` can drop them and the output holds no pixel memory.
+    /// Requires `wp_single_pixel_buffer_manager_v1` and `wp_viewporter`.
+    pub fn present_colour(
+        &mut self,
+        state: &mut WlState,
+        qh:    &QueueHandle<WlState>,
+        out:   &OutputInfo,
+        rgb:   (u8, u8, u8),
+    ) -> WlResult<bool> {
+        scope!("gesso-wl.present_colour", {
+            let spb = state
+                .single_pixel
+                .clone()
+                .ok_or(WlError::MissingGlobal("wp_single_pixel_buffer_manager_v1"))?;
+            if state.viewporter.is_none() {
+                return Err(WlError::MissingGlobal("wp_viewporter"));
+            }
+
+            let po = self.ensure_layer(state, qh, out, out.width, out.height)?;
+
+            if !state.layer_configure_serial.contains_key(&out.wl_global) {
+                return Ok(false);
+            }
+            if !state.is_frame_ready(out.wl_global) {
+                return Ok(false);
+            }
+
+            // Channels are full-range u32; 0xff → 0xffff_ffff.
+            let wide = |c: u8| c as u32 * 0x0101_0101;
+            let buffer = spb.create_u32_rgba_buffer(wide(rgb.0), wide(rgb.1), wide(rgb.2), u32::MAX, qh, ());
+
+            debug!("commit (single-pixel)");
+
+            // A 1×1 buffer isn't a multiple of an integer buffer scale, so
+            // this always goes through the viewport.
+            let (w, h) = surface_size(state, out);
+            po.apply_geom(state, qh, SurfaceGeom::Viewport(w, h))?;
+
+            let surface = &po.layer.surface;
+            let cb = surface.frame(qh, out.wl_global);
+            state.frame_callbacks.insert(out.wl_global, cb);
+            state.mark_frame_pending(out.wl_global);
+
+            surface.attach(Some(&buffer), 0, 0);
+            surface.damage_buffer(0, 0, 1, 1);
+            surface.commit();
+
+            if let Some(old) = po.solid.replace(buffer) {
+                old.destroy();
+            }
+
+            Ok(true)
+        })
+    }
+
     /// Compatibility shim: copies a slice into SHM. Still compositor-paced.
     pub fn present_xrgb8888(
         &mut self,
@@ -2,6 +2,7 @@
 // License: MIT
 
 use crate::error::{WlError, WlResult};
+use crate::outputs::OutputTransform;
 
 use eventline::{debug, warn};
 use std::collections::HashMap;
@@ -30,6 +31,13 @@ use wayland_protocols::xdg::xdg_output::zv1::client::{
     zxdg_output_v1,
 };
 
+use wayland_protocols::wp::fractional_scale::v1::client::{
+    wp_fractional_scale_manager_v1,
+    wp_fractional_scale_v1,
+};
+use wayland_protocols::wp::single_pixel_buffer::v1::client::wp_single_pixel_buffer_manager_v1;
+use wayland_protocols::wp::viewporter::client::{wp_viewport, wp_viewporter};
+
 #[derive(Debug, Clone)]
 pub struct OutputRaw {
     pub global_name: u32,
@@ -39,6 +47,19 @@ pub struct OutputRaw {
 
     // NEW: compositor-provided name (e.g. "DP-1")
     pub name: Option<String>,
+
+    // Position in the compositor's global space. Comes from wl_output.geometry
+    // and is overridden by xdg-output's logical_position when available.
+    pub x: i32,
+    pub y: i32,
+
+    // xdg-output logical size (0 until the compositor sends it).
+    pub logical_width: u32,
+    pub logical_height: u32,
+
+    // wl_output.geometry transform. Mode sizes are in the panel's native
+    // orientation; the surface we get is rotated by this.
+    pub transform: OutputTransform,
 }
 
 pub struct WlState {
@@ -52,9 +73,22 @@ pub struct WlState {
     // NEW: keep xdg_output proxies alive (per output global)
     pub xdg_outputs: HashMap<u32, zxdg_output_v1::ZxdgOutputV1>,
 
+    // Optional: together these let a solid colour be a 1×1 buffer scaled
+    // up by the compositor instead of a full-size SHM buffer.
+    pub single_pixel: Option<wp_single_pixel_buffer_manager_v1::WpSinglePixelBufferManagerV1>,
+    pub viewporter: Option<wp_viewporter::WpViewporter>,
+
+    // Optional: per-surface preferred scale, including fractional ones.
+    pub fractional_scale_manager: Option<wp_fractional_scale_manager_v1::WpFractionalScaleManagerV1>,
+    // output wl_global -> preferred scale in 120ths (wp_fractional_scale_v1)
+    pub preferred_scale: HashMap<u32, u32>,
+
     // output wl_global -> last configure serial
     pub layer_configure_serial: HashMap<u32, u32>,
 
+    // output wl_global -> layer surface size (surface-local) from configure
+    pub layer_size: HashMap<u32, (u32, u32)>,
+
     pub shm_formats: Vec<u32>,
 
     // key: registry global name for wl_output
@@ -86,7 +120,14 @@ impl WlState {
             xdg_output_manager: None,
             xdg_outputs: HashMap::new(),
 
+            single_pixel: None,
+            viewporter: None,
+
+            fractional_scale_manager: None,
+            preferred_scale: HashMap::new(),
+
             layer_configure_serial: HashMap::new(),
+            layer_size: HashMap::new(),
             shm_formats: Vec::new(),
             outputs: HashMap::new(),
             buffer_released: Vec::new(),
@@ -139,6 +180,8 @@ impl WlState {
     fn drop_output_state(&mut self, out_global: u32) {
         self.outputs.remove(&out_global);
         self.layer_configure_serial.remove(&out_global);
+        self.layer_size.remove(&out_global);
+        self.preferred_scale.remove(&out_global);
         self.frame_ready.remove(&out_global);
         self.frame_callbacks.remove(&out_global);
 
@@ -212,6 +255,11 @@ impl Dispatch<wl_registry::WlRegistry, ()> for WlState {
                                     height: 1,
                                     scale: 1,
                                     name: None,
+                                    x: 0,
+                                    y: 0,
+                                    logical_width: 0,
+                                    logical_height: 0,
+                                    transform: OutputTransform::Normal,
                                 },
                             ),
                         );
@@ -232,6 +280,16 @@ impl Dispatch<wl_registry::WlRegistry, ()> for WlState {
                         // Outputs may already exist; create xdg objects now.
                         state.ensure_xdg_for_all_outputs(qh);
                     }
+                    "wp_single_pixel_buffer_manager_v1" => {
+                        state.single_pixel = Some(registry.bind(name, version.min(1), qh, ()));
+                    }
+                    "wp_viewporter" => {
+                        state.viewporter = Some(registry.bind(name, version.min(1), qh, ()));
+                    }
+                    "wp_fractional_scale_manager_v1" => {
+                        state.fractional_scale_manager =
+                            Some(registry.bind(name, version.min(1), qh, ()));
+                    }
                     _ => {}
                 }
             }
@@ -274,8 +332,19 @@ impl Dispatch<wl_output::WlOutput, u32> for WlState {
         _conn: &Connection,
         _qh: &QueueHandle<Self>,
     ) {
+        let has_xdg = state.xdg_outputs.contains_key(global_name);
         if let Some((_o, raw)) = state.outputs.get_mut(global_name) {
             match event {
+                wl_output::Event::Geometry { x, y, transform, .. } => {
+                    if let WEnum::Value(t) = transform {
+                        raw.transform = OutputTransform::from_wl(t);
+                    }
+                    // xdg-output's logical position wins when we have it.
+                    if !has_xdg {
+                        raw.x = x;
+                        raw.y = y;
+                    }
+                }
                 wl_output::Event::Mode { width, height, .. } => {
                     raw.width = (width as u32).max(1);
                     raw.height = (height as u32).max(1);
@@ -319,8 +388,14 @@ impl Dispatch<zxdg_output_v1::ZxdgOutputV1, u32> for WlState {
                     raw.name = Some(name);
                 }
                 zxdg_output_v1::Event::Description { .. } => {}
-                zxdg_output_v1::Event::LogicalSize { .. } => {}
-                zxdg_output_v1::Event::LogicalPosition { .. } => {}
+                zxdg_output_v1::Event::LogicalSize { width, height } => {
+                    raw.logical_width = width.max(0) as u32;
+                    raw.logical_height = height.max(0) as u32;
+                }
+                zxdg_output_v1::Event::LogicalPosition { x, y } => {
+                    raw.x = x;
+                    raw.y = y;
+                }
                 zxdg_output_v1::Event::Done => {}
                 _ => {}
             }
@@ -379,6 +454,79 @@ impl Dispatch<wl_buffer::WlBuffer, (u32, usize)> for WlState {
     }
 }
 
+// Single-pixel buffers carry no storage, so there is nothing to do on release.
+impl Dispatch<wl_buffer::WlBuffer, ()> for WlState {
+    fn event(
+        _: &mut Self,
+        _: &wl_buffer::WlBuffer,
+        _: wl_buffer::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {}
+}
+
+impl Dispatch<wp_single_pixel_buffer_manager_v1::WpSinglePixelBufferManagerV1, ()> for WlState {
+    fn event(
+        _: &mut Self,
+        _: &wp_single_pixel_buffer_manager_v1::WpSinglePixelBufferManagerV1,
+        _: wp_single_pixel_buffer_manager_v1::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {}
+}
+
+impl Dispatch<wp_viewporter::WpViewporter, ()> for WlState {
+    fn event(
+        _: &mut Self,
+        _: &wp_viewporter::WpViewporter,
+        _: wp_viewporter::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {}
+}
+
+impl Dispatch<wp_fractional_scale_manager_v1::WpFractionalScaleManagerV1, ()> for WlState {
+    fn event(
+        _: &mut Self,
+        _: &wp_fractional_scale_manager_v1::WpFractionalScaleManagerV1,
+        _: wp_fractional_scale_manager_v1::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {}
+}
+
+// Preferred scale for our wallpaper surface on this output (scale × 120).
+impl Dispatch<wp_fractional_scale_v1::WpFractionalScaleV1, u32> for WlState {
+    fn event(
+        state: &mut Self,
+        _proxy: &wp_fractional_scale_v1::WpFractionalScaleV1,
+        event: wp_fractional_scale_v1::Event,
+        out_global: &u32,
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+    ) {
+        if let wp_fractional_scale_v1::Event::PreferredScale { scale } = event {
+            debug!("preferred fractional scale changed");
+            state.preferred_scale.insert(*out_global, scale.max(1));
+        }
+    }
+}
+
+impl Dispatch<wp_viewport::WpViewport, ()> for WlState {
+    fn event(
+        _: &mut Self,
+        _: &wp_viewport::WpViewport,
+        _: wp_viewport::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {}
+}
+
 // Frame callback pacing (wl_surface.frame)
 // IMPORTANT: we keep callback proxies alive in state.frame_callbacks until Done.
 impl Dispatch<wl_callback::WlCallback, u32> for WlState {
@@ -420,9 +568,10 @@ impl Dispatch<zwlr_layer_surface_v1::ZwlrLayerSurfaceV1, u32> for WlState {
         _qh: &QueueHandle<Self>,
     ) {
         match event {
-            zwlr_layer_surface_v1::Event::Configure { serial, .. } => {
+            zwlr_layer_surface_v1::Event::Configure { serial, width, height } => {
                 proxy.ack_configure(serial);
                 state.layer_configure_serial.insert(*out_global, serial);
+                state.layer_size.insert(*out_global, (width, height));
 
                 // After configure, allow a frame immediately.
                 state.mark_frame_ready(*out_global);
@@ -430,6 +579,7 @@ impl Dispatch<zwlr_layer_surface_v1::ZwlrLayerSurfaceV1, u32> for WlState {
             zwlr_layer_surface_v1::Event::Closed => {
                 warn!("layer surface closed");
                 state.layer_configure_serial.remove(out_global);
+                state.layer_size.remove(out_global);
                 state.frame_ready.remove(out_global);
                 state.frame_callbacks.remove(out_global);
             }
@@ -10,41 +10,100 @@ use clap::{Parser, Subcommand, ValueEnum};
     version
 )]
 pub struct Cli {
+    /// Print responses, errors and events as JSON (one object per line)
+    #[arg(long, global = true)]
+    pub json: bool,
+
     #[command(subcommand)]
     pub cmd: Command,
 }
 
 #[derive(Subcommand, Debug)]
 pub enum Command {
-    /// Set an image wallpaper (path or name resolved via GESSO_DIRS)
+    /// Set an image wallpaper (path or name resolved via GESSO_DIRS / config dirs)
     ///
     /// Examples:
     ///   gesso set wall.jpg
     ///   gesso set wall.jpg -t drop
     ///   gesso set wall.jpg -t fade -d 350
     ///   gesso set wall.jpg -t wave -f right
+    ///   gesso set wall.jpg -t wave --angle 45
     ///   gesso set wall.jpg -t fade -s 24
+    ///   gesso set wide.jpg --span -t wave
+    ///   gesso set wall.jpg --zoom 1.25 --offset 0,-80
+    ///   gesso set wall.jpg --ken-burns --motion-fps 8
+    ///   gesso set intro.gif --loop once
     Set {
         target: String,
 
-        #[arg(long, short = 'm', value_enum, default_value_t = ModeArg::Fill)]
-        mode: ModeArg,
+        /// Scaling mode (default: config.toml, else fill)
+        #[arg(long, short = 'm', value_enum)]
+        mode: Option<ModeArg>,
 
         /// Background fill colour for fit/center (e.g. "#101010")
         #[arg(long, short = 'c')]
         colour: Option<String>,
 
-        /// Transition type (default: none)
+        /// Which part of the image fill/center keep when cropping (default: center)
+        #[arg(long, value_enum, conflicts_with = "focus")]
+        anchor: Option<AnchorArg>,
+
+        /// Point of the image fill/center keep in view, as fractions (e.g. 0.3,0.2)
+        #[arg(long, value_name = "X,Y")]
+        focus: Option<String>,
+
+        /// Magnify relative to fill (e.g. 1.25); below 1 shows the background.
+        /// Implies --mode fill.
+        #[arg(long)]
+        zoom: Option<f32>,
+
+        /// Shift the fill crop by DX,DY output pixels (e.g. -120,40), clamped so
+        /// no edge is uncovered. Implies --mode fill.
+        #[arg(long, value_name = "DX,DY", allow_hyphen_values = true)]
+        offset: Option<String>,
+
+        /// Slowly pan and zoom across the image ("Ken Burns"). Stills only.
+        /// Implies --mode fill.
+        #[arg(long)]
+        ken_burns: bool,
+
+        /// Seconds per pan/zoom pass (default: 120).
+        #[arg(long, value_name = "SECS", requires = "ken_burns")]
+        motion_period: Option<u32>,
+
+        /// Extra zoom reached at the end of a pass (default: 0.15).
+        #[arg(long, value_name = "FACTOR", requires = "ken_burns")]
+        motion_zoom: Option<f32>,
+
+        /// Pan/zoom frame rate, 1-50 (default: 10).
+        #[arg(long, value_name = "FPS", requires = "ken_burns")]
+        motion_fps: Option<u8>,
+
+        /// Show the pixels as stored, without converting an embedded ICC
+        /// colour profile (Display P3, Adobe RGB, ...) to sRGB.
+        #[arg(long)]
+        no_icc: bool,
+
+        /// How often an animation plays before stopping on its last frame:
+        /// a count, "once" or "forever" (default: the file's own loop count).
+        #[arg(long = "loop", value_name = "N|forever|once")]
+        loops: Option<String>,
+
+        /// Transition type (default: config.toml, else none)
         ///
         /// none: instant
-        /// drop: hard circle expands from center (default ~750ms, mode-adjusted)
+        /// drop: circle expands from center or --at (default ~750ms, mode-adjusted)
         /// fade: crossfade (default ~360ms, mode-adjusted)
-        /// wave: directional wipe (default ~920ms, mode-adjusted, see --from)
-        #[arg(long, short = 't', value_enum, default_value_t = TransitionArg::None)]
-        transition: TransitionArg,
+        /// wave: directional wipe (default ~920ms, mode-adjusted, see --from / --angle)
+        /// slide: new wallpaper pushes the old one off (see --direction)
+        /// cover: new wallpaper slides in over the old one (see --direction)
+        /// pixelate: blocks grow over the old one, then shrink on the new (see --block)
+        /// dissolve: pixels flip in a noise pattern (see --grain, --noise)
+        #[arg(long, short = 't', value_enum)]
+        transition: Option<TransitionArg>,
 
         /// Override transition duration in ms.
-        #[arg(long, short = 'd')]
+        #[arg(long, short = 'd', value_parser = clap::value_parser!(u32).range(1..))]
         duration: Option<u32>,
 
         /// Quantize the transition into N discrete steps (e.g. 24, 60).
@@ -56,9 +115,59 @@ pub enum Command {
         #[arg(long = "from", short = 'f', value_enum, default_value_t = WaveFromArg::Left)]
         from: WaveFromArg,
 
+        /// Wave direction of travel in degrees, overriding --from
+        /// (0 = bottom→top, 90 = left→right, 135 = top-left→bottom-right).
+        #[arg(long = "angle", value_name = "DEG", allow_negative_numbers = true, conflicts_with = "from", value_parser = crate::parse::parse_angle)]
+        wave_angle: Option<f32>,
+
+        /// Which way the new wallpaper moves (only used when --transition
+        /// slide or cover).
+        #[arg(long, value_enum, default_value_t = SlideDirArg::Left)]
+        direction: SlideDirArg,
+
+        /// Where a drop starts: X,Y in px or % (e.g. 100,50 or 20%,80%),
+        /// "random", or "centre" (the default).
+        #[arg(long, value_name = "X,Y|random")]
+        at: Option<String>,
+
+        /// Feathered edge for drop, in px (default: crisp).
+        #[arg(long, value_name = "PX")]
+        softness: Option<u16>,
+
+        /// Largest block size for pixelate, in px (default: 48).
+        #[arg(long, value_name = "PX")]
+        block: Option<u16>,
+
+        /// Dissolve cell size, in px (default: 2).
+        #[arg(long, value_name = "PX")]
+        grain: Option<u16>,
+
+        /// Dissolve pattern: blue is evenly spread, white clumps like static.
+        #[arg(long, value_enum, default_value_t = NoiseArg::Blue)]
+        noise: NoiseArg,
+
+        /// Fix the dissolve pattern (default: a new one each time).
+        #[arg(long)]
+        seed: Option<u32>,
+
+        /// Progress curve, e.g. ease-out-expo or cubic-bezier(0.2,0,0,1)
+        /// (default: each transition's own).
+        #[arg(long, value_name = "CURVE")]
+        easing: Option<String>,
+
         /// Target a specific output by wl_output.name (e.g. DP-1, HDMI-A-1).
         #[arg(long, short = 'o')]
         output: Option<String>,
+
+        /// Span one image across all selected outputs using their layout
+        /// positions; transitions sweep across the whole canvas.
+        #[arg(long)]
+        span: bool,
+
+        /// Also use this wallpaper for outputs with nothing saved
+        /// (e.g. a monitor plugged in later).
+        #[arg(long)]
+        default: bool,
     },
 
     /// Set a solid colour background
@@ -71,17 +180,21 @@ pub enum Command {
     Colour {
         colour: String,
 
-        /// Transition type (default: none)
+        /// Transition type (default: config.toml, else none)
         ///
         /// none: instant
-        /// drop: hard circle expands from center (default ~620ms)
+        /// drop: circle expands from center or --at (default ~620ms)
         /// fade: crossfade (default ~300ms)
-        /// wave: directional wipe (default ~820ms, see --from)
-        #[arg(long, short = 't', value_enum, default_value_t = TransitionArg::None)]
-        transition: TransitionArg,
+        /// wave: directional wipe (default ~820ms, see --from / --angle)
+        /// slide: new colour pushes the old one off (see --direction)
+        /// cover: new colour slides in over the old one (see --direction)
+        /// pixelate: blocky swap (see --block)
+        /// dissolve: pixels flip in a noise pattern (see --grain, --noise)
+        #[arg(long, short = 't', value_enum)]
+        transition: Option<TransitionArg>,
 
         /// Override transition duration in ms.
-        #[arg(long, short = 'd')]
+        #[arg(long, short = 'd', value_parser = clap::value_parser!(u32).range(1..))]
         duration: Option<u32>,
 
         /// Quantize the transition into N discrete steps (e.g. 24, 60).
@@ -93,19 +206,287 @@ pub enum Command {
         #[arg(long = "from", short = 'f', value_enum, default_value_t = WaveFromArg::Left)]
         from: WaveFromArg,
 
+        /// Wave direction of travel in degrees, overriding --from
+        /// (0 = bottom→top, 90 = left→right, 135 = top-left→bottom-right).
+        #[arg(long = "angle", value_name = "DEG", allow_negative_numbers = true, conflicts_with = "from", value_parser = crate::parse::parse_angle)]
+        wave_angle: Option<f32>,
+
+        /// Which way the new wallpaper moves (only used when --transition
+        /// slide or cover).
+        #[arg(long, value_enum, default_value_t = SlideDirArg::Left)]
+        direction: SlideDirArg,
+
+        /// Where a drop starts: X,Y in px or % (e.g. 100,50 or 20%,80%),
+        /// "random", or "centre" (the default).
+        #[arg(long, value_name = "X,Y|random")]
+        at: Option<String>,
+
+        /// Feathered edge for drop, in px (default: crisp).
+        #[arg(long, value_name = "PX")]
+        softness: Option<u16>,
+
+        /// Largest block size for pixelate, in px (default: 48).
+        #[arg(long, value_name = "PX")]
+        block: Option<u16>,
+
+        /// Dissolve cell size, in px (default: 2).
+        #[arg(long, value_name = "PX")]
+        grain: Option<u16>,
+
+        /// Dissolve pattern: blue is evenly spread, white clumps like static.
+        #[arg(long, value_enum, default_value_t = NoiseArg::Blue)]
+        noise: NoiseArg,
+
+        /// Fix the dissolve pattern (default: a new one each time).
+        #[arg(long)]
+        seed: Option<u32>,
+
+        /// Progress curve, e.g. ease-out-expo or cubic-bezier(0.2,0,0,1)
+        /// (default: each transition's own).
+        #[arg(long, value_name = "CURVE")]
+        easing: Option<String>,
+
+        #[arg(long, short = 'o')]
+        output: Option<String>,
+
+        /// Run the transition across all selected outputs as one canvas.
+        #[arg(long)]
+        span: bool,
+
+        /// Also use this colour for outputs with nothing saved.
+        #[arg(long)]
+        default: bool,
+    },
+
+    /// Set a linear or radial gradient background
+    ///
+    /// Stops are spread evenly unless given a position with `@`
+    /// (a percentage or a 0..1 fraction).
+    ///
+    /// Examples:
+    ///   gesso gradient "#1e1e2e" "#313244" --angle 135
+    ///   gesso gradient "#11111b" "#1e1e2e@70%" "#45475a" --dither
+    ///   gesso gradient "#313244" "#11111b" --radial --centre 0.5,0.3 --radius 0.8
+    ///   gesso gradient "#1e1e2e" "#313244" --span -t wave
+    Gradient {
+        /// Colour stops, e.g. "#1e1e2e" or "#1e1e2e@40%".
+        #[arg(required = true, num_args = 2.., value_name = "STOP")]
+        stops: Vec<String>,
+
+        /// Direction of a linear gradient in degrees
+        /// (0 = bottom→top, 90 = left→right, 135 = top-left→bottom-right).
+        #[arg(long, short = 'a', default_value_t = 180.0, allow_negative_numbers = true)]
+        angle: f32,
+
+        /// Radiate from a centre point instead of running along --angle.
+        #[arg(long)]
+        radial: bool,
+
+        /// Radial centre as x,y fractions of the output (default: 0.5,0.5).
+        #[arg(long, value_name = "X,Y", requires = "radial")]
+        centre: Option<String>,
+
+        /// Radial radius as a fraction of the distance to the farthest corner.
+        #[arg(long, default_value_t = 1.0, requires = "radial")]
+        radius: f32,
+
+        /// Ordered dither, so dark or subtle gradients don't band.
+        #[arg(long)]
+        dither: bool,
+
+        /// Transition type (default: config.toml, else none)
+        #[arg(long, short = 't', value_enum)]
+        transition: Option<TransitionArg>,
+
+        /// Override transition duration in ms.
+        #[arg(long, short = 'd', value_parser = clap::value_parser!(u32).range(1..))]
+        duration: Option<u32>,
+
+        /// Quantize the transition into N discrete steps.
+        #[arg(long = "transition-steps", short = 's')]
+        transition_steps: Option<u16>,
+
+        /// Wipe direction (only used when --transition wave).
+        #[arg(long = "from", short = 'f', value_enum, default_value_t = WaveFromArg::Left)]
+        from: WaveFromArg,
+
+        /// Wave direction of travel in degrees, overriding --from
+        /// (0 = bottom→top, 90 = left→right, 135 = top-left→bottom-right).
+        #[arg(long = "wave-angle", value_name = "DEG", allow_negative_numbers = true, conflicts_with = "from", value_parser = crate::parse::parse_angle)]
+        wave_angle: Option<f32>,
+
+        /// Which way the new wallpaper moves (only used when --transition
+        /// slide or cover).
+        #[arg(long, value_enum, default_value_t = SlideDirArg::Left)]
+        direction: SlideDirArg,
+
+        /// Where a drop starts: X,Y in px or % (e.g. 100,50 or 20%,80%),
+        /// "random", or "centre" (the default).
+        #[arg(long, value_name = "X,Y|random")]
+        at: Option<String>,
+
+        /// Feathered edge for drop, in px (default: crisp).
+        #[arg(long, value_name = "PX")]
+        softness: Option<u16>,
+
+        /// Largest block size for pixelate, in px (default: 48).
+        #[arg(long, value_name = "PX")]
+        block: Option<u16>,
+
+        /// Dissolve cell size, in px (default: 2).
+        #[arg(long, value_name = "PX")]
+        grain: Option<u16>,
+
+        /// Dissolve pattern: blue is evenly spread, white clumps like static.
+        #[arg(long, value_enum, default_value_t = NoiseArg::Blue)]
+        noise: NoiseArg,
+
+        /// Fix the dissolve pattern (default: a new one each time).
+        #[arg(long)]
+        seed: Option<u32>,
+
+        /// Progress curve, e.g. ease-out-expo or cubic-bezier(0.2,0,0,1)
+        /// (default: each transition's own).
+        #[arg(long, value_name = "CURVE")]
+        easing: Option<String>,
+
+        #[arg(long, short = 'o')]
+        output: Option<String>,
+
+        /// Stretch one gradient across all selected outputs.
+        #[arg(long)]
+        span: bool,
+
+        /// Also use this gradient for outputs with nothing saved.
+        #[arg(long)]
+        default: bool,
+    },
+
+    /// Cycle through images on a timer (files and/or directories)
+    ///
+    /// The daemon schedules the changes and remembers its place across restarts.
+    /// Setting or unsetting a wallpaper on a playlist output stops the playlist.
+    ///
+    /// Examples:
+    ///   gesso playlist ~/Pictures/walls
+    ///   gesso playlist ~/Pictures/walls -i 300 --order shuffle-no-repeat -t fade
+    ///   gesso playlist a.jpg b.png c.webp -i 60 -o DP-1
+    Playlist {
+        /// Image files and/or directories (directories are scanned, not recursed).
+        #[arg(required = true, value_name = "SOURCE")]
+        sources: Vec<String>,
+
+        /// Seconds between changes.
+        #[arg(long, short = 'i', default_value_t = 600)]
+        interval: u32,
+
+        #[arg(long, value_enum, default_value_t = OrderArg::Sequential)]
+        order: OrderArg,
+
+        /// Scaling mode (default: config.toml, else fill)
+        #[arg(long, short = 'm', value_enum)]
+        mode: Option<ModeArg>,
+
+        /// Background fill colour for fit/center (e.g. "#101010")
+        #[arg(long, short = 'c')]
+        colour: Option<String>,
+
+        /// Transition used for each change (default: config.toml, else none)
+        #[arg(long, short = 't', value_enum)]
+        transition: Option<TransitionArg>,
+
+        /// Override transition duration in ms.
+        #[arg(long, short = 'd', value_parser = clap::value_parser!(u32).range(1..))]
+        duration: Option<u32>,
+
+        /// Quantize the transition into N discrete steps.
+        #[arg(long = "transition-steps", short = 's')]
+        transition_steps: Option<u16>,
+
+        /// Wipe direction (only used when --transition wave).
+        #[arg(long = "from", short = 'f', value_enum, default_value_t = WaveFromArg::Left)]
+        from: WaveFromArg,
+
+        /// Wave direction of travel in degrees, overriding --from
+        /// (0 = bottom→top, 90 = left→right, 135 = top-left→bottom-right).
+        #[arg(long = "angle", value_name = "DEG", allow_negative_numbers = true, conflicts_with = "from", value_parser = crate::parse::parse_angle)]
+        wave_angle: Option<f32>,
+
+        /// Which way the new wallpaper moves (only used when --transition
+        /// slide or cover).
+        #[arg(long, value_enum, default_value_t = SlideDirArg::Left)]
+        direction: SlideDirArg,
+
+        /// Where a drop starts: X,Y in px or % (e.g. 100,50 or 20%,80%),
+        /// "random", or "centre" (the default).
+        #[arg(long, value_name = "X,Y|random")]
+        at: Option<String>,
+
+        /// Feathered edge for drop, in px (default: crisp).
+        #[arg(long, value_name = "PX")]
+        softness: Option<u16>,
+
+        /// Largest block size for pixelate, in px (default: 48).
+        #[arg(long, value_name = "PX")]
+        block: Option<u16>,
+
+        /// Dissolve cell size, in px (default: 2).
+        #[arg(long, value_name = "PX")]
+        grain: Option<u16>,
+
+        /// Dissolve pattern: blue is evenly spread, white clumps like static.
+        #[arg(long, value_enum, default_value_t = NoiseArg::Blue)]
+        noise: NoiseArg,
+
+        /// Fix the dissolve pattern (default: a new one each time).
+        #[arg(long)]
+        seed: Option<u32>,
+
+        /// Progress curve, e.g. ease-out-expo or cubic-bezier(0.2,0,0,1)
+        /// (default: each transition's own).
+        #[arg(long, value_name = "CURVE")]
+        easing: Option<String>,
+
         #[arg(long, short = 'o')]
         output: Option<String>,
+
+        /// Span each image across all selected outputs.
+        #[arg(long)]
+        span: bool,
+    },
+
+    /// Skip to the next playlist image.
+    Next,
+
+    /// Go back to the previous playlist image.
+    Prev,
+
+    /// Pause the playlist timer; run again to resume.
+    Pause,
+
+    /// Hold animated wallpapers (GIFs, Ken Burns) on their current frame.
+    ///
+    /// Meant for idle hooks, e.g.:
+    ///   swayidle timeout 300 'gesso idle on' resume 'gesso idle off'
+    Idle {
+        #[arg(value_enum)]
+        state: IdleArg,
     },
 
     /// Unset wallpaper on one output (by name) or all outputs (default).
     ///
     /// Examples:
-    ///   gesso unset          # all
-    ///   gesso unset DP-1     # just DP-1
+    ///   gesso unset            # all
+    ///   gesso unset DP-1       # just DP-1
+    ///   gesso unset --default  # forget the wallpaper for new outputs
     Unset {
         /// Output name (positional). If omitted, unsets all outputs.
-        #[arg(value_name = "OUTPUT")]
+        #[arg(value_name = "OUTPUT", conflicts_with = "default")]
         output: Option<String>,
+
+        /// Only forget the wallpaper used for outputs with nothing saved.
+        #[arg(long)]
+        default: bool,
     },
 
     /// List all detected outputs with resolution and scale.
@@ -120,13 +501,31 @@ pub enum Command {
     /// Show Wayland compositor health: globals, shm formats, and warnings.
     Doctor,
 
+    /// Re-read the daemon's config.toml (same as `pkill -HUP gessod`).
+    Reload,
+
+    /// Stream daemon events, one line each, until the daemon stops.
+    ///
+    /// Lines start with the event name followed by key=value pairs:
+    ///   wallpaper output=DP-1 target=image path=/home/me/wall.jpg mode=fill
+    ///   transition-start output=DP-1 transition=fade
+    ///   output-added name=HDMI-A-1 width=1920 height=1080 scale=1
+    Subscribe,
+
     Stop,
 }
 
+#[derive(Copy, Clone, Debug, ValueEnum)]
+pub enum IdleArg {
+    On,
+    Off,
+}
+
 #[derive(Copy, Clone, Debug, ValueEnum)]
 pub enum ModeArg {
     Fill,
     Fit,
+    FitBlur,
     Stretch,
     Center,
     Tile,
@@ -138,10 +537,50 @@ pub enum TransitionArg {
     Drop,
     Fade,
     Wave,
+    Slide,
+    Cover,
+    Pixelate,
+    Dissolve,
+}
+
+#[derive(Copy, Clone, Debug, ValueEnum)]
+pub enum NoiseArg {
+    White,
+    Blue,
 }
 
 #[derive(Copy, Clone, Debug, ValueEnum)]
 pub enum WaveFromArg {
     Left,
     Right,
+    Top,
+    Bottom,
+}
+
+#[derive(Copy, Clone, Debug, ValueEnum)]
+pub enum SlideDirArg {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+#[derive(Copy, Clone, Debug, ValueEnum)]
+pub enum AnchorArg {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+#[derive(Copy, Clone, Debug, ValueEnum)]
+pub enum OrderArg {
+    Sequential,
+    Shuffle,
+    ShuffleNoRepeat,
 }
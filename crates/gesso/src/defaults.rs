@@ -2,30 +2,12 @@
 // License: MIT
 
 use gesso_ipc::protocol as ipc;
-use crate::cli::{TransitionArg, WaveFromArg};
+use crate::cli::{NoiseArg, SlideDirArg, TransitionArg, WaveFromArg};
 
 // ---- sane defaults ----
-const IMG_DROP_MS: u32 = 2300;
-const IMG_FADE_MS: u32 = 950;
-const IMG_WAVE_MS: u32 = 1750;
-const COL_DROP_MS: u32 = 1700;
-const COL_FADE_MS: u32 = 1100;
-const COL_WAVE_MS: u32 = 1700;
-
-fn mode_nudge_ms(mode: ipc::Mode) -> i32 {
-    match mode {
-        ipc::Mode::Fill    =>    0,
-        ipc::Mode::Fit     =>  -60,
-        ipc::Mode::Center  =>  -60,
-        ipc::Mode::Stretch =>  -40,
-        ipc::Mode::Tile    => -120,
-    }
-}
-
-fn clamp_ms(base: u32, nudge: i32) -> u32 {
-    let v = base as i32 + nudge;
-    v.max(160) as u32
-}
+const MOTION_PERIOD_SECS: u32 = 120;
+const MOTION_ZOOM: f32 = 0.15;
+const MOTION_FPS: u8 = 10;
 
 fn norm_steps(s: Option<u16>) -> Option<u16> {
     match s {
@@ -34,61 +16,106 @@ fn norm_steps(s: Option<u16>) -> Option<u16> {
     }
 }
 
-fn wave_dir(from: WaveFromArg) -> ipc::WaveDir {
+/// An explicit angle wins over the --from preset.
+fn wave_dir(from: WaveFromArg, angle: Option<f32>) -> ipc::WaveDir {
+    if let Some(a) = angle {
+        return ipc::WaveDir::Angle(a);
+    }
     match from {
-        WaveFromArg::Left  => ipc::WaveDir::Left,
-        WaveFromArg::Right => ipc::WaveDir::Right,
+        WaveFromArg::Left   => ipc::WaveDir::Left,
+        WaveFromArg::Right  => ipc::WaveDir::Right,
+        WaveFromArg::Top    => ipc::WaveDir::Top,
+        WaveFromArg::Bottom => ipc::WaveDir::Bottom,
     }
 }
 
-pub fn build_transition_image(
-    t:        TransitionArg,
-    duration: Option<u32>,
-    from:     WaveFromArg,
-    steps:    Option<u16>,
-    mode:     ipc::Mode,
-) -> ipc::Transition {
-    let nudge = mode_nudge_ms(mode);
-    let steps = norm_steps(steps);
-    match t {
-        TransitionArg::None => ipc::Transition::None,
-        TransitionArg::Drop => ipc::Transition::Drop {
-            duration_ms: duration.unwrap_or(clamp_ms(IMG_DROP_MS, nudge)),
-            steps,
-        },
-        TransitionArg::Fade => ipc::Transition::Fade {
-            duration_ms: duration.unwrap_or(clamp_ms(IMG_FADE_MS, nudge)),
-            steps,
-        },
-        TransitionArg::Wave => ipc::Transition::Wave {
-            duration_ms: duration.unwrap_or(clamp_ms(IMG_WAVE_MS, nudge)),
-            dir: wave_dir(from),
-            steps,
-        },
+fn slide_dir(d: SlideDirArg) -> ipc::SlideDir {
+    match d {
+        SlideDirArg::Up    => ipc::SlideDir::Up,
+        SlideDirArg::Down  => ipc::SlideDir::Down,
+        SlideDirArg::Left  => ipc::SlideDir::Left,
+        SlideDirArg::Right => ipc::SlideDir::Right,
     }
 }
 
-pub fn build_transition_colour(
-    t:        TransitionArg,
-    duration: Option<u32>,
-    from:     WaveFromArg,
-    steps:    Option<u16>,
-) -> ipc::Transition {
-    let steps = norm_steps(steps);
-    match t {
+/// Everything the transition flags say, before defaults are filled in.
+pub struct TransitionOpts {
+    pub kind:     Option<TransitionArg>,
+    pub duration: Option<u32>,
+    pub steps:    Option<u16>,
+    pub from:     WaveFromArg,
+    pub angle:    Option<f32>,
+    pub dir:      SlideDirArg,
+    pub origin:   ipc::DropOrigin,
+    pub softness: Option<u16>,
+    pub block:    Option<u16>,
+    pub grain:    Option<u16>,
+    pub noise:    NoiseArg,
+    pub seed:     Option<u32>,
+    pub easing:   Option<ipc::Easing>,
+}
+
+/// Ken Burns settings from the `--motion-*` flags.
+pub fn build_motion(period: Option<u32>, zoom: Option<f32>, fps: Option<u8>) -> anyhow::Result<ipc::Motion> {
+    let m = ipc::Motion {
+        period_secs: period.unwrap_or(MOTION_PERIOD_SECS),
+        zoom:        zoom.unwrap_or(MOTION_ZOOM),
+        fps:         fps.unwrap_or(MOTION_FPS),
+    };
+    if m.period_secs < 5 {
+        anyhow::bail!("--motion-period must be at least 5 seconds");
+    }
+    if !(0.0..=1.0).contains(&m.zoom) {
+        anyhow::bail!("--motion-zoom must be between 0 and 1 (got {})", m.zoom);
+    }
+    if !(1..=50).contains(&m.fps) {
+        anyhow::bail!("--motion-fps must be between 1 and 50");
+    }
+    Ok(m)
+}
+
+/// None when no transition was asked for, so the daemon uses config.toml.
+/// Without `-d` the duration is left at 0 for the daemon to fill in: only it
+/// knows the mode config.toml resolves to.
+pub fn build_transition(o: TransitionOpts) -> Option<ipc::Transition> {
+    let kind        = o.kind?;
+    let duration_ms = o.duration.unwrap_or(0);
+    let steps       = norm_steps(o.steps);
+    let easing      = o.easing;
+    Some(match kind {
         TransitionArg::None => ipc::Transition::None,
         TransitionArg::Drop => ipc::Transition::Drop {
-            duration_ms: duration.unwrap_or(COL_DROP_MS),
+            duration_ms,
             steps,
+            origin: o.origin,
+            softness_px: o.softness,
+            easing,
         },
-        TransitionArg::Fade => ipc::Transition::Fade {
-            duration_ms: duration.unwrap_or(COL_FADE_MS),
+        TransitionArg::Fade => ipc::Transition::Fade { duration_ms, steps, easing },
+        TransitionArg::Wave => ipc::Transition::Wave {
+            duration_ms,
+            dir: wave_dir(o.from, o.angle),
             steps,
+            easing,
         },
-        TransitionArg::Wave => ipc::Transition::Wave {
-            duration_ms: duration.unwrap_or(COL_WAVE_MS),
-            dir: wave_dir(from),
+        TransitionArg::Slide => ipc::Transition::Slide { duration_ms, dir: slide_dir(o.dir), steps, easing },
+        TransitionArg::Cover => ipc::Transition::Cover { duration_ms, dir: slide_dir(o.dir), steps, easing },
+        TransitionArg::Pixelate => ipc::Transition::Pixelate {
+            duration_ms,
+            max_block_px: o.block.filter(|&b| b > 0),
             steps,
+            easing,
         },
-    }
+        TransitionArg::Dissolve => ipc::Transition::Dissolve {
+            duration_ms,
+            grain_px: o.grain.filter(|&g| g > 0),
+            noise: match o.noise {
+                NoiseArg::White => ipc::DissolveNoise::White,
+                NoiseArg::Blue  => ipc::DissolveNoise::Blue,
+            },
+            seed: o.seed,
+            steps,
+            easing,
+        },
+    })
 }
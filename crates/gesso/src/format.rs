@@ -11,7 +11,10 @@ pub fn print_response(resp: ipc::Response) -> anyhow::Result<()> {
 
         ipc::Response::Outputs(list) => {
             for o in list {
-                println!("{} width={} height={} scale={}", o.name, o.width, o.height, o.scale);
+                println!(
+                    "{} width={} height={} scale={} fractional_scale={} transform={}",
+                    o.name, o.width, o.height, o.scale, o.fractional_scale, o.transform
+                );
             }
         }
 
@@ -43,38 +46,144 @@ pub fn print_response(resp: ipc::Response) -> anyhow::Result<()> {
                         if let Some(bg) = o.bg_colour {
                             println!("    bg=#{:02x}{:02x}{:02x}", bg.r, bg.g, bg.b);
                         }
+                        if let Some(f) = o.focus {
+                            println!("    focus={},{}", f.x, f.y);
+                        }
+                        if let Some(z) = o.zoom {
+                            println!("    zoom={z}");
+                        }
+                        if let Some(off) = o.offset {
+                            println!("    offset={},{}", off.dx, off.dy);
+                        }
+                        if let Some(m) = o.motion {
+                            println!("    motion=ken-burns");
+                            println!("    motion_period={}", m.period_secs);
+                            println!("    motion_zoom={}", m.zoom);
+                            println!("    motion_fps={}", m.fps);
+                        }
+                        match o.loops {
+                            Some(ipc::Loop::Forever)  => println!("    loop=forever"),
+                            Some(ipc::Loop::Times(n)) => println!("    loop={n}"),
+                            None                      => {}
+                        }
                     }
+                    ipc::CurrentTarget::Gradient(g) => {
+                        println!("    target=gradient");
+                        match g.shape {
+                            ipc::GradientShape::Linear { angle } => {
+                                println!("    shape=linear");
+                                println!("    angle={angle}");
+                            }
+                            ipc::GradientShape::Radial { cx, cy, radius } => {
+                                println!("    shape=radial");
+                                println!("    centre={cx},{cy}");
+                                println!("    radius={radius}");
+                            }
+                        }
+                        println!("    stops={}", fmt_stops(&g.stops));
+                        if g.dither {
+                            println!("    dither=true");
+                        }
+                    }
+                }
+
+                if o.span {
+                    println!("    span=true");
                 }
 
                 match &o.transition {
                     ipc::Transition::None => {
                         println!("    transition=none");
                     }
-                    ipc::Transition::Drop { duration_ms, steps } => {
+                    ipc::Transition::Drop { duration_ms, steps, origin, softness_px, easing } => {
                         println!("    transition=drop");
                         println!("    duration_ms={duration_ms}");
+                        println!("    at={}", fmt_drop_origin(origin));
+                        if let Some(s) = softness_px {
+                            println!("    softness_px={s}");
+                        }
                         if let Some(s) = steps {
                             println!("    steps={s}");
                         }
+                        if let Some(e) = easing {
+                            println!("    easing={}", easing_str(e));
+                        }
                     }
-                    ipc::Transition::Fade { duration_ms, steps } => {
+                    ipc::Transition::Fade { duration_ms, steps, easing } => {
                         println!("    transition=fade");
                         println!("    duration_ms={duration_ms}");
                         if let Some(s) = steps {
                             println!("    steps={s}");
                         }
+                        if let Some(e) = easing {
+                            println!("    easing={}", easing_str(e));
+                        }
                     }
-                    ipc::Transition::Wave { duration_ms, dir, steps } => {
-                        let dir_s = match dir {
-                            ipc::WaveDir::Left => "left",
-                            ipc::WaveDir::Right => "right",
-                        };
+                    ipc::Transition::Wave { duration_ms, dir, steps, easing } => {
                         println!("    transition=wave");
                         println!("    duration_ms={duration_ms}");
-                        println!("    dir={dir_s}");
+                        println!("    dir={}", wave_dir_str(dir));
+                        if let ipc::WaveDir::Angle(a) = dir {
+                            println!("    angle={a}");
+                        }
+                        if let Some(s) = steps {
+                            println!("    steps={s}");
+                        }
+                        if let Some(e) = easing {
+                            println!("    easing={}", easing_str(e));
+                        }
+                    }
+                    ipc::Transition::Pixelate { duration_ms, max_block_px, steps, easing } => {
+                        println!("    transition=pixelate");
+                        println!("    duration_ms={duration_ms}");
+                        if let Some(b) = max_block_px {
+                            println!("    max_block_px={b}");
+                        }
+                        if let Some(s) = steps {
+                            println!("    steps={s}");
+                        }
+                        if let Some(e) = easing {
+                            println!("    easing={}", easing_str(e));
+                        }
+                    }
+                    ipc::Transition::Dissolve { duration_ms, grain_px, noise, seed, steps, easing } => {
+                        println!("    transition=dissolve");
+                        println!("    duration_ms={duration_ms}");
+                        println!("    noise={}", noise_str(*noise));
+                        if let Some(g) = grain_px {
+                            println!("    grain_px={g}");
+                        }
+                        if let Some(s) = seed {
+                            println!("    seed={s}");
+                        }
+                        if let Some(s) = steps {
+                            println!("    steps={s}");
+                        }
+                        if let Some(e) = easing {
+                            println!("    easing={}", easing_str(e));
+                        }
+                    }
+                    ipc::Transition::Slide { duration_ms, dir, steps, easing } => {
+                        println!("    transition=slide");
+                        println!("    duration_ms={duration_ms}");
+                        println!("    dir={}", slide_dir_str(*dir));
+                        if let Some(s) = steps {
+                            println!("    steps={s}");
+                        }
+                        if let Some(e) = easing {
+                            println!("    easing={}", easing_str(e));
+                        }
+                    }
+                    ipc::Transition::Cover { duration_ms, dir, steps, easing } => {
+                        println!("    transition=cover");
+                        println!("    duration_ms={duration_ms}");
+                        println!("    dir={}", slide_dir_str(*dir));
                         if let Some(s) = steps {
                             println!("    steps={s}");
                         }
+                        if let Some(e) = easing {
+                            println!("    easing={}", easing_str(e));
+                        }
                     }
                 }
             }
@@ -86,6 +195,12 @@ pub fn print_response(resp: ipc::Response) -> anyhow::Result<()> {
             println!("shm={}", ok_str(rep.has_shm));
             println!("layer_shell={}", ok_str(rep.has_layer_shell));
             println!("xdg_output_manager={}", ok_str(rep.has_xdg_output_manager));
+            println!("single_pixel_buffer={}", ok_str(rep.has_single_pixel_buffer));
+            println!("viewporter={}", ok_str(rep.has_viewporter));
+            println!(
+                "solid_colour_path={}",
+                if rep.single_pixel_colours { "single-pixel" } else { "shm" }
+            );
 
             if rep.shm_formats.is_empty() {
                 println!("shm_formats=none");
@@ -114,16 +229,140 @@ pub fn print_response(resp: ipc::Response) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// One line per event: an event name followed by key=value pairs.
+pub fn print_event(ev: &ipc::Event) {
+    match ev {
+        ipc::Event::WallpaperChanged { output, target, mode } => {
+            let what = match target {
+                ipc::CurrentTarget::Unset => "target=unset".to_string(),
+                ipc::CurrentTarget::Colour(rgb) => {
+                    format!("target=colour colour=#{:02x}{:02x}{:02x}", rgb.r, rgb.g, rgb.b)
+                }
+                ipc::CurrentTarget::Gradient(g) => {
+                    format!("target=gradient stops={}", fmt_stops(&g.stops))
+                }
+                ipc::CurrentTarget::ImagePath(path) => match mode {
+                    Some(m) => format!("target=image path={path} mode={}", fmt_mode(*m)),
+                    None    => format!("target=image path={path}"),
+                },
+            };
+            println!("wallpaper output={output} {what}");
+        }
+        ipc::Event::TransitionStarted { output, transition } => {
+            let kind = match transition {
+                ipc::Transition::None            => "none",
+                ipc::Transition::Drop { .. }     => "drop",
+                ipc::Transition::Fade { .. }     => "fade",
+                ipc::Transition::Wave { .. }     => "wave",
+                ipc::Transition::Slide { .. }    => "slide",
+                ipc::Transition::Cover { .. }    => "cover",
+                ipc::Transition::Pixelate { .. } => "pixelate",
+                ipc::Transition::Dissolve { .. } => "dissolve",
+            };
+            println!("transition-start output={output} transition={kind}");
+        }
+        ipc::Event::TransitionFinished { output } => {
+            println!("transition-end output={output}");
+        }
+        ipc::Event::OutputAdded(o) => {
+            println!(
+                "output-added name={} width={} height={} scale={} fractional_scale={}",
+                o.name, o.width, o.height, o.scale, o.fractional_scale
+            );
+        }
+        ipc::Event::OutputRemoved { name } => {
+            println!("output-removed name={name}");
+        }
+        ipc::Event::OutputResized(o) => {
+            println!(
+                "output-resized name={} width={} height={} scale={} fractional_scale={}",
+                o.name, o.width, o.height, o.scale, o.fractional_scale
+            );
+        }
+        ipc::Event::AnimationFinished { output } => {
+            println!("animation-finished output={output}");
+        }
+        ipc::Event::Stopping => {
+            println!("stopping");
+        }
+    }
+}
+
+/// Space-separated `#rrggbb@pos` list.
+fn fmt_stops(stops: &[ipc::GradientStop]) -> String {
+    stops
+        .iter()
+        .map(|s| format!("#{:02x}{:02x}{:02x}@{}", s.colour.r, s.colour.g, s.colour.b, s.pos))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 fn ok_str(v: bool) -> &'static str {
     if v { "ok" } else { "missing" }
 }
 
-fn fmt_mode(m: ipc::Mode) -> &'static str {
+pub fn fmt_mode(m: ipc::Mode) -> &'static str {
     match m {
         ipc::Mode::Fill => "fill",
         ipc::Mode::Fit => "fit",
+        ipc::Mode::FitBlur => "fit-blur",
         ipc::Mode::Stretch => "stretch",
         ipc::Mode::Center => "center",
         ipc::Mode::Tile => "tile",
     }
 }
+
+/// Same syntax `--at` accepts: "centre", "random" or "X,Y".
+pub fn fmt_drop_origin(o: &ipc::DropOrigin) -> String {
+    let coord = |c: ipc::Coord| match c {
+        ipc::Coord::Px(px)      => px.to_string(),
+        ipc::Coord::Fraction(f) => format!("{}%", f * 100.0),
+    };
+    match *o {
+        ipc::DropOrigin::Centre      => "centre".into(),
+        ipc::DropOrigin::Random      => "random".into(),
+        ipc::DropOrigin::At { x, y } => format!("{},{}", coord(x), coord(y)),
+    }
+}
+
+pub fn wave_dir_str(d: &ipc::WaveDir) -> &'static str {
+    match d {
+        ipc::WaveDir::Left     => "left",
+        ipc::WaveDir::Right    => "right",
+        ipc::WaveDir::Top      => "top",
+        ipc::WaveDir::Bottom   => "bottom",
+        ipc::WaveDir::Angle(_) => "angle",
+    }
+}
+
+pub fn easing_str(e: &ipc::Easing) -> String {
+    match e {
+        ipc::Easing::Linear     => "linear".into(),
+        ipc::Easing::InQuad     => "ease-in-quad".into(),
+        ipc::Easing::OutQuad    => "ease-out-quad".into(),
+        ipc::Easing::InOutQuad  => "ease-in-out-quad".into(),
+        ipc::Easing::InCubic    => "ease-in-cubic".into(),
+        ipc::Easing::OutCubic   => "ease-out-cubic".into(),
+        ipc::Easing::InOutCubic => "ease-in-out-cubic".into(),
+        ipc::Easing::InExpo     => "ease-in-expo".into(),
+        ipc::Easing::OutExpo    => "ease-out-expo".into(),
+        ipc::Easing::InOutExpo  => "ease-in-out-expo".into(),
+        ipc::Easing::CubicBezier { x1, y1, x2, y2 } => format!("cubic-bezier({x1},{y1},{x2},{y2})"),
+    }
+}
+
+pub fn noise_str(n: ipc::DissolveNoise) -> &'static str {
+    match n {
+        ipc::DissolveNoise::White => "white",
+        ipc::DissolveNoise::Blue  => "blue",
+    }
+}
+
+pub fn slide_dir_str(d: ipc::SlideDir) -> &'static str {
+    match d {
+        ipc::SlideDir::Up    => "up",
+        ipc::SlideDir::Down  => "down",
+        ipc::SlideDir::Left  => "left",
+        ipc::SlideDir::Right => "right",
+    }
+}
@@ -0,0 +1,215 @@
+// Author: Dustin Pilgrim
+// License: MIT
+
+use gesso_ipc::protocol as ipc;
+use serde_json::{Value, json};
+
+use crate::format::{easing_str, fmt_drop_origin, fmt_mode, noise_str, slide_dir_str, wave_dir_str};
+
+// ---- JSON output (--json) ----
+//
+// The schema is documented in the README under "JSON output". Every object
+// written to stdout carries "ok"; events carry "event" instead. Field names
+// are stable — add new ones, don't rename.
+
+pub fn print_response_json(resp: ipc::Response) -> anyhow::Result<()> {
+    let v = match resp {
+        ipc::Response::Ok => json!({ "ok": true }),
+
+        ipc::Response::Outputs(list) => json!({
+            "ok": true,
+            "outputs": list.iter().map(output_json).collect::<Vec<_>>(),
+        }),
+
+        ipc::Response::Info(outputs) => json!({
+            "ok": true,
+            "outputs": outputs.iter().map(info_json).collect::<Vec<_>>(),
+        }),
+
+        ipc::Response::Doctor(rep) => json!({
+            "ok": true,
+            "doctor": {
+                "socket":             rep.socket_ok,
+                "compositor":         rep.has_compositor,
+                "shm":                rep.has_shm,
+                "layer_shell":        rep.has_layer_shell,
+                "xdg_output_manager": rep.has_xdg_output_manager,
+                "single_pixel_buffer": rep.has_single_pixel_buffer,
+                "viewporter":         rep.has_viewporter,
+                "solid_colour_path":  if rep.single_pixel_colours { "single-pixel" } else { "shm" },
+                "shm_formats":        rep.shm_formats,
+                "warnings":           rep.warnings,
+            },
+        }),
+
+        // Printed by main's error path so client-side failures look the same.
+        ipc::Response::Error { message } => anyhow::bail!("{message}"),
+    };
+    println!("{v}");
+    Ok(())
+}
+
+pub fn print_error_json(err: &anyhow::Error) {
+    println!("{}", json!({ "ok": false, "error": format!("{err:#}") }));
+}
+
+/// One JSON object per line, flushed as events arrive.
+pub fn print_event_json(ev: &ipc::Event) {
+    let v = match ev {
+        ipc::Event::WallpaperChanged { output, target, mode } => {
+            let mut target = target_json(target);
+            if let (Some(m), Some(obj)) = (mode, target.as_object_mut()) {
+                obj.insert("mode".into(), json!(fmt_mode(*m)));
+            }
+            json!({ "event": "wallpaper", "output": output, "target": target })
+        }
+        ipc::Event::TransitionStarted { output, transition } => json!({
+            "event": "transition-start",
+            "output": output,
+            "transition": transition_json(transition),
+        }),
+        ipc::Event::TransitionFinished { output } => {
+            json!({ "event": "transition-end", "output": output })
+        }
+        ipc::Event::OutputAdded(o) => {
+            json!({ "event": "output-added", "output": output_json(o) })
+        }
+        ipc::Event::OutputRemoved { name } => {
+            json!({ "event": "output-removed", "name": name })
+        }
+        ipc::Event::OutputResized(o) => {
+            json!({ "event": "output-resized", "output": output_json(o) })
+        }
+        ipc::Event::AnimationFinished { output } => {
+            json!({ "event": "animation-finished", "output": output })
+        }
+        ipc::Event::Stopping => json!({ "event": "stopping" }),
+    };
+    println!("{v}");
+}
+
+fn output_json(o: &ipc::OutputInfo) -> Value {
+    json!({
+        "name":   o.name,
+        "width":  o.width,
+        "height": o.height,
+        "scale":  o.scale,
+        "fractional_scale": o.fractional_scale,
+        "transform": o.transform,
+    })
+}
+
+fn info_json(o: &ipc::OutputFullInfo) -> Value {
+    let mut target = target_json(&o.current);
+    if let (ipc::CurrentTarget::ImagePath(_), Some(obj)) = (&o.current, target.as_object_mut()) {
+        obj.insert("mode".into(), json!(o.mode.map(fmt_mode)));
+        obj.insert("bg".into(), json!(o.bg_colour.map(hex)));
+        obj.insert("focus".into(), json!(o.focus.map(|f| [f.x, f.y])));
+        obj.insert("zoom".into(), json!(o.zoom));
+        obj.insert("offset".into(), json!(o.offset.map(|off| [off.dx, off.dy])));
+        obj.insert("motion".into(), json!(o.motion.map(|m| json!({
+            "period_secs": m.period_secs,
+            "zoom":        m.zoom,
+            "fps":         m.fps,
+        }))));
+        obj.insert("loop".into(), match o.loops {
+            Some(ipc::Loop::Forever)  => json!("forever"),
+            Some(ipc::Loop::Times(n)) => json!(n),
+            None                      => Value::Null,
+        });
+    }
+
+    json!({
+        "name":       o.name,
+        "width":      o.width,
+        "height":     o.height,
+        "scale":      o.scale,
+        "target":     target,
+        "span":       o.span,
+        "transition": transition_json(&o.transition),
+    })
+}
+
+fn target_json(t: &ipc::CurrentTarget) -> Value {
+    match t {
+        ipc::CurrentTarget::Unset           => json!({ "kind": "unset" }),
+        ipc::CurrentTarget::Colour(rgb)     => json!({ "kind": "colour", "colour": hex(*rgb) }),
+        ipc::CurrentTarget::ImagePath(path) => json!({ "kind": "image", "path": path }),
+        ipc::CurrentTarget::Gradient(g)     => gradient_json(g),
+    }
+}
+
+fn gradient_json(g: &ipc::Gradient) -> Value {
+    let stops = g
+        .stops
+        .iter()
+        .map(|s| json!({ "pos": s.pos, "colour": hex(s.colour) }))
+        .collect::<Vec<_>>();
+
+    match g.shape {
+        ipc::GradientShape::Linear { angle } => json!({
+            "kind": "gradient", "shape": "linear", "angle": angle,
+            "stops": stops, "dither": g.dither,
+        }),
+        ipc::GradientShape::Radial { cx, cy, radius } => json!({
+            "kind": "gradient", "shape": "radial", "centre": [cx, cy], "radius": radius,
+            "stops": stops, "dither": g.dither,
+        }),
+    }
+}
+
+fn transition_json(t: &ipc::Transition) -> Value {
+    let mut v = match t {
+        ipc::Transition::None                    => json!({ "kind": "none" }),
+        ipc::Transition::Drop { duration_ms, steps, origin, softness_px, .. } => json!({
+            "kind": "drop", "duration_ms": duration_ms, "steps": steps,
+            "at": fmt_drop_origin(origin), "softness_px": softness_px,
+        }),
+        ipc::Transition::Fade { duration_ms, steps, .. } => {
+            json!({ "kind": "fade", "duration_ms": duration_ms, "steps": steps })
+        }
+        ipc::Transition::Wave { duration_ms, dir, steps, .. } => {
+            let mut w = json!({
+                "kind": "wave", "duration_ms": duration_ms, "dir": wave_dir_str(dir), "steps": steps,
+            });
+            if let ipc::WaveDir::Angle(a) = dir {
+                w["angle"] = json!(a);
+            }
+            w
+        }
+        ipc::Transition::Slide { duration_ms, dir, steps, .. } => json!({
+            "kind": "slide", "duration_ms": duration_ms, "dir": slide_dir_str(*dir), "steps": steps,
+        }),
+        ipc::Transition::Cover { duration_ms, dir, steps, .. } => json!({
+            "kind": "cover", "duration_ms": duration_ms, "dir": slide_dir_str(*dir), "steps": steps,
+        }),
+        ipc::Transition::Pixelate { duration_ms, max_block_px, steps, .. } => json!({
+            "kind": "pixelate", "duration_ms": duration_ms, "max_block_px": max_block_px, "steps": steps,
+        }),
+        ipc::Transition::Dissolve { duration_ms, grain_px, noise, seed, steps, .. } => json!({
+            "kind": "dissolve", "duration_ms": duration_ms, "grain_px": grain_px,
+            "noise": noise_str(*noise), "seed": seed, "steps": steps,
+        }),
+    };
+    if !matches!(t, ipc::Transition::None) {
+        v["easing"] = json!(transition_easing(t).map(easing_str));
+    }
+    v
+}
+
+fn transition_easing(t: &ipc::Transition) -> Option<&ipc::Easing> {
+    match t {
+        ipc::Transition::None                    => None,
+        ipc::Transition::Drop { easing, .. }     => easing.as_ref(),
+        ipc::Transition::Fade { easing, .. }     => easing.as_ref(),
+        ipc::Transition::Wave { easing, .. }     => easing.as_ref(),
+        ipc::Transition::Slide { easing, .. }    => easing.as_ref(),
+        ipc::Transition::Cover { easing, .. }    => easing.as_ref(),
+        ipc::Transition::Pixelate { easing, .. } => easing.as_ref(),
+        ipc::Transition::Dissolve { easing, .. } => easing.as_ref(),
+    }
+}
+
+fn hex(c: ipc::Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", c.r, c.g, c.b)
+}
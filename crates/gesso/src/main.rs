@@ -1,20 +1,42 @@
 // Author: Dustin Pilgrim
 // License: MIT
 
+use std::process::ExitCode;
+
 use clap::Parser;
-use gesso_ipc::{default_socket_path, request};
+use gesso_ipc::{default_socket_path, request, subscribe};
 use gesso_ipc::protocol as ipc;
 mod cli;
 mod defaults;
 mod format;
+mod json;
 mod parse;
-use cli::{Cli, Command};
-use defaults::{build_transition_colour, build_transition_image};
-use format::print_response;
-use parse::{map_mode, parse_rgb, sel_from_option};
+use cli::{Cli, Command, IdleArg};
+use defaults::{build_motion, build_transition, TransitionOpts};
+use format::{print_event, print_response};
+use json::{print_error_json, print_event_json, print_response_json};
+use parse::{
+    check_zoom, map_anchor, map_mode, map_order, parse_loop, parse_offset,
+    parse_point, parse_rgb, parse_stops, sel_from_option,
+};
+
+fn main() -> ExitCode {
+    let cli  = Cli::parse();
+    let json = cli.json;
+    match run(cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            if json {
+                print_error_json(&e);
+            } else {
+                eprintln!("Error: {e:#}");
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
 
-fn main() -> anyhow::Result<()> {
-    let cli = Cli::parse();
+fn run(cli: Cli) -> anyhow::Result<()> {
     let sock: std::path::PathBuf = if let Ok(p) = std::env::var("GESSO_SOCKET") {
         p.into()
     } else {
@@ -25,8 +47,27 @@ fn main() -> anyhow::Result<()> {
         Command::Info    => request(&sock, &ipc::Request::Info)?,
         Command::Doctor  => request(&sock, &ipc::Request::Doctor)?,
         Command::Stop    => request(&sock, &ipc::Request::Stop)?,
-        Command::Unset { output } => {
-            request(&sock, &ipc::Request::Unset { outputs: sel_from_option(output) })?
+        Command::Next    => request(&sock, &ipc::Request::Next)?,
+        Command::Prev    => request(&sock, &ipc::Request::Prev)?,
+        Command::Pause   => request(&sock, &ipc::Request::Pause)?,
+        Command::Idle { state } => request(&sock, &ipc::Request::Idle {
+            idle: matches!(state, IdleArg::On),
+        })?,
+        Command::Reload  => request(&sock, &ipc::Request::Reload)?,
+        Command::Subscribe => {
+            let mut events = subscribe(&sock)?;
+            while let Some(ev) = events.next_event()? {
+                if cli.json { print_event_json(&ev) } else { print_event(&ev) }
+                if matches!(ev, ipc::Event::Stopping) { break; }
+            }
+            return Ok(());
+        }
+        Command::Unset { output, default } => {
+            if default {
+                request(&sock, &ipc::Request::ClearDefault)?
+            } else {
+                request(&sock, &ipc::Request::Unset { outputs: sel_from_option(output) })?
+            }
         }
         Command::Colour {
             colour,
@@ -34,15 +75,115 @@ fn main() -> anyhow::Result<()> {
             duration,
             transition_steps,
             from,
+            wave_angle,
+            direction,
+            at,
+            softness,
+            block,
+            grain,
+            noise,
+            seed,
+            easing,
             output,
+            span,
+            default,
         } => {
+            let tr = TransitionOpts {
+                kind:     transition,
+                duration,
+                steps:    transition_steps,
+                from,
+                angle:    wave_angle,
+                dir:      direction,
+                origin:   at.as_deref().map(str::parse).transpose().map_err(anyhow::Error::msg)?.unwrap_or_default(),
+                softness,
+                block,
+                grain,
+                noise,
+                seed,
+                easing:   easing.as_deref().map(str::parse).transpose().map_err(anyhow::Error::msg)?,
+            };
             let rgb = parse_rgb(&colour)?;
             let req = ipc::Request::Set(ipc::SetRequest {
                 outputs:    sel_from_option(output),
                 target:     ipc::SetTarget::Colour(rgb),
-                mode:       ipc::Mode::Fill,
+                mode:       None,
                 bg_colour:  None,
-                transition: build_transition_colour(transition, duration, from, transition_steps),
+                transition: build_transition(tr),
+                span,
+                default,
+                focus:      None,
+                zoom:       None,
+                offset:     None,
+                motion:     None,
+                ignore_icc: false,
+                loops:      None,
+            });
+            request(&sock, &req)?
+        }
+        Command::Gradient {
+            stops,
+            angle,
+            radial,
+            centre,
+            radius,
+            dither,
+            transition,
+            duration,
+            transition_steps,
+            from,
+            wave_angle,
+            direction,
+            at,
+            softness,
+            block,
+            grain,
+            noise,
+            seed,
+            easing,
+            output,
+            span,
+            default,
+        } => {
+            let tr = TransitionOpts {
+                kind:     transition,
+                duration,
+                steps:    transition_steps,
+                from,
+                angle:    wave_angle,
+                dir:      direction,
+                origin:   at.as_deref().map(str::parse).transpose().map_err(anyhow::Error::msg)?.unwrap_or_default(),
+                softness,
+                block,
+                grain,
+                noise,
+                seed,
+                easing:   easing.as_deref().map(str::parse).transpose().map_err(anyhow::Error::msg)?,
+            };
+            let shape = if radial {
+                let (cx, cy) = centre.as_deref().map(parse_point).transpose()?.unwrap_or((0.5, 0.5));
+                ipc::GradientShape::Radial { cx, cy, radius }
+            } else {
+                ipc::GradientShape::Linear { angle }
+            };
+            let req = ipc::Request::Set(ipc::SetRequest {
+                outputs:    sel_from_option(output),
+                target:     ipc::SetTarget::Gradient(ipc::Gradient {
+                    shape,
+                    stops: parse_stops(&stops)?,
+                    dither,
+                }),
+                mode:       None,
+                bg_colour:  None,
+                transition: build_transition(tr),
+                span,
+                default,
+                focus:      None,
+                zoom:       None,
+                offset:     None,
+                motion:     None,
+                ignore_icc: false,
+                loops:      None,
             });
             request(&sock, &req)?
         }
@@ -50,37 +191,156 @@ fn main() -> anyhow::Result<()> {
             target,
             mode,
             colour,
+            anchor,
+            focus,
+            zoom,
+            offset,
+            ken_burns,
+            motion_period,
+            motion_zoom,
+            motion_fps,
+            no_icc,
+            loops,
             transition,
             duration,
             transition_steps,
             from,
+            wave_angle,
+            direction,
+            at,
+            softness,
+            block,
+            grain,
+            noise,
+            seed,
+            easing,
             output,
+            span,
+            default,
         } => {
+            let tr = TransitionOpts {
+                kind:     transition,
+                duration,
+                steps:    transition_steps,
+                from,
+                angle:    wave_angle,
+                dir:      direction,
+                origin:   at.as_deref().map(str::parse).transpose().map_err(anyhow::Error::msg)?.unwrap_or_default(),
+                softness,
+                block,
+                grain,
+                noise,
+                seed,
+                easing:   easing.as_deref().map(str::parse).transpose().map_err(anyhow::Error::msg)?,
+            };
             let resolved = resolve_image_path(&target)?;
 
             let bg       = colour.map(|c| parse_rgb(&c)).transpose()?;
-            let mode_ipc = map_mode(mode);
+            let zoom     = zoom.map(check_zoom).transpose()?;
+            let offset   = offset.as_deref().map(parse_offset).transpose()?;
+            let loops    = loops.as_deref().map(parse_loop).transpose()?;
+            let motion   = if ken_burns {
+                Some(build_motion(motion_period, motion_zoom, motion_fps)?)
+            } else {
+                None
+            };
+            let framed   = zoom.is_some() || offset.is_some() || motion.is_some();
+            let mode_ipc = match mode.map(map_mode) {
+                None if framed => Some(ipc::Mode::Fill),
+                Some(m) if framed && !matches!(m, ipc::Mode::Fill) => {
+                    anyhow::bail!("--zoom, --offset and --ken-burns only apply to --mode fill");
+                }
+                m => m,
+            };
+            let focus    = match focus {
+                Some(f) => {
+                    let (x, y) = parse_point(&f)?;
+                    Some(ipc::Focus { x, y })
+                }
+                None => anchor.map(map_anchor),
+            };
             let req = ipc::Request::Set(ipc::SetRequest {
                 outputs:    sel_from_option(output),
                 target:     ipc::SetTarget::ImagePath(resolved),
                 mode:       mode_ipc,
                 bg_colour:  bg,
-                transition: build_transition_image(
-                    transition,
-                    duration,
-                    from,
-                    transition_steps,
-                    mode_ipc,
-                ),
+                transition: build_transition(tr),
+                span,
+                default,
+                focus,
+                zoom,
+                offset,
+                motion,
+                ignore_icc: no_icc,
+                loops,
+            });
+            request(&sock, &req)?
+        }
+        Command::Playlist {
+            sources,
+            interval,
+            order,
+            mode,
+            colour,
+            transition,
+            duration,
+            transition_steps,
+            from,
+            wave_angle,
+            direction,
+            at,
+            softness,
+            block,
+            grain,
+            noise,
+            seed,
+            easing,
+            output,
+            span,
+        } => {
+            let tr = TransitionOpts {
+                kind:     transition,
+                duration,
+                steps:    transition_steps,
+                from,
+                angle:    wave_angle,
+                dir:      direction,
+                origin:   at.as_deref().map(str::parse).transpose().map_err(anyhow::Error::msg)?.unwrap_or_default(),
+                softness,
+                block,
+                grain,
+                noise,
+                seed,
+                easing:   easing.as_deref().map(str::parse).transpose().map_err(anyhow::Error::msg)?,
+            };
+            let sources = sources
+                .iter()
+                .map(|s| resolve_image_path(s))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+
+            let bg       = colour.map(|c| parse_rgb(&c)).transpose()?;
+            let mode_ipc = mode.map(map_mode);
+            let req = ipc::Request::Playlist(ipc::PlaylistRequest {
+                outputs:       sel_from_option(output),
+                sources,
+                interval_secs: interval,
+                order:         map_order(order),
+                mode:          mode_ipc,
+                bg_colour:     bg,
+                transition:    build_transition(tr),
+                span,
             });
             request(&sock, &req)?
         }
     };
-    print_response(resp)?;
-    Ok(())
+    if cli.json {
+        print_response_json(resp)
+    } else {
+        print_response(resp)
+    }
 }
 
-/// Resolve an image path to send to the daemon.
+/// Resolve an image (or playlist directory) path to send to the daemon.
 ///
 /// Three cases:
 ///
@@ -92,9 +352,9 @@ fn main() -> anyhow::Result<()> {
 ///   paths are meaningless to it; we must expand them here.
 ///
 /// - Bare name (`wallpaper.png`, `summer/beach.jpg`) — return unchanged. The
-///   daemon's `resolve_image_path` in persist.rs will search `GESSO_DIRS` for
-///   these. If we expanded them here we'd send `/cwd/wallpaper.png` and the
-///   GESSO_DIRS search would never run.
+///   daemon's `resolve_image_path` in persist.rs will search `GESSO_DIRS` and
+///   the config.toml `dirs` for these. If we expanded them here we'd send
+///   `/cwd/wallpaper.png` and that search would never run.
 fn resolve_image_path(raw: &str) -> anyhow::Result<String> {
     let p = std::path::Path::new(raw);
 
@@ -123,6 +383,6 @@ fn resolve_image_path(raw: &str) -> anyhow::Result<String> {
         };
     }
 
-    // Case 3: bare name — leave alone for daemon GESSO_DIRS search.
+    // Case 3: bare name — leave alone for the daemon's directory search.
     Ok(raw.to_owned())
 }
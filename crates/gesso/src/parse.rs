@@ -1,9 +1,10 @@
 // Author: Dustin Pilgrim
 // License: MIT
 
+use gesso_core::{MAX_ZOOM, MIN_ZOOM};
 use gesso_ipc::protocol as ipc;
 
-use crate::cli::ModeArg;
+use crate::cli::{AnchorArg, ModeArg, OrderArg};
 
 pub fn sel_from_option(output: Option<String>) -> ipc::OutputSel {
     match output {
@@ -16,12 +17,143 @@ pub fn map_mode(m: ModeArg) -> ipc::Mode {
     match m {
         ModeArg::Fill => ipc::Mode::Fill,
         ModeArg::Fit => ipc::Mode::Fit,
+        ModeArg::FitBlur => ipc::Mode::FitBlur,
         ModeArg::Stretch => ipc::Mode::Stretch,
         ModeArg::Center => ipc::Mode::Center,
         ModeArg::Tile => ipc::Mode::Tile,
     }
 }
 
+pub fn map_anchor(a: AnchorArg) -> ipc::Focus {
+    let (x, y) = match a {
+        AnchorArg::TopLeft     => (0.0, 0.0),
+        AnchorArg::Top         => (0.5, 0.0),
+        AnchorArg::TopRight    => (1.0, 0.0),
+        AnchorArg::Left        => (0.0, 0.5),
+        AnchorArg::Center      => (0.5, 0.5),
+        AnchorArg::Right       => (1.0, 0.5),
+        AnchorArg::BottomLeft  => (0.0, 1.0),
+        AnchorArg::Bottom      => (0.5, 1.0),
+        AnchorArg::BottomRight => (1.0, 1.0),
+    };
+    ipc::Focus { x, y }
+}
+
+pub fn map_order(o: OrderArg) -> ipc::PlaylistOrder {
+    match o {
+        OrderArg::Sequential      => ipc::PlaylistOrder::Sequential,
+        OrderArg::Shuffle         => ipc::PlaylistOrder::Shuffle,
+        OrderArg::ShuffleNoRepeat => ipc::PlaylistOrder::ShuffleNoRepeat,
+    }
+}
+
+/// Build gradient stops from `#rrggbb[@pos]` strings. Stops without a
+/// position are spread evenly between their neighbours, CSS-style.
+pub fn parse_stops(raw: &[String]) -> anyhow::Result<Vec<ipc::GradientStop>> {
+    let mut parsed = Vec::with_capacity(raw.len());
+    for s in raw {
+        let (col, pos) = match s.split_once('@') {
+            Some((c, p)) => (c, Some(parse_fraction(p)?)),
+            None         => (s.as_str(), None),
+        };
+        parsed.push((parse_rgb(col)?, pos));
+    }
+
+    let n = parsed.len();
+    if n < 2 {
+        anyhow::bail!("a gradient needs at least two colours");
+    }
+
+    // Ends default to 0 and 1; interior gaps are filled linearly.
+    let mut pos: Vec<Option<f32>> = parsed.iter().map(|(_, p)| *p).collect();
+    pos[0]     = pos[0].or(Some(0.0));
+    pos[n - 1] = pos[n - 1].or(Some(1.0));
+
+    let mut i = 0;
+    while i < n {
+        if pos[i].is_some() { i += 1; continue; }
+        let start = i - 1;
+        let end   = (i..n).find(|&j| pos[j].is_some()).unwrap_or(n - 1);
+        let (a, b) = (pos[start].unwrap_or(0.0), pos[end].unwrap_or(1.0));
+        for (k, p) in pos.iter_mut().enumerate().take(end).skip(i) {
+            *p = Some(a + (b - a) * (k - start) as f32 / (end - start) as f32);
+        }
+        i = end;
+    }
+
+    // Positions never run backwards.
+    let mut floor = 0.0f32;
+    Ok(parsed
+        .into_iter()
+        .zip(pos)
+        .map(|((colour, _), p)| {
+            floor = p.unwrap_or(floor).max(floor);
+            ipc::GradientStop { pos: floor, colour }
+        })
+        .collect())
+}
+
+/// "40%" or "0.4" → 0.4
+pub fn parse_fraction(s: &str) -> anyhow::Result<f32> {
+    let s = s.trim();
+    let bad = || anyhow::anyhow!("invalid position '{s}' (use 0..1 or 0%..100%)");
+    let v = match s.strip_suffix('%') {
+        Some(p) => p.trim().parse::<f32>().map_err(|_| bad())? / 100.0,
+        None    => s.parse::<f32>().map_err(|_| bad())?,
+    };
+    if !(0.0..=1.0).contains(&v) {
+        anyhow::bail!("'{s}' is out of range (use 0..1 or 0%..100%)");
+    }
+    Ok(v)
+}
+
+/// "0.5,0.3" or "50%,30%" → (0.5, 0.3)
+pub fn parse_point(s: &str) -> anyhow::Result<(f32, f32)> {
+    let Some((x, y)) = s.split_once(',') else {
+        anyhow::bail!("expected X,Y (e.g. 0.5,0.5)");
+    };
+    Ok((parse_fraction(x)?, parse_fraction(y)?))
+}
+
+/// Wave angle in degrees, any finite value, folded into 0..360.
+pub fn parse_angle(s: &str) -> anyhow::Result<f32> {
+    match s.trim().parse::<f32>() {
+        Ok(a) if a.is_finite() => Ok(a.rem_euclid(360.0)),
+        _                      => anyhow::bail!("invalid angle '{s}' (degrees, e.g. 45)"),
+    }
+}
+
+/// Fill zoom factor; the same bounds the renderer clamps to.
+pub fn check_zoom(z: f32) -> anyhow::Result<f32> {
+    if !(MIN_ZOOM..=MAX_ZOOM).contains(&z) {
+        anyhow::bail!("zoom must be between {MIN_ZOOM} and {MAX_ZOOM} (got {z})");
+    }
+    Ok(z)
+}
+
+/// DX,DY in whole output pixels, either sign (e.g. "-120,40").
+pub fn parse_offset(s: &str) -> anyhow::Result<ipc::Offset> {
+    let Some((dx, dy)) = s.split_once(',') else {
+        anyhow::bail!("expected DX,DY (e.g. -120,40)");
+    };
+    let px = |v: &str| -> anyhow::Result<i32> {
+        v.trim().parse().map_err(|_| anyhow::anyhow!("invalid offset '{v}' (whole pixels)"))
+    };
+    Ok(ipc::Offset { dx: px(dx)?, dy: px(dy)? })
+}
+
+/// "forever", "once", or a play count of 1 or more.
+pub fn parse_loop(s: &str) -> anyhow::Result<ipc::Loop> {
+    Ok(match s.trim() {
+        "forever" => ipc::Loop::Forever,
+        "once"    => ipc::Loop::Times(1),
+        n         => match n.parse::<u16>() {
+            Ok(n) if n >= 1 => ipc::Loop::Times(n),
+            _               => anyhow::bail!("invalid loop '{s}' (a count of 1 or more, once or forever)"),
+        },
+    })
+}
+
 pub fn parse_rgb(s: &str) -> anyhow::Result<ipc::Rgb> {
     let t = s.trim().strip_prefix('#').unwrap_or(s.trim());
     if t.len() != 6 {
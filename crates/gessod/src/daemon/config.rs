@@ -0,0 +1,386 @@
+// Author: Dustin Pilgrim
+// License: MIT
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use gesso_core::Colour;
+use gesso_ipc::protocol as ipc;
+use serde::Deserialize;
+
+//
+// config.toml
+//
+// Fallbacks for requests that leave mode, bg colour or transition unset,
+// optionally overridden per output, plus extra image search directories and
+// how much an animated WebP may take fully decoded before it streams:
+//
+//   mode       = "fill"
+//   bg         = "#101010"
+//   transition = "slide"
+//   direction  = "up"
+//   duration   = 800
+//   easing     = "ease-out-cubic"
+//   dirs       = ["~/Pictures/walls"]
+//   anim_memory_mb = 64
+//
+//   [output.DP-1]
+//   mode = "fit"
+//   bg   = "#101010"
+//
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawSection {
+    mode:       Option<String>,
+    bg:         Option<String>,
+    transition: Option<String>,
+    duration:   Option<u32>,
+    from:       Option<String>,
+    angle:      Option<f32>,
+    direction:  Option<String>,
+    at:         Option<String>,
+    softness:   Option<u16>,
+    block:      Option<u16>,
+    grain:      Option<u16>,
+    noise:      Option<String>,
+    seed:       Option<u32>,
+    easing:     Option<String>,
+    steps:      Option<u16>,
+}
+
+// The top level is a section plus the keys only it may have. Spelled out
+// rather than flattened: serde can't combine flatten with deny_unknown_fields.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawConfig {
+    mode:       Option<String>,
+    bg:         Option<String>,
+    transition: Option<String>,
+    duration:   Option<u32>,
+    from:       Option<String>,
+    angle:      Option<f32>,
+    direction:  Option<String>,
+    at:         Option<String>,
+    softness:   Option<u16>,
+    block:      Option<u16>,
+    grain:      Option<u16>,
+    noise:      Option<String>,
+    seed:       Option<u32>,
+    easing:     Option<String>,
+    steps:      Option<u16>,
+    #[serde(default)]
+    dirs:       Vec<String>,
+    anim_memory_mb: Option<u32>,
+    #[serde(default)]
+    output:     HashMap<String, RawSection>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TransitionKind {
+    None,
+    Drop,
+    Fade,
+    Wave,
+    Slide,
+    Cover,
+    Pixelate,
+    Dissolve,
+}
+
+/// One table of settings: the top level, or an `[output.NAME]` override.
+#[derive(Debug, Clone, Default)]
+pub struct Section {
+    pub mode:      Option<ipc::Mode>,
+    pub bg_colour: Option<ipc::Rgb>,
+    transition:    Option<TransitionKind>,
+    duration_ms:   Option<u32>,
+    wave_from:     Option<ipc::WaveDir>,
+    slide_dir:     Option<ipc::SlideDir>,
+    drop_origin:   Option<ipc::DropOrigin>,
+    softness_px:   Option<u16>,
+    block_px:      Option<u16>,
+    grain_px:      Option<u16>,
+    noise:         Option<ipc::DissolveNoise>,
+    seed:          Option<u32>,
+    easing:        Option<ipc::Easing>,
+    steps:         Option<u16>,
+}
+
+impl Section {
+    /// The configured transition, or None when the config doesn't name one.
+    /// Without a `duration` it's left at 0 for the daemon's default.
+    pub fn transition(&self) -> Option<ipc::Transition> {
+        let duration_ms = self.duration_ms.unwrap_or(0);
+        let steps       = self.steps.filter(|&s| s > 0);
+        let easing      = self.easing;
+        Some(match self.transition? {
+            TransitionKind::None => ipc::Transition::None,
+            TransitionKind::Drop => ipc::Transition::Drop {
+                duration_ms,
+                steps,
+                origin: self.drop_origin.unwrap_or_default(),
+                softness_px: self.softness_px,
+                easing,
+            },
+            TransitionKind::Fade => ipc::Transition::Fade {
+                duration_ms,
+                steps,
+                easing,
+            },
+            TransitionKind::Wave => ipc::Transition::Wave {
+                duration_ms,
+                dir: self.wave_from.clone().unwrap_or(ipc::WaveDir::Left),
+                steps,
+                easing,
+            },
+            TransitionKind::Slide => ipc::Transition::Slide {
+                duration_ms,
+                dir: self.slide_dir.unwrap_or(ipc::SlideDir::Left),
+                steps,
+                easing,
+            },
+            TransitionKind::Cover => ipc::Transition::Cover {
+                duration_ms,
+                dir: self.slide_dir.unwrap_or(ipc::SlideDir::Left),
+                steps,
+                easing,
+            },
+            TransitionKind::Pixelate => ipc::Transition::Pixelate {
+                duration_ms,
+                max_block_px: self.block_px,
+                steps,
+                easing,
+            },
+            TransitionKind::Dissolve => ipc::Transition::Dissolve {
+                duration_ms,
+                grain_px: self.grain_px,
+                noise: self.noise.unwrap_or_default(),
+                seed: self.seed,
+                steps,
+                easing,
+            },
+        })
+    }
+
+    /// `self` with any unset field taken from `base`.
+    fn or(&self, base: &Section) -> Section {
+        Section {
+            mode:        self.mode.or(base.mode),
+            bg_colour:   self.bg_colour.or(base.bg_colour),
+            transition:  self.transition.or(base.transition),
+            duration_ms: self.duration_ms.or(base.duration_ms),
+            wave_from:   self.wave_from.clone().or_else(|| base.wave_from.clone()),
+            slide_dir:   self.slide_dir.or(base.slide_dir),
+            drop_origin: self.drop_origin.or(base.drop_origin),
+            softness_px: self.softness_px.or(base.softness_px),
+            block_px:    self.block_px.or(base.block_px),
+            grain_px:    self.grain_px.or(base.grain_px),
+            noise:       self.noise.or(base.noise),
+            seed:        self.seed.or(base.seed),
+            easing:      self.easing.or(base.easing),
+            steps:       self.steps.or(base.steps),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    defaults: Section,
+    outputs:  HashMap<String, Section>,
+    /// Searched after GESSO_DIRS for bare image names.
+    pub dirs: Vec<PathBuf>,
+    /// Ceiling for holding every frame of an animated WebP. None = built-in.
+    pub anim_memory_mb: Option<u32>,
+}
+
+impl Config {
+    /// Read the config file. A missing file is an empty config.
+    pub fn load() -> anyhow::Result<Self> {
+        let path = config_file_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = std::fs::read_to_string(&path)?;
+        Self::parse(&text).map_err(|e| anyhow::anyhow!("{}: {e}", path.display()))
+    }
+
+    fn parse(text: &str) -> anyhow::Result<Self> {
+        let raw: RawConfig = toml::from_str(text)?;
+
+        let mut outputs = HashMap::with_capacity(raw.output.len());
+        for (name, sec) in &raw.output {
+            let sec = section(sec).map_err(|e| anyhow::anyhow!("[output.{name}]: {e}"))?;
+            outputs.insert(name.clone(), sec);
+        }
+
+        let defaults = RawSection {
+            mode:       raw.mode,
+            bg:         raw.bg,
+            transition: raw.transition,
+            duration:   raw.duration,
+            from:       raw.from,
+            angle:      raw.angle,
+            direction:  raw.direction,
+            at:         raw.at,
+            softness:   raw.softness,
+            block:      raw.block,
+            grain:      raw.grain,
+            noise:      raw.noise,
+            seed:       raw.seed,
+            easing:     raw.easing,
+            steps:      raw.steps,
+        };
+
+        Ok(Self {
+            defaults: section(&defaults)?,
+            outputs,
+            dirs: raw.dirs.iter().map(|d| expand_home(d)).collect(),
+            anim_memory_mb: raw.anim_memory_mb,
+        })
+    }
+
+    /// Settings for one output: its override table over the top-level ones.
+    pub fn for_output(&self, name: &str) -> Section {
+        match self.outputs.get(name) {
+            Some(sec) => sec.or(&self.defaults),
+            None      => self.defaults.clone(),
+        }
+    }
+
+    /// Top-level settings, for things not tied to one output (the default wallpaper).
+    pub fn defaults(&self) -> &Section {
+        &self.defaults
+    }
+}
+
+fn section(raw: &RawSection) -> anyhow::Result<Section> {
+    let mode = raw.mode.as_deref().map(parse_mode).transpose()?;
+
+    let bg_colour = raw
+        .bg
+        .as_deref()
+        .map(|s| {
+            Colour::parse(s)
+                .map(|c| ipc::Rgb { r: c.r, g: c.g, b: c.b })
+                .map_err(|e| anyhow::anyhow!("bg '{s}': {e}"))
+        })
+        .transpose()?;
+
+    let transition = match raw.transition.as_deref() {
+        None             => None,
+        Some("none")     => Some(TransitionKind::None),
+        Some("drop")     => Some(TransitionKind::Drop),
+        Some("fade")     => Some(TransitionKind::Fade),
+        Some("wave")     => Some(TransitionKind::Wave),
+        Some("slide")    => Some(TransitionKind::Slide),
+        Some("cover")    => Some(TransitionKind::Cover),
+        Some("pixelate") => Some(TransitionKind::Pixelate),
+        Some("dissolve") => Some(TransitionKind::Dissolve),
+        Some(other)      => anyhow::bail!(
+            "unknown transition '{other}' (none, drop, fade, wave, slide, cover, pixelate, dissolve)"
+        ),
+    };
+
+    let wave_from = match (raw.from.as_deref(), raw.angle) {
+        (None, None)           => None,
+        (None, Some(a)) if !a.is_finite() => anyhow::bail!("'angle' must be a number of degrees"),
+        (None, Some(a))        => Some(ipc::WaveDir::Angle(a.rem_euclid(360.0))),
+        (Some(_), Some(_))     => anyhow::bail!("set either 'from' or 'angle', not both"),
+        (Some("left"), None)   => Some(ipc::WaveDir::Left),
+        (Some("right"), None)  => Some(ipc::WaveDir::Right),
+        (Some("top"), None)    => Some(ipc::WaveDir::Top),
+        (Some("bottom"), None) => Some(ipc::WaveDir::Bottom),
+        (Some(other), None)    => {
+            anyhow::bail!("unknown wave direction '{other}' (left, right, top, bottom)")
+        }
+    };
+
+    let slide_dir = match raw.direction.as_deref() {
+        None          => None,
+        Some("up")    => Some(ipc::SlideDir::Up),
+        Some("down")  => Some(ipc::SlideDir::Down),
+        Some("left")  => Some(ipc::SlideDir::Left),
+        Some("right") => Some(ipc::SlideDir::Right),
+        Some(other)   => anyhow::bail!("unknown direction '{other}' (up, down, left, right)"),
+    };
+
+    let noise = match raw.noise.as_deref() {
+        None          => None,
+        Some("white") => Some(ipc::DissolveNoise::White),
+        Some("blue")  => Some(ipc::DissolveNoise::Blue),
+        Some(other)   => anyhow::bail!("unknown noise '{other}' (white, blue)"),
+    };
+
+    Ok(Section {
+        mode,
+        bg_colour,
+        transition,
+        duration_ms: raw.duration,
+        wave_from,
+        slide_dir,
+        drop_origin: raw.at.as_deref().map(str::parse).transpose().map_err(anyhow::Error::msg)?,
+        softness_px: raw.softness,
+        block_px: raw.block,
+        grain_px: raw.grain,
+        noise,
+        seed: raw.seed,
+        easing: raw.easing.as_deref().map(str::parse).transpose().map_err(anyhow::Error::msg)?,
+        steps: raw.steps,
+    })
+}
+
+fn parse_mode(s: &str) -> anyhow::Result<ipc::Mode> {
+    Ok(match s {
+        "fill"     => ipc::Mode::Fill,
+        "fit"      => ipc::Mode::Fit,
+        "fit-blur" => ipc::Mode::FitBlur,
+        "stretch"  => ipc::Mode::Stretch,
+        "center"   => ipc::Mode::Center,
+        "tile"     => ipc::Mode::Tile,
+        other      => anyhow::bail!("unknown mode '{other}' (fill, fit, fit-blur, stretch, center, tile)"),
+    })
+}
+
+fn expand_home(p: &str) -> PathBuf {
+    match (p.strip_prefix("~/"), std::env::var("HOME")) {
+        (Some(rest), Ok(home)) => PathBuf::from(home).join(rest),
+        _                      => PathBuf::from(p),
+    }
+}
+
+/// Compute the config file path:
+/// - `$XDG_CONFIG_HOME/gesso/config.toml`  (if XDG_CONFIG_HOME is set)
+/// - `$HOME/.config/gesso/config.toml`  (fallback)
+pub fn config_file_path() -> anyhow::Result<PathBuf> {
+    if let Ok(base) = std::env::var("XDG_CONFIG_HOME") {
+        return Ok(PathBuf::from(base).join("gesso").join("config.toml"));
+    }
+    let home = std::env::var("HOME")?;
+    Ok(PathBuf::from(home).join(".config/gesso/config.toml"))
+}
+
+//
+// SIGHUP
+//
+
+static RELOAD_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn on_sighup(_sig: libc::c_int) {
+    RELOAD_REQUESTED.store(true, Ordering::Relaxed);
+}
+
+/// Make SIGHUP ask the run loop to reload the config instead of killing us.
+pub fn install_sighup_handler() {
+    // SAFETY: the handler only stores to an atomic, which is async-signal-safe.
+    unsafe {
+        let handler: extern "C" fn(libc::c_int) = on_sighup;
+        libc::signal(libc::SIGHUP, handler as libc::sighandler_t);
+    }
+}
+
+/// True once per SIGHUP received since the last call.
+pub fn take_reload_request() -> bool {
+    RELOAD_REQUESTED.swap(false, Ordering::Relaxed)
+}
@@ -0,0 +1,144 @@
+// Author: Dustin Pilgrim
+// License: MIT
+
+use std::collections::HashMap;
+
+use gesso_core::RenderEngine;
+use gesso_ipc::EventHub;
+use gesso_ipc::protocol as ipc;
+
+use crate::daemon::transitions::ipc_transition_from_persisted;
+use crate::daemon::types::PersistedSet;
+
+/// Turns run-loop state into subscriber events by diffing it once per
+/// iteration, so the request handlers don't need to know about subscribers.
+#[derive(Default)]
+pub struct EventTracker {
+    /// width, height, scale, fractional scale in 120ths
+    outputs:       HashMap<String, (u32, u32, u32, u32)>,
+    /// target, mode, Set requests applied
+    targets:       HashMap<String, (ipc::CurrentTarget, Option<ipc::Mode>, u64)>,
+    /// engine id of the running transition
+    transitioning: HashMap<String, u64>,
+}
+
+impl EventTracker {
+    /// Start from the current state without emitting anything.
+    pub fn new(
+        outputs:  &[gesso_wl::OutputInfo],
+        current:  &HashMap<String, ipc::CurrentTarget>,
+        last_set: &HashMap<String, PersistedSet>,
+        applied:  &HashMap<String, u64>,
+        eng:      &RenderEngine,
+    ) -> Self {
+        let mut t = Self::default();
+        t.observe(&EventHub::new(), outputs, current, last_set, applied, eng);
+        t
+    }
+
+    pub fn observe(
+        &mut self,
+        hub:      &EventHub,
+        outputs:  &[gesso_wl::OutputInfo],
+        current:  &HashMap<String, ipc::CurrentTarget>,
+        last_set: &HashMap<String, PersistedSet>,
+        applied:  &HashMap<String, u64>,
+        eng:      &RenderEngine,
+    ) {
+        // ── Outputs ─────────────────────────────────────────────────────────
+        let gone: Vec<String> = self
+            .outputs
+            .keys()
+            .filter(|name| !outputs.iter().any(|o| o.name == **name))
+            .cloned()
+            .collect();
+        for name in gone {
+            self.outputs.remove(&name);
+            self.targets.remove(&name);
+            self.transitioning.remove(&name);
+            hub.broadcast(&ipc::Event::OutputRemoved { name });
+        }
+
+        for o in outputs {
+            let geom = (o.width, o.height, o.scale, (o.fractional_scale * 120.0).round() as u32);
+            match self.outputs.get_mut(&o.name) {
+                None => {
+                    self.outputs.insert(o.name.clone(), geom);
+                    hub.broadcast(&ipc::Event::OutputAdded(output_info(o)));
+                }
+                Some(g) if *g != geom => {
+                    *g = geom;
+                    hub.broadcast(&ipc::Event::OutputResized(output_info(o)));
+                }
+                Some(_) => {}
+            }
+        }
+
+        // ── Wallpaper ───────────────────────────────────────────────────────
+        let unset = ipc::CurrentTarget::Unset;
+        for o in outputs {
+            let target = current.get(&o.name).unwrap_or(&unset);
+            let mode = match target {
+                ipc::CurrentTarget::ImagePath(_) => last_set.get(&o.name).and_then(|ps| ps.mode),
+                _                                => None,
+            };
+
+            // Every applied Set counts, even one that repeats the wallpaper.
+            let sets = applied.get(&o.name).copied().unwrap_or(0);
+
+            let changed = self
+                .targets
+                .get(&o.name)
+                .is_none_or(|(t, m, n)| t != target || *m != mode || *n != sets);
+            if !changed { continue; }
+
+            let first = !self.targets.contains_key(&o.name);
+            self.targets.insert(o.name.clone(), (target.clone(), mode, sets));
+
+            // A brand-new unset output isn't a change worth announcing.
+            if first && *target == unset { continue; }
+
+            hub.broadcast(&ipc::Event::WallpaperChanged {
+                output: o.name.clone(),
+                target: target.clone(),
+                mode,
+            });
+        }
+
+        // ── Transitions ─────────────────────────────────────────────────────
+        // Compared by id, so a transition replaced mid-way by a new Set
+        // finishes before the new one starts.
+        for o in outputs {
+            let now = eng.transition_id(&o.name);
+            let was = self.transitioning.get(&o.name).copied();
+            if now == was { continue; }
+
+            if was.is_some() {
+                self.transitioning.remove(&o.name);
+                hub.broadcast(&ipc::Event::TransitionFinished { output: o.name.clone() });
+            }
+            if let Some(id) = now {
+                self.transitioning.insert(o.name.clone(), id);
+                let transition = last_set
+                    .get(&o.name)
+                    .map(|ps| ipc_transition_from_persisted(&ps.transition))
+                    .unwrap_or(ipc::Transition::None);
+                hub.broadcast(&ipc::Event::TransitionStarted {
+                    output: o.name.clone(),
+                    transition,
+                });
+            }
+        }
+    }
+}
+
+fn output_info(o: &gesso_wl::OutputInfo) -> ipc::OutputInfo {
+    ipc::OutputInfo {
+        name:   o.name.clone(),
+        width:  o.width,
+        height: o.height,
+        scale:  o.scale,
+        fractional_scale: o.fractional_scale,
+        transform: o.transform.as_str().to_owned(),
+    }
+}
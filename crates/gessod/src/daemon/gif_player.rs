@@ -4,41 +4,51 @@
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use gesso_core::decode::gif::GifFrameStream;
-use gesso_core::decode::{AnimDecoded, AnimFrame};
-use gesso_core::render::scale::scale_image_into;
+use gesso_core::decode::stream::FrameStream;
+use gesso_core::decode::{AnimDecoded, AnimFrame, DecodeOptions};
+use gesso_core::render::scale::{scale_image_view_into, BlurScratch};
 use gesso_core::mem;
-use gesso_core::{Colour, RenderEngine, ScaleMode, Target};
+use gesso_core::{Colour, DecodedImage, Framing, RenderEngine, ScaleMode, Target, Viewport};
+use gesso_ipc::protocol as ipc;
+
+use crate::daemon::motion::KenBurns;
 
 // ── Inner playback mode ───────────────────────────────────────────────────────
 
 enum PlayMode {
-    /// GIF: streaming decoder.
+    /// GIF / APNG / large animated WebP: streaming decoder.
     ///
     /// !! FIELD ORDER MATTERS: `stream` borrows `data`'s bytes via an unsafe
     /// 'static transmute. Rust drops fields in declaration order, so `stream`
     /// MUST be listed before `data` so it is dropped first.
     Streaming {
-        stream: GifFrameStream<'static>,
+        stream: FrameStream<'static>,
         data:   Arc<Vec<u8>>,
+        opts:   DecodeOptions,
     },
-    /// Animated WebP: index-based playback over pre-decoded frames.
+    /// Animated WebP under the memory limit: index-based playback over
+    /// pre-decoded frames.
     Frames {
         frames: Vec<AnimFrame>,
         index:  usize,
     },
+    /// Still image with a Ken Burns pan/zoom: every frame is the same image
+    /// re-scaled with a slowly moving framing.
+    Motion(KenBurns),
 }
 
 // ── GifPlayer ─────────────────────────────────────────────────────────────────
 
 pub struct GifPlayer {
-    mode:   PlayMode,
+    mode:    PlayMode,
 
-    out_w:  u32,
-    out_h:  u32,
-    stride: usize,
-    scale:  ScaleMode,
-    bg:     Colour,
+    out_w:   u32,
+    out_h:   u32,
+    stride:  usize,
+    view:    Viewport,
+    scale:   ScaleMode,
+    framing: Framing,
+    bg:      Colour,
 
     pub next_deadline: Instant,
 
@@ -50,6 +60,9 @@ pub struct GifPlayer {
     /// its reference the strong count falls to 1, and `get_out_buf` reclaims the
     /// allocation via `Arc::try_unwrap` — no allocation on the next tick.
     out_arc: Option<Arc<Vec<u8>>>,
+
+    /// FitBlur backdrop buffers, reused across frames.
+    blur: BlurScratch,
 }
 
 impl GifPlayer {
@@ -57,21 +70,23 @@ impl GifPlayer {
         anim:       AnimDecoded,
         out_w:      u32,
         out_h:      u32,
+        view:       Viewport,
         scale:      ScaleMode,
+        framing:    Framing,
         bg:         Colour,
         loop_count: Option<u16>,
         now:        Instant,
     ) -> Result<Self, String> {
         let (mode, first_delay): (PlayMode, Duration) = if let Some(data) = anim.data {
-            let mut stream = make_stream(&data)?;
+            let mut stream = make_stream(&data, anim.opts)?;
             // Skip frame 0 — caller already displayed it.  skip_frame composites the
             // canvas (so disposal logic is correct) but produces no pixel output.
             let delay0 = match stream.skip_frame() {
                 Some(Ok(d))  => d,
-                Some(Err(e)) => return Err(format!("gif frame 0 error: {e}")),
-                None         => return Err("gif has no frames".into()),
+                Some(Err(e)) => return Err(format!("frame 0 error: {e}")),
+                None         => return Err("animation has no frames".into()),
             };
-            (PlayMode::Streaming { stream, data }, delay0)
+            (PlayMode::Streaming { stream, data, opts: anim.opts }, delay0)
         } else {
             if anim.frames.is_empty() {
                 return Err("animated source has no frames".into());
@@ -85,17 +100,57 @@ impl GifPlayer {
             out_w,
             out_h,
             stride: out_w as usize * 4,
+            view,
             scale,
+            framing,
             bg,
             next_deadline: now + first_delay,
             loops_left: loop_count,
             out_arc: None,
+            blur: BlurScratch::default(),
         })
     }
 
+    /// Ken Burns player for a still that is already on screen (rendered with
+    /// `framing`, which becomes the first frame of the motion).
+    pub fn ken_burns(
+        img:     DecodedImage,
+        out_w:   u32,
+        out_h:   u32,
+        view:    Viewport,
+        scale:   ScaleMode,
+        framing: Framing,
+        bg:      Colour,
+        motion:  ipc::Motion,
+        now:     Instant,
+    ) -> Self {
+        let kb = KenBurns::new(img, view, framing, motion);
+        let first_delay = kb.interval();
+        Self {
+            mode: PlayMode::Motion(kb),
+            out_w,
+            out_h,
+            stride: out_w as usize * 4,
+            view,
+            scale,
+            framing,
+            bg,
+            next_deadline: now + first_delay,
+            loops_left: None,
+            out_arc: None,
+            blur: BlurScratch::default(),
+        }
+    }
+
     #[inline]
     pub fn next_deadline(&self) -> Instant { self.next_deadline }
 
+    /// The frame currently on screen, for a transition away from this player.
+    /// Taken rather than cloned so `release()` leaves its pages alone.
+    pub fn take_last_frame(&mut self) -> Option<Arc<Vec<u8>>> {
+        self.out_arc.take()
+    }
+
     // ── Teardown ────────────────────────────────────────────────────────────
 
     /// Explicitly release large allocations BEFORE this player is dropped.
@@ -110,7 +165,7 @@ impl GifPlayer {
     /// if let Some(mut p) = gifs.remove(&name) { p.release(); }
     /// ```
     pub fn release(&mut self) {
-        // 1. Release the GIF canvas (~8 MB RGBA at 1080p).
+        // 1. Release the GIF / APNG canvas (~8 MB RGBA at 1080p).
         if let PlayMode::Streaming { stream, .. } = &mut self.mode {
             stream.release_canvas();
         }
@@ -146,12 +201,13 @@ impl GifPlayer {
 
     fn restart(&mut self) -> Result<(), ()> {
         match &mut self.mode {
-            PlayMode::Streaming { data, stream } => {
-                *stream = make_stream(data).map_err(|e| {
-                    eventline::warn!("gif restart failed: {e}");
+            PlayMode::Streaming { data, stream, opts } => {
+                *stream = make_stream(data, *opts).map_err(|e| {
+                    eventline::warn!("animation restart failed: {e}");
                 })?;
             }
             PlayMode::Frames { index, .. } => { *index = 0; }
+            PlayMode::Motion(_)            => {}
         }
         Ok(())
     }
@@ -178,15 +234,25 @@ impl GifPlayer {
         if self.finished()          { return Err(()); }
         if now < self.next_deadline { return Ok(()); }
 
+        // Motion is paced by the compositor: no new frame until the last one
+        // went out. A surface that gets no frame callbacks (hidden, output
+        // off) stalls here, and the motion clock stalls with it.
+        if let PlayMode::Motion(kb) = &self.mode {
+            if eng.needs_present(output) {
+                self.next_deadline = now + kb.interval();
+                return Ok(());
+            }
+        }
+
         let mut out_buf = self.get_out_buf();
 
-        let delay = match self.next_raw_frame_into(&mut out_buf) {
+        let delay = match self.next_raw_frame_into(&mut out_buf, now) {
             FrameResult::Delay(d)  => d,
             FrameResult::EndOfStream => {
                 self.consume_one_loop();
                 if self.finished()        { return Err(()); }
                 if self.restart().is_err() { return Err(()); }
-                match self.next_raw_frame_into(&mut out_buf) {
+                match self.next_raw_frame_into(&mut out_buf, now) {
                     FrameResult::Delay(d) => d,
                     _                     => return Err(()),
                 }
@@ -214,10 +280,12 @@ impl GifPlayer {
         Ok(())
     }
 
-    fn next_raw_frame_into(&mut self, dst: &mut [u8]) -> FrameResult {
+    fn next_raw_frame_into(&mut self, dst: &mut [u8], now: Instant) -> FrameResult {
         match &mut self.mode {
             PlayMode::Streaming { stream, .. } => {
-                match stream.next_frame_scaled_into(dst, self.out_w, self.out_h, self.scale, self.bg) {
+                match stream.next_frame_scaled_into(
+                    dst, self.out_w, self.out_h, self.view, self.scale, self.framing, self.bg, &mut self.blur,
+                ) {
                     Some(Ok(d))  => FrameResult::Delay(d),
                     Some(Err(e)) => FrameResult::Error(e),
                     None         => FrameResult::EndOfStream,
@@ -229,10 +297,21 @@ impl GifPlayer {
                     return FrameResult::EndOfStream;
                 }
                 let delay = frames[*index].delay;
-                scale_image_into(&frames[*index].img, dst, self.out_w, self.out_h, self.scale, self.bg);
+                scale_image_view_into(
+                    &frames[*index].img, dst, self.out_w, self.out_h, self.view, self.scale, self.framing, self.bg,
+                    &mut self.blur,
+                );
                 *index += 1;
                 FrameResult::Delay(delay)
             }
+            PlayMode::Motion(kb) => {
+                let framing = kb.advance(now);
+                scale_image_view_into(
+                    kb.image(), dst, self.out_w, self.out_h, self.view, self.scale, framing, self.bg,
+                    &mut self.blur,
+                );
+                FrameResult::Delay(kb.interval())
+            }
         }
     }
 }
@@ -245,10 +324,10 @@ enum FrameResult {
     Error(String),
 }
 
-fn make_stream(data: &Arc<Vec<u8>>) -> Result<GifFrameStream<'static>, String> {
+fn make_stream(data: &Arc<Vec<u8>>, opts: DecodeOptions) -> Result<FrameStream<'static>, String> {
     // Safety: Arc keeps bytes alive for PlayMode::Streaming's lifetime;
     // `stream` is declared before `data` and is always dropped first.
     let slice: &'static [u8] =
         unsafe { std::mem::transmute::<&[u8], &'static [u8]>(&data[..]) };
-    GifFrameStream::new(slice)
+    FrameStream::new(slice, opts)
 }
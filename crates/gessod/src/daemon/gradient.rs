@@ -0,0 +1,55 @@
+// Author: Dustin Pilgrim
+// License: MIT
+
+use gesso_core::{render_gradient, Colour, Gradient, GradientShape, GradientStop};
+use gesso_ipc::protocol as ipc;
+
+use crate::daemon::span::viewport_for;
+use crate::daemon::types::PersistedSpan;
+
+/// Reject gradients the renderer can't make sense of.
+pub fn validate_gradient(g: &ipc::Gradient) -> Result<(), String> {
+    if g.stops.len() < 2 {
+        return Err("gradient needs at least two colour stops".into());
+    }
+    if g.stops.iter().any(|s| !s.pos.is_finite() || !(0.0..=1.0).contains(&s.pos)) {
+        return Err("gradient stop positions must be between 0 and 1".into());
+    }
+    match g.shape {
+        ipc::GradientShape::Linear { angle } if !angle.is_finite() => {
+            Err("gradient angle must be a number".into())
+        }
+        ipc::GradientShape::Radial { cx, cy, radius }
+            if !(cx.is_finite() && cy.is_finite() && radius.is_finite() && radius > 0.0) =>
+        {
+            Err("radial gradient needs a finite centre and a radius above 0".into())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Render `g` at `out`'s resolution (its slice of the canvas when spanned).
+pub fn render_for_output(
+    out:  &gesso_wl::OutputInfo,
+    g:    &ipc::Gradient,
+    span: Option<&PersistedSpan>,
+) -> Vec<u8> {
+    render_gradient(&to_core_gradient(g), out.width, out.height, viewport_for(out, span))
+}
+
+fn to_core_gradient(g: &ipc::Gradient) -> Gradient {
+    let shape = match g.shape {
+        ipc::GradientShape::Linear { angle }          => GradientShape::Linear { angle_deg: angle },
+        ipc::GradientShape::Radial { cx, cy, radius } => GradientShape::Radial { cx, cy, radius },
+    };
+    let stops = g
+        .stops
+        .iter()
+        .map(|s| GradientStop {
+            pos:    s.pos,
+            colour: Colour { r: s.colour.r, g: s.colour.g, b: s.colour.b },
+        })
+        .collect();
+
+    Gradient { shape, stops, dither: g.dither }
+}
@@ -0,0 +1,81 @@
+// Author: Dustin Pilgrim
+// License: MIT
+
+use std::collections::{HashMap, HashSet};
+
+use gesso_core::RenderEngine;
+use gesso_ipc::protocol as ipc;
+
+use crate::daemon::gif_player::GifPlayer;
+use crate::daemon::persist::save_state;
+use crate::daemon::restore::reapply_output;
+use crate::daemon::types::PersistedSet;
+
+/// Bring the engine in line with a fresh output list.
+///
+/// - New outputs and outputs whose mode changed are (re)registered and get
+///   their saved set re-rendered, or `default` when nothing is saved for them.
+/// - Vanished outputs lose their engine state and animation player; their
+///   saved set stays in `last_set` for when they come back.
+///
+/// `prev` is the list from the previous call (empty on a fresh connection).
+pub fn sync_outputs(
+    eng:      &mut RenderEngine,
+    active:   &mut HashSet<String>,
+    current:  &mut HashMap<String, ipc::CurrentTarget>,
+    last_set: &mut HashMap<String, PersistedSet>,
+    default:  Option<&PersistedSet>,
+    gifs:     &mut HashMap<String, GifPlayer>,
+    prev:     &[gesso_wl::OutputInfo],
+    outputs:  &[gesso_wl::OutputInfo],
+) {
+    for old in prev {
+        if outputs.iter().any(|o| o.name == old.name) { continue; }
+
+        eventline::info!("output removed: {}", old.name);
+        if let Some(mut p) = gifs.remove(&old.name) { p.release(); }
+        eng.remove_output(&old.name);
+        active.remove(&old.name);
+        current.remove(&old.name);
+    }
+
+    let mut adopted_default = false;
+
+    for o in outputs {
+        let changed = match prev.iter().find(|p| p.name == o.name) {
+            Some(p) => p.width != o.width || p.height != o.height,
+            None    => true,
+        };
+        if !changed { continue; }
+
+        eng.register_output(&o.name, o.width, o.height);
+        current.entry(o.name.clone()).or_insert(ipc::CurrentTarget::Unset);
+
+        // Mode not known yet (wl_output.mode hasn't arrived); we'll see it again.
+        if o.width <= 1 || o.height <= 1 { continue; }
+
+        if let Some(mut p) = gifs.remove(&o.name) { p.release(); }
+
+        let set = match last_set.get(&o.name) {
+            Some(s) => s.clone(),
+            None => {
+                let Some(d) = default else {
+                    active.remove(&o.name);
+                    continue;
+                };
+                last_set.insert(o.name.clone(), d.clone());
+                adopted_default = true;
+                d.clone()
+            }
+        };
+
+        eventline::info!("output {} is {}x{}; reapplying wallpaper", o.name, o.width, o.height);
+        if let Err(e) = reapply_output(eng, active, current, gifs, o, &set) {
+            eventline::warn!("reapply failed for {}: {e}", o.name);
+        }
+    }
+
+    if adopted_default {
+        let _ = save_state(last_set, default);
+    }
+}
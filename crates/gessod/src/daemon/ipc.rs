@@ -3,24 +3,30 @@
 
 
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Instant;
 
 use eventline::scope;
 
 use gesso_core::{
-    decode::{decode, Decoded},
-    scale_image, Colour, RenderEngine, ScaleMode, Target,
-    Transition as CoreTransition,
+    decode::{decode_with, Decoded, DecodeOptions, ANIM_MEMORY_LIMIT},
+    scale_image_view, Colour, Framing, RenderEngine, ScaleMode, Target,
+    Transition as CoreTransition, MAX_ZOOM, MIN_ZOOM,
 };
 use gesso_core::render::OldSnapshot;
 use gesso_ipc::protocol as ipc;
 use gesso_wl::WlBackend;
 
+use crate::daemon::config::{Config, Section};
 use crate::daemon::gif_player::GifPlayer;
+use crate::daemon::gradient::{render_for_output, validate_gradient};
 use crate::daemon::persist::{resolve_image_path, save_state};
 use crate::daemon::snapshot::snapshot_pixels_for_output;
+use crate::daemon::span::{span_layout, viewport_for};
 use crate::daemon::transitions::{
-    ipc_transition_from_persisted, persisted_transition_from_ipc, to_core_transition,
+    ipc_transition_from_persisted, persisted_transition_from_ipc, request_seed, to_core_transition,
+    validate_transition, with_default_duration,
 };
 use crate::daemon::types::{PersistedSet, PersistedTarget, PersistedTransition};
 
@@ -30,11 +36,14 @@ use crate::daemon::types::{PersistedSet, PersistedTarget, PersistedTransition};
 /// `release()` call the RGBA canvas (~8 MB at 1080p) and the last rendered output
 /// frame (~8 MB) stay in jemalloc's dirty-page cache until the background thread's
 /// decay timer fires — which may never happen if the daemon stays busy.
+///
+/// Returns the player's last frame so a transition can start from it.
 #[inline]
-fn remove_gif(gifs: &mut HashMap<String, GifPlayer>, name: &str) {
-    if let Some(mut p) = gifs.remove(name) {
-        p.release();
-    }
+fn remove_gif(gifs: &mut HashMap<String, GifPlayer>, name: &str) -> Option<Arc<Vec<u8>>> {
+    let mut p = gifs.remove(name)?;
+    let last = p.take_last_frame();
+    p.release();
+    last
 }
 
 pub fn handle_request(
@@ -44,7 +53,10 @@ pub fn handle_request(
     active:   &mut HashSet<String>,
     current:  &mut HashMap<String, ipc::CurrentTarget>,
     last_set: &mut HashMap<String, PersistedSet>,
+    default:  &mut Option<PersistedSet>,
     gifs:     &mut HashMap<String, GifPlayer>,
+    applied:  &mut HashMap<String, u64>,
+    config:   &Config,
     req:      ipc::Request,
     quitting: &mut bool,
 ) -> ipc::Response {
@@ -58,6 +70,8 @@ pub fn handle_request(
                         width:  o.width,
                         height: o.height,
                         scale:  o.scale,
+                        fractional_scale: o.fractional_scale,
+                        transform: o.transform.as_str().to_owned(),
                     })
                     .collect::<Vec<_>>();
                 list.sort_by(|a, b| a.name.cmp(&b.name));
@@ -69,13 +83,19 @@ pub fn handle_request(
                     .iter()
                     .map(|o| {
                         let cur = current.get(&o.name).cloned().unwrap_or(ipc::CurrentTarget::Unset);
-                        let (mode, bg_colour, transition) = match last_set.get(&o.name) {
+                        let (mode, bg_colour, transition, span, focus, zoom, offset, motion, loops) = match last_set.get(&o.name) {
                             Some(ps) => (
                                 ps.mode,
                                 ps.bg_colour,
                                 ipc_transition_from_persisted(&ps.transition),
+                                ps.span.is_some(),
+                                ps.focus,
+                                ps.zoom,
+                                ps.offset,
+                                ps.motion,
+                                ps.loops,
                             ),
-                            None => (None, None, ipc::Transition::None),
+                            None => (None, None, ipc::Transition::None, false, None, None, None, None, None),
                         };
                         ipc::OutputFullInfo {
                             name:       o.name.clone(),
@@ -86,6 +106,12 @@ pub fn handle_request(
                             mode,
                             bg_colour,
                             transition,
+                            span,
+                            focus,
+                            zoom,
+                            offset,
+                            motion,
+                            loops,
                         }
                     })
                     .collect();
@@ -110,6 +136,9 @@ pub fn handle_request(
                     has_shm:               health.has_shm,
                     has_layer_shell:       health.has_layer_shell,
                     has_xdg_output_manager: health.has_xdg_output_manager,
+                    has_single_pixel_buffer: health.has_single_pixel_buffer,
+                    has_viewporter:        health.has_viewporter,
+                    single_pixel_colours:  health.single_pixel_colours(),
                     shm_formats:           health.shm_formats,
                     warnings,
                 })
@@ -126,6 +155,36 @@ pub fn handle_request(
                 }
             }
 
+            ipc::Request::Subscribe => {
+                ipc::Response::Error {
+                    message: "internal: subscribe should be handled by the ipc server".into(),
+                }
+            }
+
+            ipc::Request::Playlist(_) | ipc::Request::Next | ipc::Request::Prev | ipc::Request::Pause => {
+                ipc::Response::Error {
+                    message: "internal: playlist requests should be handled by run loop".into(),
+                }
+            }
+
+            ipc::Request::Reload => {
+                ipc::Response::Error {
+                    message: "internal: reload should be handled by run loop".into(),
+                }
+            }
+
+            ipc::Request::Idle { .. } => {
+                ipc::Response::Error {
+                    message: "internal: idle should be handled by run loop".into(),
+                }
+            }
+
+            ipc::Request::ClearDefault => {
+                *default = None;
+                let _ = save_state(last_set, default.as_ref());
+                ipc::Response::Ok
+            }
+
             ipc::Request::Unset { outputs: sel } => {
                 let selected = match select_outputs(outputs, &sel) {
                     Ok(v)    => v,
@@ -161,12 +220,19 @@ pub fn handle_request(
                             mode:       None,
                             bg_colour:  None,
                             transition: PersistedTransition::None,
+                            span:       None,
+                            focus:      None,
+                            zoom:       None,
+                            offset:     None,
+                            motion:     None,
+                            ignore_icc: false,
+                            loops:      None,
                         },
                     );
                     let _ = wl.unset(&name);
                 }
 
-                let _ = save_state(last_set);
+                let _ = save_state(last_set, default.as_ref());
                 ipc::Response::Ok
             }
 
@@ -178,47 +244,160 @@ pub fn handle_request(
                 if selected.is_empty() {
                     return ipc::Response::Error { message: "no outputs selected".into() };
                 }
+                if let Some(Err(message)) = set.transition.as_ref().map(validate_transition) {
+                    return ipc::Response::Error { message };
+                }
+                if let Err(message) = validate_framing(&set) {
+                    return ipc::Response::Error { message };
+                }
+
+                // Used for `--default`, which isn't tied to any one output.
+                let fallback = Fallbacks::resolve(&set, config.defaults());
+                let seed     = request_seed();
 
-                let tr_ipc     = set.transition.clone();
-                let tr_core    = to_core_transition(tr_ipc.clone());
-                let tr_persist = persisted_transition_from_ipc(tr_ipc);
+                let spans = if set.span { span_layout(outputs, &selected) } else { HashMap::new() };
 
                 match set.target {
                     ipc::SetTarget::Colour(rgb) => {
                         let col = Colour { r: rgb.r, g: rgb.g, b: rgb.b };
 
-                        for name in selected {
-                            let Some(outinfo) = outputs.iter().find(|o| o.name == name) else { continue };
+                        for name in &selected {
+                            let Some(outinfo) = outputs.iter().find(|o| o.name == *name) else { continue };
+                            let span    = spans.get(name).copied();
+                            let fb      = Fallbacks::resolve(&set, &config.for_output(name));
+                            let tr_core = to_core_transition(fb.transition.clone(), seed);
 
                             // !! release() before the new target takes over.
-                            remove_gif(gifs, &name);
+                            let playing = remove_gif(gifs, name);
 
                             if matches!(tr_core, CoreTransition::None) {
-                                let _ = eng.set_now(&name, Target::Colour(col));
+                                let _ = eng.set_now(name, Target::Colour(col));
                             } else {
-                                let from = snapshot_pixels_for_output(outinfo, last_set.get(&name));
-                                let _ = eng.set_with_transition_from(
-                                    &name,
+                                let from = snapshot_pixels_for_output(outinfo, last_set.get(name), playing);
+                                let _ = eng.set_with_transition_view(
+                                    name,
                                     OldSnapshot::Image(from),
                                     Target::Colour(col),
                                     tr_core.clone(),
+                                    viewport_for(outinfo, span.as_ref()),
                                 );
                             }
 
                             active.insert(name.clone());
                             current.insert(name.clone(), ipc::CurrentTarget::Colour(rgb));
+                            *applied.entry(name.clone()).or_default() += 1;
                             last_set.insert(
                                 name.clone(),
                                 PersistedSet {
                                     target:     PersistedTarget::Colour { r: rgb.r, g: rgb.g, b: rgb.b },
                                     mode:       None,
                                     bg_colour:  None,
-                                    transition: tr_persist.clone(),
+                                    transition: persisted_transition_from_ipc(fb.transition.clone()),
+                                    span,
+                                    focus:      None,
+                                    zoom:       None,
+                                    offset:     None,
+                                    motion:     None,
+                                    ignore_icc: false,
+                                    loops:      None,
                                 },
                             );
                         }
 
-                        let _ = save_state(last_set);
+                        eng.sync_transitions(&selected);
+                        if set.default {
+                            *default = Some(PersistedSet {
+                                target:     PersistedTarget::Colour { r: rgb.r, g: rgb.g, b: rgb.b },
+                                mode:       None,
+                                bg_colour:  None,
+                                transition: persisted_transition_from_ipc(fallback.transition.clone()),
+                                span:       None,
+                                focus:      None,
+                                zoom:       None,
+                                offset:     None,
+                                motion:     None,
+                                ignore_icc: false,
+                                loops:      None,
+                            });
+                        }
+                        let _ = save_state(last_set, default.as_ref());
+                        ipc::Response::Ok
+                    }
+
+                    ipc::SetTarget::Gradient(ref grad) => {
+                        if let Err(message) = validate_gradient(grad) {
+                            return ipc::Response::Error { message };
+                        }
+
+                        for name in &selected {
+                            let Some(outinfo) = outputs.iter().find(|o| o.name == *name) else { continue };
+                            let span    = spans.get(name).copied();
+                            let view    = viewport_for(outinfo, span.as_ref());
+                            let fb      = Fallbacks::resolve(&set, &config.for_output(name));
+                            let tr_core = to_core_transition(fb.transition.clone(), seed);
+
+                            // !! release() before the new target takes over.
+                            let playing = remove_gif(gifs, name);
+
+                            let pixels = render_for_output(outinfo, grad, span.as_ref());
+                            let target = Target::image(
+                                outinfo.width,
+                                outinfo.height,
+                                outinfo.width as usize * 4,
+                                pixels,
+                            );
+
+                            if matches!(tr_core, CoreTransition::None) {
+                                let _ = eng.set_now(name, target);
+                            } else {
+                                let from = snapshot_pixels_for_output(outinfo, last_set.get(name), playing);
+                                let _ = eng.set_with_transition_view(
+                                    name,
+                                    OldSnapshot::Image(from),
+                                    target,
+                                    tr_core.clone(),
+                                    view,
+                                );
+                            }
+
+                            active.insert(name.clone());
+                            current.insert(name.clone(), ipc::CurrentTarget::Gradient(grad.clone()));
+                            *applied.entry(name.clone()).or_default() += 1;
+                            last_set.insert(
+                                name.clone(),
+                                PersistedSet {
+                                    target:     PersistedTarget::Gradient(grad.clone()),
+                                    mode:       None,
+                                    bg_colour:  None,
+                                    transition: persisted_transition_from_ipc(fb.transition.clone()),
+                                    span,
+                                    focus:      None,
+                                    zoom:       None,
+                                    offset:     None,
+                                    motion:     None,
+                                    ignore_icc: false,
+                                    loops:      None,
+                                },
+                            );
+                        }
+
+                        eng.sync_transitions(&selected);
+                        if set.default {
+                            *default = Some(PersistedSet {
+                                target:     PersistedTarget::Gradient(grad.clone()),
+                                mode:       None,
+                                bg_colour:  None,
+                                transition: persisted_transition_from_ipc(fallback.transition.clone()),
+                                span:       None,
+                                focus:      None,
+                                zoom:       None,
+                                offset:     None,
+                                motion:     None,
+                                ignore_icc: false,
+                                loops:      None,
+                            });
+                        }
+                        let _ = save_state(last_set, default.as_ref());
                         ipc::Response::Ok
                     }
 
@@ -231,30 +410,37 @@ pub fn handle_request(
 
                         let canonical = resolved.to_string_lossy().into_owned();
 
-                        for name in selected {
-                            let Some(outinfo) = outputs.iter().find(|o| o.name == name) else { continue };
+                        for name in &selected {
+                            let Some(outinfo) = outputs.iter().find(|o| o.name == *name) else { continue };
+                            let span    = spans.get(name).copied();
+                            let view    = viewport_for(outinfo, span.as_ref());
+                            let fb      = Fallbacks::resolve(&set, &config.for_output(name));
+                            let tr_core = to_core_transition(fb.transition.clone(), seed);
 
-                            let decoded = match decode(&resolved) {
+                            let decoded = match decode_with(&resolved, to_decode_options(set.ignore_icc)) {
                                 Ok(d)  => d,
                                 Err(e) => return ipc::Response::Error {
                                     message: format!("decode failed: {e}"),
                                 },
                             };
 
-                            let bg     = set.bg_colour.unwrap_or(ipc::Rgb { r: 0, g: 0, b: 0 });
-                            let bg_col = Colour { r: bg.r, g: bg.g, b: bg.b };
-                            let scale  = to_scale_mode(set.mode);
+                            let bg      = fb.bg_colour.unwrap_or(ipc::Rgb { r: 0, g: 0, b: 0 });
+                            let bg_col  = Colour { r: bg.r, g: bg.g, b: bg.b };
+                            let scale   = to_scale_mode(fb.mode);
+                            let framing = to_framing(set.focus, set.zoom, set.offset);
 
                             match decoded {
                                 Decoded::Still(img) => {
                                     // !! release() before we show the new still.
-                                    remove_gif(gifs, &name);
+                                    let playing = remove_gif(gifs, name);
 
-                                    let pixels = scale_image(
+                                    let pixels = scale_image_view(
                                         &img,
                                         outinfo.width,
                                         outinfo.height,
+                                        view,
                                         scale,
+                                        framing,
                                         bg_col,
                                     );
                                     let target = Target::image(
@@ -265,26 +451,51 @@ pub fn handle_request(
                                     );
 
                                     if matches!(tr_core, CoreTransition::None) {
-                                        let _ = eng.set_now(&name, target);
+                                        let _ = eng.set_now(name, target);
                                     } else {
-                                        let from = snapshot_pixels_for_output(outinfo, last_set.get(&name));
-                                        let _ = eng.set_with_transition_from(
-                                            &name,
+                                        let from = snapshot_pixels_for_output(outinfo, last_set.get(name), playing);
+                                        let _ = eng.set_with_transition_view(
+                                            name,
                                             OldSnapshot::Image(from),
                                             target,
                                             tr_core.clone(),
+                                            view,
                                         );
                                     }
 
+                                    // Ken Burns only makes sense where there is overhang to pan over.
+                                    if let (Some(m), ScaleMode::Fill) = (set.motion, scale) {
+                                        let player = GifPlayer::ken_burns(
+                                            img,
+                                            outinfo.width,
+                                            outinfo.height,
+                                            view,
+                                            scale,
+                                            framing,
+                                            bg_col,
+                                            m,
+                                            Instant::now(),
+                                        );
+                                        gifs.insert(name.clone(), player);
+                                    }
+
                                     active.insert(name.clone());
                                     current.insert(name.clone(), ipc::CurrentTarget::ImagePath(canonical.clone()));
+                                    *applied.entry(name.clone()).or_default() += 1;
                                     last_set.insert(
                                         name.clone(),
                                         PersistedSet {
                                             target:     PersistedTarget::ImagePath { path: canonical.clone() },
-                                            mode:       Some(set.mode),
-                                            bg_colour:  set.bg_colour,
-                                            transition: tr_persist.clone(),
+                                            mode:       Some(fb.mode),
+                                            bg_colour:  fb.bg_colour,
+                                            transition: persisted_transition_from_ipc(fb.transition.clone()),
+                                            span,
+                                            focus:      set.focus,
+                                            zoom:       set.zoom,
+                                            offset:     set.offset,
+                                            motion:     set.motion,
+                                            ignore_icc: set.ignore_icc,
+                                            loops:      None,
                                         },
                                     );
                                 }
@@ -293,13 +504,15 @@ pub fn handle_request(
                                     // !! release() the OLD player before installing the new one.
                                     // This is the critical path: GIF → GIF replacement also
                                     // needs the old canvas released.
-                                    remove_gif(gifs, &name);
+                                    let playing = remove_gif(gifs, name);
 
-                                    let pixels = scale_image(
+                                    let pixels = scale_image_view(
                                         &anim.first_frame,
                                         outinfo.width,
                                         outinfo.height,
+                                        view,
                                         scale,
+                                        framing,
                                         bg_col,
                                     );
                                     let target0 = Target::image(
@@ -310,24 +523,27 @@ pub fn handle_request(
                                     );
 
                                     if matches!(tr_core, CoreTransition::None) {
-                                        let _ = eng.set_now(&name, target0);
+                                        let _ = eng.set_now(name, target0);
                                     } else {
-                                        let from = snapshot_pixels_for_output(outinfo, last_set.get(&name));
-                                        let _ = eng.set_with_transition_from(
-                                            &name,
+                                        let from = snapshot_pixels_for_output(outinfo, last_set.get(name), playing);
+                                        let _ = eng.set_with_transition_view(
+                                            name,
                                             OldSnapshot::Image(from),
                                             target0,
                                             tr_core.clone(),
+                                            view,
                                         );
                                     }
 
-                                    let loop_count = anim.loop_count;
+                                    let loop_count = to_loop_count(set.loops, anim.loop_count);
                                     let now = Instant::now();
                                     match GifPlayer::new(
                                         anim,
                                         outinfo.width,
                                         outinfo.height,
+                                        view,
                                         scale,
+                                        framing,
                                         bg_col,
                                         loop_count,
                                         now,
@@ -340,27 +556,51 @@ pub fn handle_request(
 
                                     active.insert(name.clone());
                                     current.insert(name.clone(), ipc::CurrentTarget::ImagePath(canonical.clone()));
+                                    *applied.entry(name.clone()).or_default() += 1;
                                     last_set.insert(
                                         name.clone(),
                                         PersistedSet {
                                             target:     PersistedTarget::ImagePath { path: canonical.clone() },
-                                            mode:       Some(set.mode),
-                                            bg_colour:  set.bg_colour,
-                                            transition: tr_persist.clone(),
+                                            mode:       Some(fb.mode),
+                                            bg_colour:  fb.bg_colour,
+                                            transition: persisted_transition_from_ipc(fb.transition.clone()),
+                                            span,
+                                            focus:      set.focus,
+                                            zoom:       set.zoom,
+                                            offset:     set.offset,
+                                            motion:     None,
+                                            ignore_icc: set.ignore_icc,
+                                            loops:      set.loops,
                                         },
                                     );
                                 }
                             }
                         }
 
-                        let _ = save_state(last_set);
+                        eng.sync_transitions(&selected);
+                        if set.default {
+                            *default = Some(PersistedSet {
+                                target:     PersistedTarget::ImagePath { path: canonical.clone() },
+                                mode:       Some(fallback.mode),
+                                bg_colour:  fallback.bg_colour,
+                                transition: persisted_transition_from_ipc(fallback.transition.clone()),
+                                span:       None,
+                                focus:      set.focus,
+                                zoom:       set.zoom,
+                                offset:     set.offset,
+                                motion:     set.motion,
+                                ignore_icc: set.ignore_icc,
+                                loops:      set.loops,
+                            });
+                        }
+                        let _ = save_state(last_set, default.as_ref());
                         ipc::Response::Ok
                     }
 
                     ipc::SetTarget::Unset => {
                         let sel = ipc::OutputSel::Named(selected);
                         handle_request(
-                            eng, wl, outputs, active, current, last_set, gifs,
+                            eng, wl, outputs, active, current, last_set, default, gifs, applied, config,
                             ipc::Request::Unset { outputs: sel },
                             quitting,
                         )
@@ -371,16 +611,103 @@ pub fn handle_request(
     })
 }
 
+/// A Set's mode, bg colour and transition with config.toml filled in where
+/// the request left them open.
+struct Fallbacks {
+    mode:       ipc::Mode,
+    bg_colour:  Option<ipc::Rgb>,
+    transition: ipc::Transition,
+}
+
+impl Fallbacks {
+    fn resolve(set: &ipc::SetRequest, cfg: &Section) -> Self {
+        let mode       = set.mode.or(cfg.mode).unwrap_or(ipc::Mode::Fill);
+        let image      = matches!(set.target, ipc::SetTarget::ImagePath(_));
+        let transition = set
+            .transition
+            .clone()
+            .or_else(|| cfg.transition())
+            .unwrap_or(ipc::Transition::None);
+        Self {
+            mode,
+            bg_colour:  set.bg_colour.or(cfg.bg_colour),
+            transition: with_default_duration(transition, image.then_some(mode)),
+        }
+    }
+}
+
 pub fn to_scale_mode(m: ipc::Mode) -> ScaleMode {
     match m {
         ipc::Mode::Fill    => ScaleMode::Fill,
         ipc::Mode::Fit     => ScaleMode::Fit,
+        ipc::Mode::FitBlur => ScaleMode::FitBlur,
         ipc::Mode::Stretch => ScaleMode::Stretch,
         ipc::Mode::Center  => ScaleMode::Center,
         ipc::Mode::Tile    => ScaleMode::Tile,
     }
 }
 
+/// `anim_memory_mb` from config.toml, in bytes.
+static ANIM_MEMORY: AtomicUsize = AtomicUsize::new(ANIM_MEMORY_LIMIT);
+
+/// Replace the animated-WebP memory ceiling (on config load and reload).
+pub fn set_anim_memory_limit(mb: Option<u32>) {
+    let bytes = mb.map_or(ANIM_MEMORY_LIMIT, |mb| (mb as usize) << 20);
+    ANIM_MEMORY.store(bytes, Ordering::Relaxed);
+}
+
+pub fn to_decode_options(ignore_icc: bool) -> DecodeOptions {
+    DecodeOptions {
+        colour_manage:     !ignore_icc,
+        anim_memory_limit: ANIM_MEMORY.load(Ordering::Relaxed),
+    }
+}
+
+/// Plays for `GifPlayer`: the request's override, else the file's own count.
+pub fn to_loop_count(loops: Option<ipc::Loop>, file: Option<u16>) -> Option<u16> {
+    match loops {
+        Some(ipc::Loop::Forever)  => None,
+        Some(ipc::Loop::Times(n)) => Some(n.max(1)),
+        None                      => file,
+    }
+}
+
+/// Reject focus points and zooms the renderer can't make sense of; NaN would
+/// otherwise blank the output and end up as null in state.json.
+fn validate_framing(set: &ipc::SetRequest) -> Result<(), String> {
+    let unit = |v: f32| (0.0..=1.0).contains(&v);
+    if set.focus.is_some_and(|f| !(unit(f.x) && unit(f.y))) {
+        return Err("focus must be between 0 and 1 on both axes".into());
+    }
+    if set.zoom.is_some_and(|z| !(MIN_ZOOM..=MAX_ZOOM).contains(&z)) {
+        return Err(format!("zoom must be between {MIN_ZOOM} and {MAX_ZOOM}"));
+    }
+    if set.motion.is_some_and(|m| !unit(m.zoom)) {
+        return Err("motion zoom must be between 0 and 1".into());
+    }
+    Ok(())
+}
+
+pub fn to_framing(
+    focus:  Option<ipc::Focus>,
+    zoom:   Option<f32>,
+    offset: Option<ipc::Offset>,
+) -> Framing {
+    let mut framing = Framing::default();
+    if let Some(f) = focus {
+        framing.focus_x = f.x;
+        framing.focus_y = f.y;
+    }
+    if let Some(z) = zoom {
+        framing.zoom = z;
+    }
+    if let Some(o) = offset {
+        framing.offset_x = o.dx;
+        framing.offset_y = o.dy;
+    }
+    framing
+}
+
 pub fn select_outputs(
     outputs: &[gesso_wl::OutputInfo],
     sel:     &ipc::OutputSel,
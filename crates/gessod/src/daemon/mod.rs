@@ -1,13 +1,20 @@
 // Author: Dustin Pilgrim
 // License: MIT
 
+pub mod config;
+pub mod events;
 pub mod gif_player;
+pub mod gradient;
+pub mod hotplug;
 pub mod ipc;
 pub mod instance_lock;
+pub mod motion;
 pub mod persist;
+pub mod playlist;
 pub mod restore;
 pub mod run;
 pub mod snapshot;
+pub mod span;
 pub mod transitions;
 pub mod types;
 pub mod wayland;
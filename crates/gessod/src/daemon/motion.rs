@@ -0,0 +1,108 @@
+// Author: Dustin Pilgrim
+// License: MIT
+
+use std::time::{Duration, Instant};
+
+use gesso_core::{scale_image, Colour, DecodedImage, Framing, ScaleMode, Viewport};
+use gesso_ipc::protocol as ipc;
+
+/// How far (as a fraction of the image) the focus drifts over one pass.
+const PAN: f32 = 0.25;
+
+//
+// Ken Burns
+//
+
+/// Slow pan/zoom over a still. Holds the image (pre-shrunk to what the
+/// deepest zoom needs) and a motion clock that only runs while frames are
+/// actually going out, so a stalled or idle output resumes where it stopped.
+pub struct KenBurns {
+    img:      DecodedImage,
+    from:     Framing,
+    to:       Framing,
+    period:   Duration,
+    interval: Duration,
+    elapsed:  Duration,
+    last:     Option<Instant>,
+}
+
+impl KenBurns {
+    /// `base` is the still's own framing, so the first frame matches what the
+    /// plain still showed.
+    pub fn new(img: DecodedImage, view: Viewport, base: Framing, m: ipc::Motion) -> Self {
+        let to = Framing {
+            focus_x: drift(base.focus_x),
+            focus_y: drift(base.focus_y),
+            zoom:    base.zoom * (1.0 + m.zoom.clamp(0.0, 1.0)),
+            ..base
+        };
+        let img = shrink_for(img, view, base.zoom.max(to.zoom));
+
+        Self {
+            img,
+            from:     base,
+            to,
+            period:   Duration::from_secs(m.period_secs.max(1) as u64),
+            interval: Duration::from_secs(1) / m.fps.clamp(1, 50) as u32,
+            elapsed:  Duration::ZERO,
+            last:     None,
+        }
+    }
+
+    #[inline]
+    pub fn image(&self) -> &DecodedImage { &self.img }
+
+    #[inline]
+    pub fn interval(&self) -> Duration { self.interval }
+
+    /// Framing for a frame shown at `now`. The clock moves by at most two
+    /// frame intervals per call, so gaps (idle, no frame callbacks) don't jump.
+    pub fn advance(&mut self, now: Instant) -> Framing {
+        if let Some(last) = self.last {
+            self.elapsed += now.saturating_duration_since(last).min(self.interval * 2);
+        }
+        self.last = Some(now);
+
+        // Ping-pong: even passes drift out, odd passes drift back.
+        let pass = self.elapsed.as_secs_f64() / self.period.as_secs_f64();
+        let mut p = pass.fract() as f32;
+        if pass as u64 % 2 == 1 { p = 1.0 - p; }
+        let p = p * p * (3.0 - 2.0 * p);
+
+        Framing {
+            focus_x:  lerp(self.from.focus_x, self.to.focus_x, p),
+            focus_y:  lerp(self.from.focus_y, self.to.focus_y, p),
+            zoom:     lerp(self.from.zoom, self.to.zoom, p),
+            offset_x: self.from.offset_x,
+            offset_y: self.from.offset_y,
+        }
+    }
+}
+
+// ── Helpers ───────────────────────────────────────────────────────────────────
+
+/// Drift toward the side with more room.
+#[inline]
+fn drift(f: f32) -> f32 {
+    if f <= 0.5 { (f + PAN).min(1.0) } else { (f - PAN).max(0.0) }
+}
+
+#[inline]
+fn lerp(a: f32, b: f32, t: f32) -> f32 { a + (b - a) * t }
+
+/// Downscale once to the size Fill reaches at `max_zoom` on the canvas, so
+/// a 24 MP photo isn't held (and resampled every frame) at full size.
+fn shrink_for(img: DecodedImage, view: Viewport, max_zoom: f32) -> DecodedImage {
+    if img.width == 0 || img.height == 0 { return img; }
+
+    let cover = f32::max(
+        view.canvas_width  as f32 / img.width  as f32,
+        view.canvas_height as f32 / img.height as f32,
+    ) * max_zoom;
+    if cover >= 1.0 { return img; }
+
+    let w = ((img.width  as f32 * cover).ceil() as u32).clamp(1, img.width);
+    let h = ((img.height as f32 * cover).ceil() as u32).clamp(1, img.height);
+    let pixels = scale_image(&img, w, h, ScaleMode::Stretch, Framing::default(), Colour { r: 0, g: 0, b: 0 });
+    DecodedImage { width: w, height: h, stride: w as usize * 4, pixels }
+}
@@ -3,17 +3,16 @@
 
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::RwLock;
 
 use gesso_core::paths::gesso_dirs_from_env;
 
-use crate::daemon::types::{PersistedOutput, PersistedSet, PersistedState};
-
-pub fn save_state(last_set: &std::collections::HashMap<String, PersistedSet>) -> anyhow::Result<()> {
-    let path = state_file_path()?;
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent)?;
-    }
+use crate::daemon::types::{PersistedOutput, PersistedPlaylist, PersistedSet, PersistedState};
 
+pub fn save_state(
+    last_set: &std::collections::HashMap<String, PersistedSet>,
+    default:  Option<&PersistedSet>,
+) -> anyhow::Result<()> {
     let mut outputs: Vec<PersistedOutput> = last_set
         .iter()
         .map(|(name, set)| PersistedOutput {
@@ -23,7 +22,26 @@ pub fn save_state(last_set: &std::collections::HashMap<String, PersistedSet>) ->
         .collect();
     outputs.sort_by(|a, b| a.name.cmp(&b.name));
 
-    let bytes = serde_json::to_vec_pretty(&PersistedState { outputs })?;
+    // Other sections (playlist) are owned elsewhere; keep whatever is on disk.
+    let mut st = load_state().ok().flatten().unwrap_or_default();
+    st.outputs = outputs;
+    st.default = default.cloned();
+    write_state(&st)
+}
+
+/// Update only the playlist section of state.json.
+pub fn save_playlist(playlist: Option<&PersistedPlaylist>) -> anyhow::Result<()> {
+    let mut st = load_state().ok().flatten().unwrap_or_default();
+    st.playlist = playlist.cloned();
+    write_state(&st)
+}
+
+fn write_state(st: &PersistedState) -> anyhow::Result<()> {
+    let path = state_file_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let bytes = serde_json::to_vec_pretty(st)?;
     fs::write(path, bytes)?;
     Ok(())
 }
@@ -48,12 +66,23 @@ pub fn state_file_path() -> anyhow::Result<PathBuf> {
     Ok(PathBuf::from(home).join(".local/state/gesso/state.json"))
 }
 
+/// Search directories from config.toml, consulted after GESSO_DIRS.
+static CONFIG_DIRS: RwLock<Vec<PathBuf>> = RwLock::new(Vec::new());
+
+/// Replace the config.toml search directories (on load and reload).
+pub fn set_config_dirs(dirs: Vec<PathBuf>) {
+    if let Ok(mut d) = CONFIG_DIRS.write() {
+        *d = dirs;
+    }
+}
+
 pub fn resolve_image_path(path: &str) -> Option<PathBuf> {
     let p = Path::new(path);
     if p.exists() {
         return Some(p.to_path_buf());
     }
-    for dir in gesso_dirs_from_env() {
+    let config_dirs = CONFIG_DIRS.read().map(|d| d.clone()).unwrap_or_default();
+    for dir in gesso_dirs_from_env().into_iter().chain(config_dirs) {
         let c = dir.join(path);
         if c.exists() {
             return Some(c);
@@ -0,0 +1,335 @@
+// Author: Dustin Pilgrim
+// License: MIT
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use gesso_core::RenderEngine;
+use gesso_ipc::protocol as ipc;
+use gesso_wl::WlBackend;
+
+use crate::daemon::config::Config;
+use crate::daemon::gif_player::GifPlayer;
+use crate::daemon::ipc::handle_request;
+use crate::daemon::persist::resolve_image_path;
+use crate::daemon::transitions::{
+    ipc_transition_from_persisted, persisted_transition_from_ipc, validate_transition,
+};
+use crate::daemon::types::{PersistedPlaylist, PersistedSet};
+
+/// Extensions picked up when a playlist source is a directory.
+const IMAGE_EXTS: &[&str] = &["png", "apng", "jpg", "jpeg", "gif", "webp"];
+
+/// Slideshow state. Owned by the run loop; at most one is active.
+pub struct Playlist {
+    outputs:    ipc::OutputSel,
+    sources:    Vec<String>,
+    items:      Vec<String>,
+    /// Play order for the current round (indexes into `items`).
+    sequence:   Vec<usize>,
+    pos:        usize,
+    interval:   Duration,
+    order:      ipc::PlaylistOrder,
+    /// None = config.toml per output, resolved on every change.
+    mode:       Option<ipc::Mode>,
+    bg_colour:  Option<ipc::Rgb>,
+    transition: Option<ipc::Transition>,
+    span:       bool,
+    rng:        u64,
+
+    /// Some = paused, holding the time that was left on the clock.
+    paused: Option<Duration>,
+
+    next_deadline: Instant,
+}
+
+impl Playlist {
+    pub fn start(req: ipc::PlaylistRequest, now: Instant) -> Result<Self, String> {
+        if req.interval_secs == 0 {
+            return Err("playlist interval must be at least 1 second".into());
+        }
+
+        if let Some(tr) = &req.transition {
+            validate_transition(tr)?;
+        }
+
+        let items = expand_sources(&req.sources);
+        if items.is_empty() {
+            return Err("playlist is empty (no supported images found)".into());
+        }
+
+        let mut pl = Self {
+            outputs:    req.outputs,
+            sources:    req.sources,
+            items,
+            sequence:   Vec::new(),
+            pos:        0,
+            interval:   Duration::from_secs(req.interval_secs as u64),
+            order:      req.order,
+            mode:       req.mode,
+            bg_colour:  req.bg_colour,
+            transition: req.transition,
+            span:       req.span,
+            rng:        seed_from_clock(),
+            paused:     None,
+            next_deadline: now,
+        };
+        pl.new_round(None);
+        pl.rearm(now);
+        Ok(pl)
+    }
+
+    /// Resume a saved playlist. The current image is already on screen via
+    /// the normal per-output restore, so the clock simply starts over.
+    pub fn from_persisted(p: PersistedPlaylist, now: Instant) -> Option<Self> {
+        if p.items.is_empty() || p.interval_secs == 0 {
+            return None;
+        }
+
+        let mut pl = Self {
+            outputs:    p.outputs,
+            sources:    p.sources,
+            items:      p.items,
+            sequence:   p.sequence,
+            pos:        p.pos,
+            interval:   Duration::from_secs(p.interval_secs as u64),
+            order:      p.order,
+            mode:       p.mode,
+            bg_colour:  p.bg_colour,
+            transition: p.transition.as_ref().map(ipc_transition_from_persisted),
+            span:       p.span,
+            rng:        seed_from_clock(),
+            paused:     None,
+            next_deadline: now,
+        };
+
+        let n = pl.items.len();
+        if pl.sequence.is_empty() || pl.sequence.iter().any(|&i| i >= n) {
+            pl.new_round(None);
+        }
+        if pl.pos >= pl.sequence.len() {
+            pl.pos = 0;
+        }
+
+        pl.rearm(now);
+        if p.paused {
+            pl.paused = Some(pl.interval);
+        }
+        Some(pl)
+    }
+
+    pub fn to_persisted(&self) -> PersistedPlaylist {
+        PersistedPlaylist {
+            outputs:       self.outputs.clone(),
+            sources:       self.sources.clone(),
+            items:         self.items.clone(),
+            sequence:      self.sequence.clone(),
+            pos:           self.pos,
+            interval_secs: self.interval.as_secs() as u32,
+            order:         self.order,
+            mode:          self.mode,
+            bg_colour:     self.bg_colour,
+            transition:    self.transition.clone().map(persisted_transition_from_ipc),
+            span:          self.span,
+            paused:        self.paused.is_some(),
+        }
+    }
+
+    // ── Timer ───────────────────────────────────────────────────────────────
+
+    /// When the next change is due, or None while paused.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        match self.paused {
+            Some(_) => None,
+            None    => Some(self.next_deadline),
+        }
+    }
+
+    #[inline]
+    pub fn due(&self, now: Instant) -> bool {
+        self.paused.is_none() && now >= self.next_deadline
+    }
+
+    pub fn rearm(&mut self, now: Instant) {
+        self.next_deadline = now + self.interval;
+    }
+
+    /// Pause, or resume with whatever time was left. Returns the new paused state.
+    pub fn toggle_pause(&mut self, now: Instant) -> bool {
+        match self.paused.take() {
+            Some(left) => {
+                self.next_deadline = now + left;
+                false
+            }
+            None => {
+                self.paused = Some(self.next_deadline.saturating_duration_since(now));
+                true
+            }
+        }
+    }
+
+    // ── Position ────────────────────────────────────────────────────────────
+
+    pub fn current(&self) -> &str {
+        let idx = self.sequence.get(self.pos).copied().unwrap_or(0);
+        &self.items[idx]
+    }
+
+    /// Move one step forward or back. Running off the end starts a new round
+    /// (directories are rescanned so new files are picked up); going back
+    /// from the first image wraps within the current round.
+    pub fn step(&mut self, forward: bool) {
+        if forward {
+            if self.pos + 1 < self.sequence.len() {
+                self.pos += 1;
+            } else {
+                let last = self.sequence.get(self.pos).copied();
+                let fresh = expand_sources(&self.sources);
+                if !fresh.is_empty() && fresh != self.items {
+                    self.items = fresh;
+                    self.new_round(None);
+                } else {
+                    self.new_round(last);
+                }
+                self.pos = 0;
+            }
+        } else if self.pos > 0 {
+            self.pos -= 1;
+        } else {
+            self.pos = self.sequence.len().saturating_sub(1);
+        }
+    }
+
+    /// True if a request for `sel` takes over any of the playlist's outputs.
+    pub fn overlaps(&self, sel: &ipc::OutputSel) -> bool {
+        match (&self.outputs, sel) {
+            (ipc::OutputSel::All, _) | (_, ipc::OutputSel::All) => true,
+            (ipc::OutputSel::Named(a), ipc::OutputSel::Named(b)) => a.iter().any(|n| b.contains(n)),
+        }
+    }
+
+    /// The Set request that shows the current image.
+    pub fn set_request(&self) -> ipc::Request {
+        ipc::Request::Set(ipc::SetRequest {
+            outputs:    self.outputs.clone(),
+            target:     ipc::SetTarget::ImagePath(self.current().to_owned()),
+            mode:       self.mode,
+            bg_colour:  self.bg_colour,
+            transition: self.transition.clone(),
+            span:       self.span,
+            default:    false,
+            focus:      None,
+            zoom:       None,
+            offset:     None,
+            motion:     None,
+            ignore_icc: false,
+            loops:      None,
+        })
+    }
+
+    // ── Ordering ────────────────────────────────────────────────────────────
+
+    /// Build the play order for a new round. `avoid_first` keeps a shuffled
+    /// round from opening with the image that closed the previous one.
+    fn new_round(&mut self, avoid_first: Option<usize>) {
+        let n = self.items.len();
+        self.sequence = match self.order {
+            ipc::PlaylistOrder::Sequential => (0..n).collect(),
+            ipc::PlaylistOrder::Shuffle => {
+                (0..n).map(|_| (self.next_rand() % n as u64) as usize).collect()
+            }
+            ipc::PlaylistOrder::ShuffleNoRepeat => {
+                let mut seq: Vec<usize> = (0..n).collect();
+                for i in (1..n).rev() {
+                    let j = (self.next_rand() % (i as u64 + 1)) as usize;
+                    seq.swap(i, j);
+                }
+                seq
+            }
+        };
+
+        if n > 1
+            && self.order != ipc::PlaylistOrder::Sequential
+            && avoid_first.is_some_and(|a| self.sequence[0] == a)
+        {
+            let j = 1 + (self.next_rand() % (n as u64 - 1)) as usize;
+            self.sequence.swap(0, j);
+        }
+    }
+
+    /// splitmix64 — plenty for picking wallpapers.
+    fn next_rand(&mut self) -> u64 {
+        self.rng = self.rng.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.rng;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+/// Show the playlist's current image on its outputs.
+pub fn show_current(
+    pl:       &Playlist,
+    eng:      &mut RenderEngine,
+    wl:       &mut WlBackend,
+    outputs:  &[gesso_wl::OutputInfo],
+    active:   &mut HashSet<String>,
+    current:  &mut HashMap<String, ipc::CurrentTarget>,
+    last_set: &mut HashMap<String, PersistedSet>,
+    default:  &mut Option<PersistedSet>,
+    gifs:     &mut HashMap<String, GifPlayer>,
+    applied:  &mut HashMap<String, u64>,
+    config:   &Config,
+) -> ipc::Response {
+    // A Set never asks the daemon to quit.
+    let mut quitting = false;
+    handle_request(
+        eng, wl, outputs, active, current, last_set, default, gifs, applied, config,
+        pl.set_request(), &mut quitting,
+    )
+}
+
+/// Expand files and directories into a sorted image list.
+fn expand_sources(sources: &[String]) -> Vec<String> {
+    let mut items = Vec::new();
+
+    for src in sources {
+        let Some(path) = resolve_image_path(src) else {
+            eventline::warn!("playlist: source not found: {src}");
+            continue;
+        };
+
+        if path.is_dir() {
+            let Ok(rd) = std::fs::read_dir(&path) else {
+                eventline::warn!("playlist: cannot read directory {}", path.display());
+                continue;
+            };
+            let mut found: Vec<String> = rd
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .filter(|p| p.is_file() && has_image_ext(p))
+                .map(|p| p.to_string_lossy().into_owned())
+                .collect();
+            found.sort();
+            items.extend(found);
+        } else {
+            items.push(path.to_string_lossy().into_owned());
+        }
+    }
+
+    items
+}
+
+fn has_image_ext(p: &Path) -> bool {
+    p.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| IMAGE_EXTS.iter().any(|x| e.eq_ignore_ascii_case(x)))
+        .unwrap_or(false)
+}
+
+fn seed_from_clock() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0x2545_F491_4F6C_DD1D)
+}
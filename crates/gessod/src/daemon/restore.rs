@@ -2,17 +2,20 @@
 // License: MIT
 
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use std::time::Instant;
 
-use gesso_core::{scale_image, Colour, RenderEngine, Target};
-use gesso_core::decode::{decode, Decoded};
+use gesso_core::{scale_image_view, Colour, RenderEngine, ScaleMode, Target, Transition as CoreTransition};
+use gesso_core::decode::{decode_with, Decoded};
 use gesso_core::render::OldSnapshot;
 use gesso_ipc::protocol as ipc;
 
 use crate::daemon::gif_player::GifPlayer;
-use crate::daemon::ipc::to_scale_mode;
+use crate::daemon::gradient::render_for_output;
+use crate::daemon::ipc::{to_decode_options, to_framing, to_loop_count, to_scale_mode};
 use crate::daemon::persist::resolve_image_path;
 use crate::daemon::snapshot::snapshot_pixels_for_output;
+use crate::daemon::span::viewport_for;
 use crate::daemon::transitions::to_core_transition_persisted;
 use crate::daemon::types::{PersistedSet, PersistedState, PersistedTarget};
 
@@ -25,6 +28,8 @@ pub fn apply_persisted_state(
     outputs:  &[gesso_wl::OutputInfo],
     st:       PersistedState,
 ) -> anyhow::Result<()> {
+    let mut spanned = Vec::new();
+
     for po in st.outputs {
         let Some(out) = outputs.iter().find(|o| o.name == po.name) else {
             last_set.insert(po.name.clone(), po.set.clone());
@@ -32,148 +37,233 @@ pub fn apply_persisted_state(
         };
 
         let prev = last_set.get(&po.name).cloned();
-        let from = snapshot_pixels_for_output(out, prev.as_ref());
+        let playing = gifs.get_mut(&po.name).and_then(GifPlayer::take_last_frame);
+        let from    = snapshot_pixels_for_output(out, prev.as_ref(), playing);
+        let tr   = to_core_transition_persisted(&po.set.transition);
+
+        apply_set(eng, active, current, gifs, out, &po.set, from, tr)?;
+
+        if po.set.span.is_some() {
+            spanned.push(po.name.clone());
+        }
+        last_set.insert(po.name.clone(), po.set.clone());
+    }
+
+    // Spanned outputs must sweep as one.
+    eng.sync_transitions(&spanned);
+
+    Ok(())
+}
+
+/// Re-render a saved set on an output that just appeared or changed size.
+///
+/// Instant (no transition): there is nothing meaningful on screen to
+/// transition from, and the compositor is usually still settling the layout.
+pub fn reapply_output(
+    eng:     &mut RenderEngine,
+    active:  &mut HashSet<String>,
+    current: &mut HashMap<String, ipc::CurrentTarget>,
+    gifs:    &mut HashMap<String, GifPlayer>,
+    out:     &gesso_wl::OutputInfo,
+    set:     &PersistedSet,
+) -> anyhow::Result<()> {
+    let from = Arc::new(Vec::new());
+    apply_set(eng, active, current, gifs, out, set, from, CoreTransition::None)
+}
 
-        match &po.set.target {
-            PersistedTarget::Unset => {
-                gifs.remove(&po.name);
-                current.insert(po.name.clone(), ipc::CurrentTarget::Unset);
-                active.remove(&po.name);
+fn apply_set(
+    eng:     &mut RenderEngine,
+    active:  &mut HashSet<String>,
+    current: &mut HashMap<String, ipc::CurrentTarget>,
+    gifs:    &mut HashMap<String, GifPlayer>,
+    out:     &gesso_wl::OutputInfo,
+    set:     &PersistedSet,
+    from:    Arc<Vec<u8>>,
+    tr:      CoreTransition,
+) -> anyhow::Result<()> {
+    let name = &out.name;
+    let view = viewport_for(out, set.span.as_ref());
+
+    match &set.target {
+        PersistedTarget::Unset => {
+            gifs.remove(name);
+            current.insert(name.clone(), ipc::CurrentTarget::Unset);
+            active.remove(name);
+        }
+
+        PersistedTarget::Colour { r, g, b } => {
+            gifs.remove(name);
+
+            let col = Colour { r: *r, g: *g, b: *b };
+
+            if matches!(tr, CoreTransition::None) {
+                eng.set_now(name, Target::Colour(col))?;
+            } else {
+                eng.set_with_transition_view(
+                    name,
+                    OldSnapshot::Image(from),
+                    Target::Colour(col),
+                    tr,
+                    view,
+                )?;
             }
 
-            PersistedTarget::Colour { r, g, b } => {
-                gifs.remove(&po.name);
-
-                let col = Colour { r: *r, g: *g, b: *b };
-                let tr  = to_core_transition_persisted(&po.set.transition);
-
-                if matches!(tr, gesso_core::Transition::None) {
-                    eng.set_now(&po.name, Target::Colour(col))?;
-                } else {
-                    eng.set_with_transition_from(
-                        &po.name,
-                        OldSnapshot::Image(from),
-                        Target::Colour(col),
-                        tr,
-                    )?;
-                }
+            active.insert(name.clone());
+            current.insert(
+                name.clone(),
+                ipc::CurrentTarget::Colour(ipc::Rgb { r: *r, g: *g, b: *b }),
+            );
+        }
+
+        PersistedTarget::Gradient(g) => {
+            gifs.remove(name);
+
+            let pixels = render_for_output(out, g, set.span.as_ref());
+            let target = Target::image(out.width, out.height, out.width as usize * 4, pixels);
 
-                active.insert(po.name.clone());
-                current.insert(
-                    po.name.clone(),
-                    ipc::CurrentTarget::Colour(ipc::Rgb { r: *r, g: *g, b: *b }),
-                );
+            if matches!(tr, CoreTransition::None) {
+                eng.set_now(name, target)?;
+            } else {
+                eng.set_with_transition_view(
+                    name,
+                    OldSnapshot::Image(from),
+                    target,
+                    tr,
+                    view,
+                )?;
             }
 
-            PersistedTarget::ImagePath { path } => {
-                let Some(resolved) = resolve_image_path(path) else {
-                    gifs.remove(&po.name);
-                    current.insert(po.name.clone(), ipc::CurrentTarget::Unset);
-                    active.remove(&po.name);
-                    last_set.insert(po.name.clone(), po.set.clone());
-                    continue;
-                };
-
-                let mode   = po.set.mode.unwrap_or(ipc::Mode::Fill);
-                let bg     = po.set.bg_colour.unwrap_or(ipc::Rgb { r: 0, g: 0, b: 0 });
-                let bg_col = Colour { r: bg.r, g: bg.g, b: bg.b };
-                let scale  = to_scale_mode(mode);
-                let tr     = to_core_transition_persisted(&po.set.transition);
-
-                let decoded = decode(&resolved)
-                    .map_err(|e| anyhow::anyhow!("decode failed: {e}"))?;
-
-                gifs.remove(&po.name);
-
-                match decoded {
-                    Decoded::Still(img) => {
-                        let pixels = scale_image(&img, out.width, out.height, scale, bg_col);
-                        let target = Target::image(
-                            out.width,
-                            out.height,
-                            out.width as usize * 4,
-                            pixels,
-                        );
+            active.insert(name.clone());
+            current.insert(name.clone(), ipc::CurrentTarget::Gradient(g.clone()));
+        }
 
-                        if matches!(tr, gesso_core::Transition::None) {
-                            eng.set_now(&po.name, target)?;
-                        } else {
-                            eng.set_with_transition_from(
-                                &po.name,
-                                OldSnapshot::Image(from),
-                                target,
-                                tr,
-                            )?;
-                        }
+        PersistedTarget::ImagePath { path } => {
+            let Some(resolved) = resolve_image_path(path) else {
+                gifs.remove(name);
+                current.insert(name.clone(), ipc::CurrentTarget::Unset);
+                active.remove(name);
+                return Ok(());
+            };
 
-                        active.insert(po.name.clone());
-                        current.insert(
-                            po.name.clone(),
-                            ipc::CurrentTarget::ImagePath(path.clone()),
-                        );
+            let mode    = set.mode.unwrap_or(ipc::Mode::Fill);
+            let bg      = set.bg_colour.unwrap_or(ipc::Rgb { r: 0, g: 0, b: 0 });
+            let bg_col  = Colour { r: bg.r, g: bg.g, b: bg.b };
+            let scale   = to_scale_mode(mode);
+            let framing = to_framing(set.focus, set.zoom, set.offset);
+
+            let decoded = decode_with(&resolved, to_decode_options(set.ignore_icc))
+                .map_err(|e| anyhow::anyhow!("decode failed: {e}"))?;
+
+            gifs.remove(name);
+
+            match decoded {
+                Decoded::Still(img) => {
+                    let pixels = scale_image_view(&img, out.width, out.height, view, scale, framing, bg_col);
+                    let target = Target::image(
+                        out.width,
+                        out.height,
+                        out.width as usize * 4,
+                        pixels,
+                    );
+
+                    if matches!(tr, CoreTransition::None) {
+                        eng.set_now(name, target)?;
+                    } else {
+                        eng.set_with_transition_view(
+                            name,
+                            OldSnapshot::Image(from),
+                            target,
+                            tr,
+                            view,
+                        )?;
                     }
 
-                    Decoded::Animated(anim) => {
-                        // Show first frame with transition if persisted.
-                        let pixels = scale_image(
-                            &anim.first_frame,
+                    if let (Some(m), ScaleMode::Fill) = (set.motion, scale) {
+                        let player = GifPlayer::ken_burns(
+                            img,
                             out.width,
                             out.height,
+                            view,
                             scale,
+                            framing,
                             bg_col,
+                            m,
+                            Instant::now(),
                         );
-                        let target0 = Target::image(
-                            out.width,
-                            out.height,
-                            out.width as usize * 4,
-                            pixels,
-                        );
+                        gifs.insert(name.clone(), player);
+                    }
 
-                        if matches!(tr, gesso_core::Transition::None) {
-                            eng.set_now(&po.name, target0)?;
-                        } else {
-                            eng.set_with_transition_from(
-                                &po.name,
-                                OldSnapshot::Image(from),
-                                target0,
-                                tr,
-                            )?;
-                        }
+                    active.insert(name.clone());
+                    current.insert(
+                        name.clone(),
+                        ipc::CurrentTarget::ImagePath(path.clone()),
+                    );
+                }
 
-                        // Install the player. run loop skips tick() while
-                        // is_transitioning() so frames won't race the intro.
-                        let now = Instant::now();
-                        match GifPlayer::new(
-                            anim,
-                            out.width,
-                            out.height,
-                            scale,
-                            bg_col,
-                            None, // loop forever
-                            now,
-                        ) {
-                            Ok(player) => {
-                                gifs.insert(po.name.clone(), player);
-                            }
-                            Err(e) => {
-                                eventline::warn!(
-                                    "restore: animation player init failed for {}: {e}",
-                                    po.name
-                                );
-                            }
-                        }
+                Decoded::Animated(anim) => {
+                    // Show first frame with transition if persisted.
+                    let pixels = scale_image_view(
+                        &anim.first_frame,
+                        out.width,
+                        out.height,
+                        view,
+                        scale,
+                        framing,
+                        bg_col,
+                    );
+                    let target0 = Target::image(
+                        out.width,
+                        out.height,
+                        out.width as usize * 4,
+                        pixels,
+                    );
 
-                        active.insert(po.name.clone());
-                        current.insert(
-                            po.name.clone(),
-                            ipc::CurrentTarget::ImagePath(path.clone()),
-                        );
+                    if matches!(tr, CoreTransition::None) {
+                        eng.set_now(name, target0)?;
+                    } else {
+                        eng.set_with_transition_view(
+                            name,
+                            OldSnapshot::Image(from),
+                            target0,
+                            tr,
+                            view,
+                        )?;
                     }
+
+                    // Install the player. run loop skips tick() while
+                    // is_transitioning() so frames won't race the intro.
+                    let loop_count = to_loop_count(set.loops, anim.loop_count);
+                    let now = Instant::now();
+                    match GifPlayer::new(
+                        anim,
+                        out.width,
+                        out.height,
+                        view,
+                        scale,
+                        framing,
+                        bg_col,
+                        loop_count,
+                        now,
+                    ) {
+                        Ok(player) => {
+                            gifs.insert(name.clone(), player);
+                        }
+                        Err(e) => {
+                            eventline::warn!(
+                                "restore: animation player init failed for {}: {e}",
+                                name
+                            );
+                        }
+                    }
+
+                    active.insert(name.clone());
+                    current.insert(
+                        name.clone(),
+                        ipc::CurrentTarget::ImagePath(path.clone()),
+                    );
                 }
             }
         }
-
-        last_set.insert(po.name.clone(), po.set.clone());
     }
 
     Ok(())
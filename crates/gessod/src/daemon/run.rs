@@ -8,12 +8,17 @@ use std::time::{Duration, Instant};
 use eventline::{info, scope};
 
 use gesso_core::RenderEngine;
+use gesso_ipc::EventHub;
 use gesso_ipc::protocol as ipc;
 use gesso_wl::WlBackend;
 
+use crate::daemon::config::{install_sighup_handler, take_reload_request, Config};
+use crate::daemon::events::EventTracker;
 use crate::daemon::gif_player::GifPlayer;
-use crate::daemon::ipc::handle_request;
-use crate::daemon::persist::load_state;
+use crate::daemon::hotplug::sync_outputs;
+use crate::daemon::ipc::{handle_request, set_anim_memory_limit};
+use crate::daemon::persist::{load_state, save_playlist, set_config_dirs};
+use crate::daemon::playlist::{show_current, Playlist};
 use crate::daemon::restore::apply_persisted_state;
 use crate::daemon::types::PersistedSet;
 use crate::daemon::wayland::{wayland_socket_alive, wayland_socket_path};
@@ -36,7 +41,11 @@ fn wait_for_named_outputs(wl: &mut WlBackend) -> anyhow::Result<Vec<gesso_wl::Ou
     Ok(outs)
 }
 
-pub fn run(rx: mpsc::Receiver<ipc::Request>, tx: mpsc::Sender<ipc::Response>) -> anyhow::Result<()> {
+pub fn run(
+    rx:  mpsc::Receiver<ipc::Request>,
+    tx:  mpsc::Sender<ipc::Response>,
+    hub: EventHub,
+) -> anyhow::Result<()> {
     scope!("gessod.run", {
         info!("starting gessod");
 
@@ -48,6 +57,9 @@ pub fn run(rx: mpsc::Receiver<ipc::Request>, tx: mpsc::Sender<ipc::Response>) ->
             }
         };
 
+        let mut config = load_config();
+        install_sighup_handler();
+
         let mut wl = WlBackend::connect()?;
         wl.roundtrip()?;
 
@@ -66,21 +78,47 @@ pub fn run(rx: mpsc::Receiver<ipc::Request>, tx: mpsc::Sender<ipc::Response>) ->
         let mut active:   HashSet<String>                     = HashSet::new();
         let mut current:  HashMap<String, ipc::CurrentTarget> = HashMap::new();
         let mut last_set: HashMap<String, PersistedSet>        = HashMap::new();
+        let mut default:  Option<PersistedSet>                 = None;
         let mut gifs:     HashMap<String, GifPlayer>           = HashMap::new();
+        let mut playlist: Option<Playlist>                     = None;
+        // Set requests applied per output, so a repeat of the same wallpaper
+        // still reaches event subscribers.
+        let mut applied:  HashMap<String, u64>                 = HashMap::new();
 
         for o in &outputs {
             current.insert(o.name.clone(), ipc::CurrentTarget::Unset);
         }
 
-        if let Ok(Some(persist)) = load_state() {
+        if let Ok(Some(mut persist)) = load_state() {
             info!("restoring persisted state");
+            default  = persist.default.take();
+            playlist = persist
+                .playlist
+                .take()
+                .and_then(|p| Playlist::from_persisted(p, Instant::now()));
             apply_persisted_state(
                 &mut eng, &mut active, &mut current, &mut last_set, &mut gifs,
                 &outputs, persist,
             )?;
         }
 
+        // Outputs with nothing saved count as new: they pick up the default.
+        {
+            let known: Vec<gesso_wl::OutputInfo> = outputs
+                .iter()
+                .filter(|o| last_set.contains_key(&o.name))
+                .cloned()
+                .collect();
+            sync_outputs(
+                &mut eng, &mut active, &mut current, &mut last_set, default.as_ref(),
+                &mut gifs, &known, &outputs,
+            );
+        }
+
+        let mut events   = EventTracker::new(&outputs, &current, &last_set, &applied, &eng);
         let mut quitting = false;
+        // Set by `gesso idle`: animation players hold their frame while true.
+        let mut idle     = false;
 
         loop {
             if !wayland_socket_alive(&wl_sock) {
@@ -88,6 +126,13 @@ pub fn run(rx: mpsc::Receiver<ipc::Request>, tx: mpsc::Sender<ipc::Response>) ->
                 break;
             }
 
+            if take_reload_request() {
+                match reload_config(&mut config) {
+                    Ok(())  => info!("config reloaded (SIGHUP)"),
+                    Err(e)  => eventline::warn!("config: {e:#}; keeping previous settings"),
+                }
+            }
+
             // ── Wayland dispatch ──────────────────────────────────────────────
             if let Err(e) = wl.dispatch() {
                 eventline::warn!("wl.dispatch failed: {e:#}; reconnecting");
@@ -99,27 +144,35 @@ pub fn run(rx: mpsc::Receiver<ipc::Request>, tx: mpsc::Sender<ipc::Response>) ->
 
                 wl = WlBackend::connect()?;
                 wl.roundtrip()?;
+                let prev = std::mem::take(&mut outputs);
                 outputs = wait_for_named_outputs(&mut wl)?;
                 if outputs.is_empty() {
                     eventline::warn!("reconnected but still no named outputs.");
                 }
-                for o in &outputs {
-                    eng.register_output(&o.name, o.width, o.height);
-                    current.entry(o.name.clone()).or_insert(ipc::CurrentTarget::Unset);
-                }
+                // Fresh surfaces: everything needs to be drawn again, so only
+                // the outputs that went away are passed as previous.
+                let gone: Vec<_> = prev
+                    .into_iter()
+                    .filter(|p| !outputs.iter().any(|o| o.name == p.name))
+                    .collect();
+                sync_outputs(
+                    &mut eng, &mut active, &mut current, &mut last_set, default.as_ref(),
+                    &mut gifs, &gone, &outputs,
+                );
             } else {
+                let prev = std::mem::take(&mut outputs);
                 outputs = wl.outputs();
                 if outputs.is_empty() {
                     outputs = wait_for_named_outputs(&mut wl)?;
                 }
-                for o in &outputs {
-                    eng.register_output(&o.name, o.width, o.height);
-                    current.entry(o.name.clone()).or_insert(ipc::CurrentTarget::Unset);
-                }
+                sync_outputs(
+                    &mut eng, &mut active, &mut current, &mut last_set, default.as_ref(),
+                    &mut gifs, &prev, &outputs,
+                );
             }
 
             // ── Tick animation players ────────────────────────────────────────
-            {
+            if !idle {
                 let now = Instant::now();
                 let mut finished: Vec<String> = Vec::new();
 
@@ -135,25 +188,39 @@ pub fn run(rx: mpsc::Receiver<ipc::Request>, tx: mpsc::Sender<ipc::Response>) ->
 
                 for name in finished {
                     gifs.remove(&name);
+                    hub.broadcast(&ipc::Event::AnimationFinished { output: name });
+                }
+            }
+
+            // ── Playlist ──────────────────────────────────────────────────────
+            if let Some(pl) = playlist.as_mut() {
+                let now = Instant::now();
+                if pl.due(now) {
+                    pl.step(true);
+                    pl.rearm(now);
+                    if let ipc::Response::Error { message } = show_current(
+                        pl, &mut eng, &mut wl, &outputs,
+                        &mut active, &mut current, &mut last_set, &mut default, &mut gifs,
+                        &mut applied, &config,
+                    ) {
+                        eventline::warn!("playlist: {message}");
+                    }
+                    let _ = save_playlist(Some(&pl.to_persisted()));
                 }
             }
 
             // ── Drain IPC ─────────────────────────────────────────────────────
             while let Ok(req) = rx.try_recv() {
-                let resp = match req {
-                    ipc::Request::Restore => handle_restore(
-                        &mut eng, &mut active, &mut current, &mut last_set,
-                        &mut gifs, &outputs,
-                    ),
-                    other => handle_request(
-                        &mut eng, &mut wl, &outputs,
-                        &mut active, &mut current, &mut last_set, &mut gifs,
-                        other, &mut quitting,
-                    ),
-                };
+                let resp = dispatch(
+                    &mut eng, &mut wl, &outputs,
+                    &mut active, &mut current, &mut last_set, &mut default, &mut gifs,
+                    &mut applied, &mut playlist, &mut config, req, &mut quitting, &mut idle,
+                );
                 let _ = tx.send(resp);
             }
 
+            events.observe(&hub, &outputs, &current, &last_set, &applied, &eng);
+
             if quitting { break; }
 
             // ── Present ───────────────────────────────────────────────────────
@@ -164,11 +231,26 @@ pub fn run(rx: mpsc::Receiver<ipc::Request>, tx: mpsc::Sender<ipc::Response>) ->
 
             if any_needs_present {
                 let mut any_presented = false;
+                let single_pixel = wl.supports_single_pixel_colours();
 
                 for o in &outputs {
                     if !active.contains(&o.name) || !eng.needs_present(&o.name) {
                         continue;
                     }
+
+                    // Solid colours skip SHM entirely when the compositor can
+                    // scale a single-pixel buffer for us.
+                    if single_pixel {
+                        if let Some(c) = eng.solid_colour(&o.name) {
+                            let presented = wl.present_colour(&o.name, c.r, c.g, c.b)?;
+                            if presented {
+                                eng.mark_presented(&o.name);
+                                any_presented = true;
+                            }
+                            continue;
+                        }
+                    }
+
                     let presented = wl.present_rendered(&o.name, o.width, o.height, |dst| {
                         eng.render_output_into(&o.name, dst);
                         Ok(())
@@ -205,30 +287,28 @@ pub fn run(rx: mpsc::Receiver<ipc::Request>, tx: mpsc::Sender<ipc::Response>) ->
                 }
             }
 
-            // Sleep until next IPC or animation deadline.
+            // Sleep until next IPC, animation, or playlist deadline.
             let now = Instant::now();
             let mut timeout = Duration::from_millis(250);
             for o in &outputs {
-                if !active.contains(&o.name) { continue; }
+                if idle || !active.contains(&o.name) { continue; }
                 if let Some(p) = gifs.get(&o.name) {
                     let dt = p.next_deadline().saturating_duration_since(now);
                     if dt < timeout { timeout = dt; }
                 }
             }
+            if let Some(deadline) = playlist.as_ref().and_then(|p| p.next_deadline()) {
+                let dt = deadline.saturating_duration_since(now);
+                if dt < timeout { timeout = dt; }
+            }
 
             match rx.recv_timeout(timeout) {
                 Ok(req) => {
-                    let resp = match req {
-                        ipc::Request::Restore => handle_restore(
-                            &mut eng, &mut active, &mut current, &mut last_set,
-                            &mut gifs, &outputs,
-                        ),
-                        other => handle_request(
-                            &mut eng, &mut wl, &outputs,
-                            &mut active, &mut current, &mut last_set, &mut gifs,
-                            other, &mut quitting,
-                        ),
-                    };
+                    let resp = dispatch(
+                        &mut eng, &mut wl, &outputs,
+                        &mut active, &mut current, &mut last_set, &mut default, &mut gifs,
+                        &mut applied, &mut playlist, &mut config, req, &mut quitting, &mut idle,
+                    );
                     let _ = tx.send(resp);
                 }
                 Err(mpsc::RecvTimeoutError::Timeout) => {
@@ -241,20 +321,123 @@ pub fn run(rx: mpsc::Receiver<ipc::Request>, tx: mpsc::Sender<ipc::Response>) ->
             }
         }
 
+        hub.broadcast(&ipc::Event::Stopping);
         Ok(())
     })
 }
 
+/// Route one IPC request. Restore and playlist control live here because
+/// they need run-loop state that `handle_request` doesn't see.
+fn dispatch(
+    eng:      &mut RenderEngine,
+    wl:       &mut WlBackend,
+    outputs:  &[gesso_wl::OutputInfo],
+    active:   &mut HashSet<String>,
+    current:  &mut HashMap<String, ipc::CurrentTarget>,
+    last_set: &mut HashMap<String, PersistedSet>,
+    default:  &mut Option<PersistedSet>,
+    gifs:     &mut HashMap<String, GifPlayer>,
+    applied:  &mut HashMap<String, u64>,
+    playlist: &mut Option<Playlist>,
+    config:   &mut Config,
+    req:      ipc::Request,
+    quitting: &mut bool,
+    idle:     &mut bool,
+) -> ipc::Response {
+    match req {
+        ipc::Request::Restore => handle_restore(
+            eng, active, current, last_set, default, gifs, outputs,
+        ),
+
+        ipc::Request::Playlist(pr) => {
+            let pl = match Playlist::start(pr, Instant::now()) {
+                Ok(p)    => p,
+                Err(msg) => return ipc::Response::Error { message: msg },
+            };
+            let resp = show_current(
+                &pl, eng, wl, outputs, active, current, last_set, default, gifs, applied, config,
+            );
+            if matches!(resp, ipc::Response::Ok) {
+                let _ = save_playlist(Some(&pl.to_persisted()));
+                *playlist = Some(pl);
+            }
+            resp
+        }
+
+        ipc::Request::Next | ipc::Request::Prev => {
+            let Some(pl) = playlist.as_mut() else {
+                return ipc::Response::Error { message: "no playlist running".into() };
+            };
+            pl.step(matches!(req, ipc::Request::Next));
+            pl.rearm(Instant::now());
+            let resp = show_current(
+                pl, eng, wl, outputs, active, current, last_set, default, gifs, applied, config,
+            );
+            let _ = save_playlist(Some(&pl.to_persisted()));
+            resp
+        }
+
+        ipc::Request::Reload => match reload_config(config) {
+            Ok(()) => {
+                info!("config reloaded");
+                ipc::Response::Ok
+            }
+            Err(e) => ipc::Response::Error { message: format!("config: {e:#}") },
+        },
+
+        ipc::Request::Idle { idle: now_idle } => {
+            if *idle != now_idle {
+                info!("animations {}", if now_idle { "held (idle)" } else { "resumed" });
+            }
+            *idle = now_idle;
+            ipc::Response::Ok
+        }
+
+        ipc::Request::Pause => {
+            let Some(pl) = playlist.as_mut() else {
+                return ipc::Response::Error { message: "no playlist running".into() };
+            };
+            let paused = pl.toggle_pause(Instant::now());
+            info!("playlist {}", if paused { "paused" } else { "resumed" });
+            let _ = save_playlist(Some(&pl.to_persisted()));
+            ipc::Response::Ok
+        }
+
+        other => {
+            // A manual set/unset on any playlist output ends the slideshow.
+            let sel = match &other {
+                ipc::Request::Set(s)                => Some(&s.outputs),
+                ipc::Request::Unset { outputs: sel } => Some(sel),
+                _                                    => None,
+            };
+            if let (Some(sel), Some(pl)) = (sel, playlist.as_ref()) {
+                if pl.overlaps(sel) {
+                    info!("playlist stopped by manual change");
+                    *playlist = None;
+                    let _ = save_playlist(None);
+                }
+            }
+
+            handle_request(
+                eng, wl, outputs, active, current, last_set, default, gifs, applied, config,
+                other, quitting,
+            )
+        }
+    }
+}
+
 fn handle_restore(
     eng:      &mut RenderEngine,
     active:   &mut HashSet<String>,
     current:  &mut HashMap<String, ipc::CurrentTarget>,
     last_set: &mut HashMap<String, PersistedSet>,
+    default:  &mut Option<PersistedSet>,
     gifs:     &mut HashMap<String, GifPlayer>,
     outputs:  &[gesso_wl::OutputInfo],
 ) -> ipc::Response {
     match load_state() {
-        Ok(Some(persist)) => {
+        Ok(Some(mut persist)) => {
+            *default = persist.default.take();
             match apply_persisted_state(eng, active, current, last_set, gifs, outputs, persist) {
                 Ok(())  => ipc::Response::Ok,
                 Err(e)  => ipc::Response::Error { message: format!("restore failed: {e}") },
@@ -263,3 +446,28 @@ fn handle_restore(
         _ => ipc::Response::Error { message: "no persisted state".into() },
     }
 }
+
+/// Load config.toml for startup or SIGHUP. Errors are logged and the daemon
+/// carries on with built-in defaults.
+fn load_config() -> Config {
+    let config = match Config::load() {
+        Ok(c)  => c,
+        Err(e) => {
+            eventline::warn!("config: {e:#}; using defaults");
+            Config::default()
+        }
+    };
+    set_config_dirs(config.dirs.clone());
+    set_anim_memory_limit(config.anim_memory_mb);
+    config
+}
+
+/// Swap in a freshly read config.toml. On error the old one stays in place,
+/// so a typo doesn't silently drop every setting.
+fn reload_config(config: &mut Config) -> anyhow::Result<()> {
+    let fresh = Config::load()?;
+    set_config_dirs(fresh.dirs.clone());
+    set_anim_memory_limit(fresh.anim_memory_mb);
+    *config = fresh;
+    Ok(())
+}
@@ -3,23 +3,35 @@
 
 use std::sync::Arc;
 
-use gesso_core::{decode_image, scale_image, Colour, ScaleMode};
+use gesso_core::{decode_image_with, scale_image_view, Colour, ScaleMode};
 use gesso_ipc::protocol as ipc;
 
+use crate::daemon::gradient::render_for_output;
+use crate::daemon::ipc::{to_decode_options, to_framing};
 use crate::daemon::persist::resolve_image_path;
+use crate::daemon::span::viewport_for;
 use crate::daemon::types::{PersistedSet, PersistedTarget};
 
 /// New “pixels snapshot” function used by transitions.
 /// Returns an xrgb8888 buffer sized exactly to the output (width*height*4).
+///
+/// `playing` is the last frame of an animation or Ken Burns player on this
+/// output; when present it is used as-is, since re-rendering `prev` would
+/// jump back to the first frame or the base framing.
 pub fn snapshot_pixels_for_output(
     out: &gesso_wl::OutputInfo,
     prev: Option<&PersistedSet>,
+    playing: Option<Arc<Vec<u8>>>,
 ) -> Arc<Vec<u8>> {
     let w = out.width;
     let h = out.height;
     let stride = w as usize * 4;
     let nbytes = stride * h as usize;
 
+    if let Some(frame) = playing.filter(|f| f.len() == nbytes) {
+        return frame;
+    }
+
     let Some(ps) = prev else {
         return Arc::new(vec![0u8; nbytes]);
     };
@@ -37,12 +49,14 @@ pub fn snapshot_pixels_for_output(
             Arc::new(bytes)
         }
 
+        PersistedTarget::Gradient(g) => Arc::new(render_for_output(out, g, ps.span.as_ref())),
+
         PersistedTarget::ImagePath { path } => {
             let Some(resolved) = resolve_image_path(path) else {
                 return Arc::new(vec![0u8; nbytes]);
             };
 
-            let decoded = match decode_image(&resolved) {
+            let decoded = match decode_image_with(&resolved, to_decode_options(ps.ignore_icc)) {
                 Ok(d) => d,
                 Err(_) => return Arc::new(vec![0u8; nbytes]),
             };
@@ -50,11 +64,13 @@ pub fn snapshot_pixels_for_output(
             let mode = ps.mode.unwrap_or(ipc::Mode::Fill);
             let bg = ps.bg_colour.unwrap_or(ipc::Rgb { r: 0, g: 0, b: 0 });
 
-            let pixels = scale_image(
+            let pixels = scale_image_view(
                 &decoded,
                 w,
                 h,
+                viewport_for(out, ps.span.as_ref()),
                 to_scale_mode(mode),
+                to_framing(ps.focus, ps.zoom, ps.offset),
                 Colour { r: bg.r, g: bg.g, b: bg.b },
             );
 
@@ -67,6 +83,7 @@ fn to_scale_mode(m: ipc::Mode) -> ScaleMode {
     match m {
         ipc::Mode::Fill => ScaleMode::Fill,
         ipc::Mode::Fit => ScaleMode::Fit,
+        ipc::Mode::FitBlur => ScaleMode::FitBlur,
         ipc::Mode::Stretch => ScaleMode::Stretch,
         ipc::Mode::Center => ScaleMode::Center,
         ipc::Mode::Tile => ScaleMode::Tile,
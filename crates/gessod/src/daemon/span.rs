@@ -0,0 +1,75 @@
+// Author: Dustin Pilgrim
+// License: MIT
+
+//
+// Span mode: one image stretched across several outputs.
+//
+// The virtual canvas is the bounding box of the selected outputs' logical
+// rectangles (xdg-output layout). Each output renders its own slice of that
+// canvas at its own pixel density, so mixed-scale setups still line up at the
+// seams in logical space.
+
+use std::collections::HashMap;
+
+use gesso_core::Viewport;
+
+use crate::daemon::types::PersistedSpan;
+
+/// Compute the span layout for `selected`. Outputs not found in `outputs`
+/// are skipped. Returns an empty map when nothing is selected.
+pub fn span_layout(
+    outputs:  &[gesso_wl::OutputInfo],
+    selected: &[String],
+) -> HashMap<String, PersistedSpan> {
+    let picked: Vec<&gesso_wl::OutputInfo> = outputs
+        .iter()
+        .filter(|o| selected.contains(&o.name))
+        .collect();
+
+    let mut spans = HashMap::with_capacity(picked.len());
+    if picked.is_empty() {
+        return spans;
+    }
+
+    let min_x = picked.iter().map(|o| o.x).min().unwrap_or(0);
+    let min_y = picked.iter().map(|o| o.y).min().unwrap_or(0);
+    let max_x = picked.iter().map(|o| o.x + o.logical_width as i32).max().unwrap_or(0);
+    let max_y = picked.iter().map(|o| o.y + o.logical_height as i32).max().unwrap_or(0);
+
+    let canvas_width  = (max_x - min_x).max(1) as u32;
+    let canvas_height = (max_y - min_y).max(1) as u32;
+
+    for o in picked {
+        spans.insert(
+            o.name.clone(),
+            PersistedSpan {
+                canvas_width,
+                canvas_height,
+                x:      o.x - min_x,
+                y:      o.y - min_y,
+                width:  o.logical_width.max(1),
+                height: o.logical_height.max(1),
+            },
+        );
+    }
+
+    spans
+}
+
+/// Map a (logical) span entry to this output's physical-pixel viewport.
+/// `None` is the ordinary per-output case.
+pub fn viewport_for(out: &gesso_wl::OutputInfo, span: Option<&PersistedSpan>) -> Viewport {
+    let Some(sp) = span else {
+        return Viewport::full(out.width, out.height);
+    };
+
+    let fx = out.width  as f64 / sp.width.max(1)  as f64;
+    let fy = out.height as f64 / sp.height.max(1) as f64;
+
+    Viewport {
+        canvas_width:  ((sp.canvas_width  as f64 * fx).round() as u32).max(out.width),
+        canvas_height: ((sp.canvas_height as f64 * fy).round() as u32).max(out.height),
+        x: (sp.x as f64 * fx).round() as i32,
+        y: (sp.y as f64 * fy).round() as i32,
+    }
+}
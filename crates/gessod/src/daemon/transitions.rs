@@ -1,7 +1,13 @@
 // Author: Dustin Pilgrim
 // License: MIT
 
-use gesso_core::{Transition as CoreTransition, WaveDir as CoreWaveDir};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use gesso_core::{
+    Coord as CoreCoord, DissolveNoise as CoreDissolveNoise, DropOrigin as CoreDropOrigin,
+    Easing as CoreEasing, SlideDir as CoreSlideDir, Transition as CoreTransition,
+    WaveDir as CoreWaveDir,
+};
 use gesso_ipc::protocol as ipc;
 
 use crate::daemon::types::PersistedTransition;
@@ -15,31 +21,203 @@ pub const DEFAULT_DROP_SEED: u32 = 0;
 
 pub const DEFAULT_FADE_MS: u32 = 350;
 
+pub const DEFAULT_PIXELATE_BLOCK_PX: u16 = 48;
+
+pub const DEFAULT_DISSOLVE_GRAIN_PX: u16 = 2;
+
+/// Reject transitions the renderer can't make sense of.
+pub fn validate_transition(t: &ipc::Transition) -> Result<(), String> {
+    if matches!(t, ipc::Transition::Wave { dir: ipc::WaveDir::Angle(a), .. } if !a.is_finite()) {
+        return Err("wave angle must be a number".into());
+    }
+    if let ipc::Transition::Drop { origin: ipc::DropOrigin::At { x, y }, .. } = t {
+        let bad = |c: &ipc::Coord| matches!(c, ipc::Coord::Fraction(f) if !(0.0..=1.0).contains(f));
+        if bad(x) || bad(y) {
+            return Err("drop origin fractions must be between 0 and 1".into());
+        }
+    }
+
+    let easing = match t {
+        ipc::Transition::None => None,
+        ipc::Transition::Drop { easing, .. }
+        | ipc::Transition::Fade { easing, .. }
+        | ipc::Transition::Wave { easing, .. }
+        | ipc::Transition::Slide { easing, .. }
+        | ipc::Transition::Cover { easing, .. }
+        | ipc::Transition::Pixelate { easing, .. }
+        | ipc::Transition::Dissolve { easing, .. } => *easing,
+    };
+    match easing {
+        Some(ipc::Easing::CubicBezier { x1, y1, x2, y2 })
+            if !((0.0..=1.0).contains(&x1) && (0.0..=1.0).contains(&x2) && y1.is_finite() && y2.is_finite()) =>
+        {
+            Err("cubic-bezier needs x values within 0..1 and finite y values".into())
+        }
+        _ => Ok(()),
+    }
+}
+
+// Durations for a transition sent without one (duration_ms 0), by kind.
+// Colours and gradients change less on screen, so theirs run a bit shorter.
+const IMG_DROP_MS: u32 = 2300;
+const IMG_FADE_MS: u32 = 950;
+const IMG_WAVE_MS: u32 = 1750;
+const IMG_SLIDE_MS: u32 = 1200;
+const IMG_COVER_MS: u32 = 1100;
+const IMG_PIXELATE_MS: u32 = 1100;
+const IMG_DISSOLVE_MS: u32 = 1200;
+const COL_DROP_MS: u32 = 1700;
+const COL_FADE_MS: u32 = 1100;
+const COL_WAVE_MS: u32 = 1700;
+const COL_SLIDE_MS: u32 = 1000;
+const COL_COVER_MS: u32 = 950;
+const COL_PIXELATE_MS: u32 = 900;
+const COL_DISSOLVE_MS: u32 = 1000;
+
+/// Modes that move less of the image on screen get a slightly quicker change.
+fn mode_nudge_ms(mode: ipc::Mode) -> i32 {
+    match mode {
+        ipc::Mode::Fill    =>    0,
+        ipc::Mode::Fit     =>  -60,
+        ipc::Mode::FitBlur =>  -60,
+        ipc::Mode::Center  =>  -60,
+        ipc::Mode::Stretch =>  -40,
+        ipc::Mode::Tile    => -120,
+    }
+}
+
+/// Fill in a duration left at 0. `image_mode` is the resolved mode for an
+/// image target, None for colours and gradients.
+pub fn with_default_duration(mut t: ipc::Transition, image_mode: Option<ipc::Mode>) -> ipc::Transition {
+    let slot = match &mut t {
+        ipc::Transition::None                         => None,
+        ipc::Transition::Drop { duration_ms, .. }     => Some((duration_ms, IMG_DROP_MS, COL_DROP_MS)),
+        ipc::Transition::Fade { duration_ms, .. }     => Some((duration_ms, IMG_FADE_MS, COL_FADE_MS)),
+        ipc::Transition::Wave { duration_ms, .. }     => Some((duration_ms, IMG_WAVE_MS, COL_WAVE_MS)),
+        ipc::Transition::Slide { duration_ms, .. }    => Some((duration_ms, IMG_SLIDE_MS, COL_SLIDE_MS)),
+        ipc::Transition::Cover { duration_ms, .. }    => Some((duration_ms, IMG_COVER_MS, COL_COVER_MS)),
+        ipc::Transition::Pixelate { duration_ms, .. } => Some((duration_ms, IMG_PIXELATE_MS, COL_PIXELATE_MS)),
+        ipc::Transition::Dissolve { duration_ms, .. } => Some((duration_ms, IMG_DISSOLVE_MS, COL_DISSOLVE_MS)),
+    };
+    if let Some((ms, img, col)) = slot.filter(|(ms, ..)| **ms == 0) {
+        *ms = match image_mode {
+            Some(mode) => (img as i32 + mode_nudge_ms(mode)).max(160) as u32,
+            None       => col,
+        };
+    }
+    t
+}
+
 fn steps_u16_from_opt(s: Option<u16>) -> u16 {
     s.unwrap_or(0)
 }
 
+/// Seed for a random Drop origin or an unseeded Dissolve. Drawn once per
+/// request so every output in a span agrees on the pattern.
+pub fn request_seed() -> u32 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos() ^ d.as_secs() as u32)
+        .unwrap_or(0)
+}
+
+fn to_core_coord(c: ipc::Coord) -> CoreCoord {
+    match c {
+        ipc::Coord::Px(px)      => CoreCoord::Px(px),
+        ipc::Coord::Fraction(f) => CoreCoord::Fraction(f),
+    }
+}
+
+fn to_core_drop_origin(o: ipc::DropOrigin) -> CoreDropOrigin {
+    match o {
+        ipc::DropOrigin::Centre      => CoreDropOrigin::Centre,
+        ipc::DropOrigin::At { x, y } => CoreDropOrigin::At { x: to_core_coord(x), y: to_core_coord(y) },
+        ipc::DropOrigin::Random      => CoreDropOrigin::Random,
+    }
+}
+
+fn to_core_noise(n: ipc::DissolveNoise) -> CoreDissolveNoise {
+    match n {
+        ipc::DissolveNoise::White => CoreDissolveNoise::White,
+        ipc::DissolveNoise::Blue  => CoreDissolveNoise::Blue,
+    }
+}
+
+fn to_core_easing(e: ipc::Easing) -> CoreEasing {
+    match e {
+        ipc::Easing::Linear     => CoreEasing::Linear,
+        ipc::Easing::InQuad     => CoreEasing::InQuad,
+        ipc::Easing::OutQuad    => CoreEasing::OutQuad,
+        ipc::Easing::InOutQuad  => CoreEasing::InOutQuad,
+        ipc::Easing::InCubic    => CoreEasing::InCubic,
+        ipc::Easing::OutCubic   => CoreEasing::OutCubic,
+        ipc::Easing::InOutCubic => CoreEasing::InOutCubic,
+        ipc::Easing::InExpo     => CoreEasing::InExpo,
+        ipc::Easing::OutExpo    => CoreEasing::OutExpo,
+        ipc::Easing::InOutExpo  => CoreEasing::InOutExpo,
+        ipc::Easing::CubicBezier { x1, y1, x2, y2 } => CoreEasing::CubicBezier { x1, y1, x2, y2 },
+    }
+}
+
+fn to_core_wave_dir(d: &ipc::WaveDir) -> CoreWaveDir {
+    match d {
+        ipc::WaveDir::Left     => CoreWaveDir::Left,
+        ipc::WaveDir::Right    => CoreWaveDir::Right,
+        ipc::WaveDir::Top      => CoreWaveDir::Top,
+        ipc::WaveDir::Bottom   => CoreWaveDir::Bottom,
+        ipc::WaveDir::Angle(a) => CoreWaveDir::Angle(a.rem_euclid(360.0)),
+    }
+}
+
+fn to_core_slide_dir(d: ipc::SlideDir) -> CoreSlideDir {
+    match d {
+        ipc::SlideDir::Up    => CoreSlideDir::Up,
+        ipc::SlideDir::Down  => CoreSlideDir::Down,
+        ipc::SlideDir::Left  => CoreSlideDir::Left,
+        ipc::SlideDir::Right => CoreSlideDir::Right,
+    }
+}
+
 pub fn persisted_transition_from_ipc(t: ipc::Transition) -> PersistedTransition {
     match t {
         ipc::Transition::None => PersistedTransition::None,
 
-        ipc::Transition::Drop { duration_ms, steps } => PersistedTransition::Drop {
+        ipc::Transition::Drop { duration_ms, steps, origin, softness_px, easing } => PersistedTransition::Drop {
             duration_ms,
-            softness_px: Some(DEFAULT_DROP_SOFTNESS_PX),
+            softness_px: Some(softness_px.unwrap_or(DEFAULT_DROP_SOFTNESS_PX)),
             seed: Some(DEFAULT_DROP_SEED),
             steps,
+            origin: Some(origin),
+            easing,
         },
 
-        ipc::Transition::Fade { duration_ms, steps } => PersistedTransition::Fade { duration_ms, steps },
+        ipc::Transition::Fade { duration_ms, steps, easing } => PersistedTransition::Fade { duration_ms, steps, easing },
 
-        ipc::Transition::Wave { duration_ms, dir, steps } => PersistedTransition::Wave {
+        ipc::Transition::Wave { duration_ms, dir, steps, easing } => PersistedTransition::Wave {
             duration_ms,
             wave_from: dir,
             softness_px: Some(DEFAULT_WAVE_SOFTNESS_PX),
             amplitude_px: Some(DEFAULT_WAVE_AMPLITUDE_PX),
             wavelength_px: Some(DEFAULT_WAVE_WAVELENGTH_PX),
             steps,
+            easing,
         },
+
+        ipc::Transition::Slide { duration_ms, dir, steps, easing } => {
+            PersistedTransition::Slide { duration_ms, dir, steps, easing }
+        }
+
+        ipc::Transition::Cover { duration_ms, dir, steps, easing } => {
+            PersistedTransition::Cover { duration_ms, dir, steps, easing }
+        }
+
+        ipc::Transition::Pixelate { duration_ms, max_block_px, steps, easing } => {
+            PersistedTransition::Pixelate { duration_ms, max_block_px, steps, easing }
+        }
+
+        ipc::Transition::Dissolve { duration_ms, grain_px, noise, seed, steps, easing } => {
+            PersistedTransition::Dissolve { duration_ms, grain_px, noise, seed, steps, easing }
+        }
     }
 }
 
@@ -47,19 +225,56 @@ pub fn ipc_transition_from_persisted(t: &PersistedTransition) -> ipc::Transition
     match t {
         PersistedTransition::None => ipc::Transition::None,
 
-        PersistedTransition::Drop { duration_ms, steps, .. } => {
-            ipc::Transition::Drop { duration_ms: *duration_ms, steps: *steps }
-        }
+        PersistedTransition::Drop { duration_ms, steps, origin, softness_px, easing, .. } => ipc::Transition::Drop {
+            duration_ms: *duration_ms,
+            steps: *steps,
+            origin: origin.unwrap_or_default(),
+            softness_px: *softness_px,
+            easing: *easing,
+        },
 
-        PersistedTransition::Fade { duration_ms, steps } => {
-            ipc::Transition::Fade { duration_ms: *duration_ms, steps: *steps }
+        PersistedTransition::Fade { duration_ms, steps, easing } => {
+            ipc::Transition::Fade { duration_ms: *duration_ms, steps: *steps, easing: *easing }
         }
 
-        PersistedTransition::Wave { duration_ms, wave_from, steps, .. } => ipc::Transition::Wave {
+        PersistedTransition::Wave { duration_ms, wave_from, steps, easing, .. } => ipc::Transition::Wave {
             duration_ms: *duration_ms,
             dir: wave_from.clone(),
             steps: *steps,
+            easing: *easing,
+        },
+
+        PersistedTransition::Slide { duration_ms, dir, steps, easing } => ipc::Transition::Slide {
+            duration_ms: *duration_ms,
+            dir: *dir,
+            steps: *steps,
+            easing: *easing,
+        },
+
+        PersistedTransition::Cover { duration_ms, dir, steps, easing } => ipc::Transition::Cover {
+            duration_ms: *duration_ms,
+            dir: *dir,
+            steps: *steps,
+            easing: *easing,
         },
+
+        PersistedTransition::Pixelate { duration_ms, max_block_px, steps, easing } => ipc::Transition::Pixelate {
+            duration_ms: *duration_ms,
+            max_block_px: *max_block_px,
+            steps: *steps,
+            easing: *easing,
+        },
+
+        PersistedTransition::Dissolve { duration_ms, grain_px, noise, seed, steps, easing } => {
+            ipc::Transition::Dissolve {
+                duration_ms: *duration_ms,
+                grain_px: *grain_px,
+                noise: *noise,
+                seed: *seed,
+                steps: *steps,
+                easing: *easing,
+            }
+        }
     }
 }
 
@@ -67,16 +282,19 @@ pub fn to_core_transition_persisted(t: &PersistedTransition) -> CoreTransition {
     match t {
         PersistedTransition::None => CoreTransition::None,
 
-        PersistedTransition::Drop { duration_ms, softness_px, seed, steps } => CoreTransition::Drop {
+        PersistedTransition::Drop { duration_ms, softness_px, seed, steps, origin, easing } => CoreTransition::Drop {
             duration_ms: *duration_ms,
             softness_px: softness_px.unwrap_or(DEFAULT_DROP_SOFTNESS_PX),
             seed: seed.unwrap_or(DEFAULT_DROP_SEED),
             steps: steps_u16_from_opt(*steps),
+            origin: to_core_drop_origin(origin.unwrap_or_default()),
+            easing: easing.map(to_core_easing),
         },
 
-        PersistedTransition::Fade { duration_ms, steps } => CoreTransition::Fade {
+        PersistedTransition::Fade { duration_ms, steps, easing } => CoreTransition::Fade {
             duration_ms: *duration_ms,
             steps: steps_u16_from_opt(*steps),
+            easing: easing.map(to_core_easing),
         },
 
         PersistedTransition::Wave {
@@ -86,46 +304,111 @@ pub fn to_core_transition_persisted(t: &PersistedTransition) -> CoreTransition {
             amplitude_px,
             wavelength_px,
             steps,
+            easing,
         } => CoreTransition::Wave {
             duration_ms: *duration_ms,
-            dir: match wave_from {
-                ipc::WaveDir::Left => CoreWaveDir::Left,
-                ipc::WaveDir::Right => CoreWaveDir::Right,
-            },
+            dir: to_core_wave_dir(wave_from),
             softness_px: softness_px.unwrap_or(DEFAULT_WAVE_SOFTNESS_PX),
             amplitude_px: amplitude_px.unwrap_or(DEFAULT_WAVE_AMPLITUDE_PX),
             wavelength_px: wavelength_px.unwrap_or(DEFAULT_WAVE_WAVELENGTH_PX),
             steps: steps_u16_from_opt(*steps),
+            easing: easing.map(to_core_easing),
+        },
+
+        PersistedTransition::Slide { duration_ms, dir, steps, easing } => CoreTransition::Slide {
+            duration_ms: *duration_ms,
+            dir: to_core_slide_dir(*dir),
+            steps: steps_u16_from_opt(*steps),
+            easing: easing.map(to_core_easing),
+        },
+
+        PersistedTransition::Cover { duration_ms, dir, steps, easing } => CoreTransition::Cover {
+            duration_ms: *duration_ms,
+            dir: to_core_slide_dir(*dir),
+            steps: steps_u16_from_opt(*steps),
+            easing: easing.map(to_core_easing),
+        },
+
+        PersistedTransition::Pixelate { duration_ms, max_block_px, steps, easing } => CoreTransition::Pixelate {
+            duration_ms: *duration_ms,
+            max_block_px: max_block_px.unwrap_or(DEFAULT_PIXELATE_BLOCK_PX),
+            steps: steps_u16_from_opt(*steps),
+            easing: easing.map(to_core_easing),
         },
+
+        PersistedTransition::Dissolve { duration_ms, grain_px, noise, seed, steps, easing } => {
+            CoreTransition::Dissolve {
+                duration_ms: *duration_ms,
+                seed: seed.unwrap_or(DEFAULT_DROP_SEED),
+                grain_px: grain_px.unwrap_or(DEFAULT_DISSOLVE_GRAIN_PX),
+                noise: to_core_noise(*noise),
+                steps: steps_u16_from_opt(*steps),
+                easing: easing.map(to_core_easing),
+            }
+        }
     }
 }
 
-pub fn to_core_transition(t: ipc::Transition) -> CoreTransition {
+/// `seed` is used where the request leaves the pattern to chance.
+pub fn to_core_transition(t: ipc::Transition, seed: u32) -> CoreTransition {
     match t {
         ipc::Transition::None => CoreTransition::None,
 
-        ipc::Transition::Drop { duration_ms, steps } => CoreTransition::Drop {
+        ipc::Transition::Drop { duration_ms, steps, origin, softness_px, easing } => CoreTransition::Drop {
             duration_ms,
-            softness_px: DEFAULT_DROP_SOFTNESS_PX,
-            seed: DEFAULT_DROP_SEED,
+            softness_px: softness_px.unwrap_or(DEFAULT_DROP_SOFTNESS_PX),
+            seed,
             steps: steps_u16_from_opt(steps),
+            origin: to_core_drop_origin(origin),
+            easing: easing.map(to_core_easing),
         },
 
-        ipc::Transition::Fade { duration_ms, steps } => CoreTransition::Fade {
+        ipc::Transition::Fade { duration_ms, steps, easing } => CoreTransition::Fade {
             duration_ms: if duration_ms == 0 { DEFAULT_FADE_MS } else { duration_ms },
             steps: steps_u16_from_opt(steps),
+            easing: easing.map(to_core_easing),
         },
 
-        ipc::Transition::Wave { duration_ms, dir, steps } => CoreTransition::Wave {
+        ipc::Transition::Wave { duration_ms, dir, steps, easing } => CoreTransition::Wave {
             duration_ms,
-            dir: match dir {
-                ipc::WaveDir::Left => CoreWaveDir::Left,
-                ipc::WaveDir::Right => CoreWaveDir::Right,
-            },
+            dir: to_core_wave_dir(&dir),
             softness_px: DEFAULT_WAVE_SOFTNESS_PX,
             amplitude_px: DEFAULT_WAVE_AMPLITUDE_PX,
             wavelength_px: DEFAULT_WAVE_WAVELENGTH_PX,
             steps: steps_u16_from_opt(steps),
+            easing: easing.map(to_core_easing),
         },
+
+        ipc::Transition::Slide { duration_ms, dir, steps, easing } => CoreTransition::Slide {
+            duration_ms,
+            dir: to_core_slide_dir(dir),
+            steps: steps_u16_from_opt(steps),
+            easing: easing.map(to_core_easing),
+        },
+
+        ipc::Transition::Cover { duration_ms, dir, steps, easing } => CoreTransition::Cover {
+            duration_ms,
+            dir: to_core_slide_dir(dir),
+            steps: steps_u16_from_opt(steps),
+            easing: easing.map(to_core_easing),
+        },
+
+        ipc::Transition::Pixelate { duration_ms, max_block_px, steps, easing } => CoreTransition::Pixelate {
+            duration_ms,
+            max_block_px: max_block_px.unwrap_or(DEFAULT_PIXELATE_BLOCK_PX),
+            steps: steps_u16_from_opt(steps),
+            easing: easing.map(to_core_easing),
+        },
+
+        ipc::Transition::Dissolve { duration_ms, grain_px, noise, seed: fixed, steps, easing } => {
+            CoreTransition::Dissolve {
+                duration_ms,
+                seed: fixed.unwrap_or(seed),
+                grain_px: grain_px.unwrap_or(DEFAULT_DISSOLVE_GRAIN_PX),
+                noise: to_core_noise(noise),
+                steps: steps_u16_from_opt(steps),
+                easing: easing.map(to_core_easing),
+            }
+        }
     }
 }
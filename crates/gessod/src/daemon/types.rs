@@ -5,9 +5,14 @@
 // Persisted State
 //
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct PersistedState {
     pub outputs: Vec<PersistedOutput>,
+    /// Applied to outputs with no saved entry (e.g. a monitor seen for the first time).
+    #[serde(default)]
+    pub default: Option<PersistedSet>,
+    #[serde(default)]
+    pub playlist: Option<PersistedPlaylist>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -22,6 +27,37 @@ pub struct PersistedSet {
     pub mode: Option<gesso_ipc::protocol::Mode>,
     pub bg_colour: Option<gesso_ipc::protocol::Rgb>,
     pub transition: PersistedTransition,
+    /// Present when this output shows its slice of a spanned image.
+    #[serde(default)]
+    pub span: Option<PersistedSpan>,
+    /// Fill / Center crop focus; None = centred.
+    #[serde(default)]
+    pub focus: Option<gesso_ipc::protocol::Focus>,
+    /// Fill zoom and crop shift; None = plain fill.
+    #[serde(default)]
+    pub zoom: Option<f32>,
+    #[serde(default)]
+    pub offset: Option<gesso_ipc::protocol::Offset>,
+    /// Ken Burns pan/zoom for stills; None = static.
+    #[serde(default)]
+    pub motion: Option<gesso_ipc::protocol::Motion>,
+    /// Skip ICC → sRGB conversion when decoding.
+    #[serde(default)]
+    pub ignore_icc: bool,
+    /// Animation loop override; None = the file's own count.
+    #[serde(default)]
+    pub loops: Option<gesso_ipc::protocol::Loop>,
+}
+
+/// One output's place in a spanned layout, in logical (xdg-output) units.
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub struct PersistedSpan {
+    pub canvas_width: u32,
+    pub canvas_height: u32,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -30,6 +66,7 @@ pub enum PersistedTarget {
     Unset,
     Colour { r: u8, g: u8, b: u8 },
     ImagePath { path: String },
+    Gradient(gesso_ipc::protocol::Gradient),
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -43,12 +80,18 @@ pub enum PersistedTransition {
         seed: Option<u32>,
         #[serde(default)]
         steps: Option<u16>,
+        #[serde(default)]
+        origin: Option<gesso_ipc::protocol::DropOrigin>,
+        #[serde(default)]
+        easing: Option<gesso_ipc::protocol::Easing>,
     },
 
     Fade {
         duration_ms: u32,
         #[serde(default)]
         steps: Option<u16>,
+        #[serde(default)]
+        easing: Option<gesso_ipc::protocol::Easing>,
     },
 
     Wave {
@@ -59,5 +102,69 @@ pub enum PersistedTransition {
         wavelength_px: Option<u16>,
         #[serde(default)]
         steps: Option<u16>,
+        #[serde(default)]
+        easing: Option<gesso_ipc::protocol::Easing>,
+    },
+
+    Slide {
+        duration_ms: u32,
+        dir: gesso_ipc::protocol::SlideDir,
+        #[serde(default)]
+        steps: Option<u16>,
+        #[serde(default)]
+        easing: Option<gesso_ipc::protocol::Easing>,
+    },
+
+    Cover {
+        duration_ms: u32,
+        dir: gesso_ipc::protocol::SlideDir,
+        #[serde(default)]
+        steps: Option<u16>,
+        #[serde(default)]
+        easing: Option<gesso_ipc::protocol::Easing>,
+    },
+
+    Pixelate {
+        duration_ms: u32,
+        max_block_px: Option<u16>,
+        #[serde(default)]
+        steps: Option<u16>,
+        #[serde(default)]
+        easing: Option<gesso_ipc::protocol::Easing>,
+    },
+
+    Dissolve {
+        duration_ms: u32,
+        grain_px: Option<u16>,
+        noise: gesso_ipc::protocol::DissolveNoise,
+        seed: Option<u32>,
+        #[serde(default)]
+        steps: Option<u16>,
+        #[serde(default)]
+        easing: Option<gesso_ipc::protocol::Easing>,
     },
 }
+
+//
+// Playlist
+//
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PersistedPlaylist {
+    pub outputs: gesso_ipc::protocol::OutputSel,
+    pub sources: Vec<String>,
+    /// Expanded image list the sequence indexes into.
+    pub items: Vec<String>,
+    pub sequence: Vec<usize>,
+    pub pos: usize,
+    pub interval_secs: u32,
+    pub order: gesso_ipc::protocol::PlaylistOrder,
+    /// None = follow config.toml.
+    pub mode: Option<gesso_ipc::protocol::Mode>,
+    pub bg_colour: Option<gesso_ipc::protocol::Rgb>,
+    pub transition: Option<PersistedTransition>,
+    #[serde(default)]
+    pub span: bool,
+    #[serde(default)]
+    pub paused: bool,
+}
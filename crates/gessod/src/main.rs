@@ -11,7 +11,7 @@ use std::thread;
 
 use eventline::info;
 
-use gesso_ipc::{bind, default_socket_path};
+use gesso_ipc::{bind, default_socket_path, EventHub};
 use gesso_ipc::protocol as ipc;
 
 // ── Allocator (jemalloc) ─────────────────────────────────────────────────────
@@ -176,7 +176,10 @@ fn main() -> anyhow::Result<()> {
     // ── 7. IPC server thread ──
     // 512 KB stack is ample for simple serialisation/deserialisation work.
     // The default 8 MB wastes ~7.5 MB of RSS unnecessarily.
-    let listener = bind(&sock)?;
+    // Event subscribers are parked by the IPC thread and fed by the render loop.
+    let hub        = EventHub::new();
+    let server_hub = hub.clone();
+    let listener   = bind(&sock)?;
     thread::Builder::new()
         .name("gessod-ipc".into())
         .stack_size(512 * 1024)
@@ -190,12 +193,12 @@ fn main() -> anyhow::Result<()> {
                     Err(_) => ipc::Response::Error { message: "daemon not running".into() },
                 }
             };
-            if let Err(e) = gesso_ipc::run_server(listener, handler) {
+            if let Err(e) = gesso_ipc::run_server(listener, server_hub, handler) {
                 eprintln!("ipc server error: {e}");
             }
         })
         .map_err(|e| anyhow::anyhow!("gessod: spawn ipc thread: {e}"))?;
 
     // ── 8. Render loop (main thread) ──
-    daemon::run(req_rx, resp_tx)
+    daemon::run(req_rx, resp_tx, hub)
 }